@@ -0,0 +1,272 @@
+//! Analytics post-processing — period-over-period deltas and anomaly detection.
+//!
+//! Pure functions over already-fetched `CfAnalytics`; no network access.
+
+use std::collections::HashMap;
+
+use crate::types::{AnomalyKind, CfAnalytics, CfAnomaly, CfComparison, CfDailyCount, CfDelta};
+
+/// Number of preceding slots (days or hours) that form the rolling baseline.
+const BASELINE_WINDOW: usize = 7;
+/// Minimum number of preceding slots required before a slot can be flagged.
+const MIN_BASELINE: usize = 3;
+/// How many standard deviations from the baseline mean count as an anomaly.
+const ANOMALY_THRESHOLD: f64 = 3.0;
+
+/// Build a delta between a current and a previous count.
+fn delta(key: String, current: u64, previous: u64) -> CfDelta {
+    CfDelta {
+        key,
+        current,
+        previous,
+        delta: current as i64 - previous as i64,
+        delta_pct: if previous == 0 {
+            None
+        } else {
+            Some((current as f64 - previous as f64) / previous as f64 * 100.0)
+        },
+    }
+}
+
+/// Pair up two keyed count lists, keeping every key present in either period.
+/// Sorted by current count, then previous count, both descending.
+fn merge_deltas(current: &[(String, u64)], previous: &[(String, u64)]) -> Vec<CfDelta> {
+    let prev: HashMap<&str, u64> = previous.iter().map(|(k, c)| (k.as_str(), *c)).collect();
+    let mut deltas: Vec<CfDelta> = current
+        .iter()
+        .map(|(k, c)| delta(k.clone(), *c, prev.get(k.as_str()).copied().unwrap_or(0)))
+        .collect();
+    for (k, c) in previous {
+        if !current.iter().any(|(ck, _)| ck == k) {
+            deltas.push(delta(k.clone(), 0, *c));
+        }
+    }
+    deltas.sort_by(|a, b| b.current.cmp(&a.current).then(b.previous.cmp(&a.previous)));
+    deltas
+}
+
+/// Per-window totals, keyed as they appear in `CfComparison::totals`.
+fn totals(a: &CfAnalytics) -> [(&'static str, u64); 6] {
+    let sum = |f: fn(&CfDailyCount) -> u64| a.daily_requests.iter().map(f).sum();
+    [
+        ("requests", a.total_requests),
+        ("uniques", sum(|d| d.uniques)),
+        ("bytes", sum(|d| d.bytes)),
+        ("cached_bytes", sum(|d| d.cached_bytes)),
+        ("cached_requests", sum(|d| d.cached_requests)),
+        ("threats", sum(|d| d.threats)),
+    ]
+}
+
+/// Compare two equal-length analytics windows: totals, top paths and countries.
+pub fn compare(current: &CfAnalytics, previous: &CfAnalytics) -> CfComparison {
+    let paths = |a: &CfAnalytics| -> Vec<(String, u64)> {
+        a.top_paths
+            .iter()
+            .map(|p| (p.path.clone(), p.count))
+            .collect()
+    };
+    let countries = |a: &CfAnalytics| -> Vec<(String, u64)> {
+        a.top_countries
+            .iter()
+            .map(|c| (c.country.clone(), c.count))
+            .collect()
+    };
+
+    CfComparison {
+        previous_period_start: previous
+            .daily_requests
+            .first()
+            .map(|d| d.date.clone())
+            .unwrap_or_default(),
+        previous_period_end: previous
+            .daily_requests
+            .last()
+            .map(|d| d.date.clone())
+            .unwrap_or_default(),
+        totals: totals(current)
+            .into_iter()
+            .zip(totals(previous))
+            .map(|((key, cur), (_, prev))| delta(key.to_string(), cur, prev))
+            .collect(),
+        paths: merge_deltas(&paths(current), &paths(previous)),
+        countries: merge_deltas(&countries(current), &countries(previous)),
+    }
+}
+
+/// Flag slots in `series` that deviate strongly from the rolling mean of the
+/// preceding `BASELINE_WINDOW` slots. `history` holds the slots immediately
+/// before `series` (e.g. the previous window) and only seeds the baseline.
+///
+/// The spread is floored at the Poisson noise (√mean) so that flat, low-traffic
+/// series don't flag every small wobble.
+pub fn detect_anomalies(series: &[CfDailyCount], history: &[CfDailyCount]) -> Vec<CfAnomaly> {
+    let counts: Vec<f64> = history
+        .iter()
+        .chain(series.iter())
+        .map(|d| d.count as f64)
+        .collect();
+    let offset = history.len();
+
+    let mut anomalies = Vec::new();
+    for (i, slot) in series.iter().enumerate() {
+        let idx = offset + i;
+        let window = &counts[idx.saturating_sub(BASELINE_WINDOW)..idx];
+        if window.len() < MIN_BASELINE {
+            continue;
+        }
+
+        let mean = window.iter().sum::<f64>() / window.len() as f64;
+        let variance = window.iter().map(|c| (c - mean).powi(2)).sum::<f64>() / window.len() as f64;
+        let spread = variance.sqrt().max(mean.sqrt()).max(1.0);
+        let score = (slot.count as f64 - mean) / spread;
+
+        if score.abs() >= ANOMALY_THRESHOLD {
+            anomalies.push(CfAnomaly {
+                date: slot.date.clone(),
+                count: slot.count,
+                baseline: mean,
+                score,
+                kind: if score > 0.0 {
+                    AnomalyKind::Spike
+                } else {
+                    AnomalyKind::Drop
+                },
+            });
+        }
+    }
+    anomalies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CfPathCount;
+
+    fn day(date: &str, count: u64) -> CfDailyCount {
+        CfDailyCount {
+            date: date.to_string(),
+            count,
+            uniques: count / 2,
+            bytes: count * 100,
+            cached_bytes: count * 40,
+            cached_requests: count / 4,
+            threats: 0,
+        }
+    }
+
+    fn series(counts: &[u64]) -> Vec<CfDailyCount> {
+        counts
+            .iter()
+            .enumerate()
+            .map(|(i, c)| day(&format!("2026-01-{:02}", i + 1), *c))
+            .collect()
+    }
+
+    fn window(daily: Vec<CfDailyCount>, paths: &[(&str, u64)]) -> CfAnalytics {
+        CfAnalytics {
+            period: String::new(),
+            total_requests: daily.iter().map(|d| d.count).sum(),
+            daily_requests: daily,
+            top_paths: paths
+                .iter()
+                .map(|(p, c)| CfPathCount {
+                    path: p.to_string(),
+                    count: *c,
+                })
+                .collect(),
+            top_countries: Vec::new(),
+            status_codes: Vec::new(),
+            browsers: Vec::new(),
+            comparison: None,
+            anomalies: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn delta_maths() {
+        let d = delta("x".to_string(), 150, 100);
+        assert_eq!(d.delta, 50);
+        assert_eq!(d.delta_pct, Some(50.0));
+
+        let d = delta("x".to_string(), 25, 100);
+        assert_eq!(d.delta, -75);
+        assert_eq!(d.delta_pct, Some(-75.0));
+    }
+
+    #[test]
+    fn zero_baseline_has_no_percentage() {
+        let d = delta("x".to_string(), 10, 0);
+        assert_eq!(d.delta, 10);
+        assert_eq!(d.delta_pct, None);
+    }
+
+    #[test]
+    fn compare_covers_every_total() {
+        let current = window(series(&[100, 300]), &[("/a", 10), ("/b", 5)]);
+        let previous = window(series(&[200]), &[("/a", 20), ("/c", 7)]);
+        let cmp = compare(&current, &previous);
+
+        let keys: Vec<&str> = cmp.totals.iter().map(|d| d.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            [
+                "requests",
+                "uniques",
+                "bytes",
+                "cached_bytes",
+                "cached_requests",
+                "threats"
+            ]
+        );
+        let requests = &cmp.totals[0];
+        assert_eq!((requests.current, requests.previous), (400, 200));
+        assert_eq!(requests.delta_pct, Some(100.0));
+        let bytes = &cmp.totals[2];
+        assert_eq!((bytes.current, bytes.previous), (40_000, 20_000));
+        assert_eq!(cmp.totals[5].delta_pct, None);
+
+        assert_eq!(cmp.previous_period_start, "2026-01-01");
+        assert_eq!(cmp.previous_period_end, "2026-01-01");
+
+        // Paths from both windows are kept, sorted by current count.
+        let paths: Vec<(&str, u64, u64)> = cmp
+            .paths
+            .iter()
+            .map(|d| (d.key.as_str(), d.current, d.previous))
+            .collect();
+        assert_eq!(paths, [("/a", 10, 20), ("/b", 5, 0), ("/c", 0, 7)]);
+    }
+
+    #[test]
+    fn single_day_has_no_baseline() {
+        assert!(detect_anomalies(&series(&[1_000]), &[]).is_empty());
+    }
+
+    #[test]
+    fn flat_series_has_no_anomalies() {
+        assert!(detect_anomalies(&series(&[50; 14]), &[]).is_empty());
+        assert!(detect_anomalies(&series(&[0; 14]), &[]).is_empty());
+    }
+
+    #[test]
+    fn spike_and_drop_are_flagged() {
+        let anomalies = detect_anomalies(&series(&[100, 100, 100, 100, 1_000, 100]), &[]);
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].date, "2026-01-05");
+        assert_eq!(anomalies[0].kind, AnomalyKind::Spike);
+        assert_eq!(anomalies[0].baseline, 100.0);
+
+        let anomalies = detect_anomalies(&series(&[400, 400, 400, 400, 0]), &[]);
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].kind, AnomalyKind::Drop);
+    }
+
+    #[test]
+    fn history_seeds_the_baseline() {
+        let history = series(&[100, 100, 100]);
+        let anomalies = detect_anomalies(&series(&[1_000]), &history);
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].count, 1_000);
+    }
+}
@@ -0,0 +1,332 @@
+//! TypeScript bindings for the IPC surface, generated from the Rust side.
+//!
+//! `src/lib/bindings.ts` holds every type reachable from `types()` and a typed
+//! wrapper for each command in `generate_handler!`, read from its signature in
+//! `commands.rs`. The test fails when the checked-in file is stale; regenerate
+//! it with `UPDATE_BINDINGS=1 cargo test bindings`.
+
+use specta::TypeCollection;
+use specta_typescript::{BigIntExportBehavior, Typescript};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use syn::{Expr, FnArg, GenericArgument, Item, Lit, Meta, Pat, PathArguments, ReturnType, Type};
+
+use crate::error::AppError;
+use crate::types::*;
+
+const HEADER: &str =
+    "// Generated from src-tauri by `UPDATE_BINDINGS=1 cargo test bindings`. Do not edit.
+
+import { invoke } from \"./invoke\";
+";
+
+/// Argument types Tauri injects instead of deserializing them from the frontend.
+const INJECTED: &[&str] = &["AppHandle", "State", "Window", "WebviewWindow"];
+
+/// Types the commands take or return; everything they reference is included.
+fn types() -> TypeCollection {
+    let mut types = TypeCollection::default();
+    types
+        .register::<AppError>()
+        .register::<AppConfig>()
+        .register::<SecretsStatus>()
+        .register::<RepoConfig>()
+        .register::<ContentEntry>()
+        .register::<CreateEntryArgs>()
+        .register::<FileContents>()
+        .register::<WriteResult>()
+        .register::<SnapshotInfo>()
+        .register::<HealthStatus>()
+        .register::<CfDeploymentInfo>()
+        .register::<CfAnalytics>()
+        .register::<CfWebAnalytics>()
+        .register::<AnalyticsExportSource>()
+        .register::<ExportFormat>()
+        .register::<OperationProgress>()
+        .register::<OperationQueue>()
+        .register::<PendingPush>()
+        .register::<PushQueueChanged>()
+        .register::<ContentChange>()
+        .register::<HeadMoved>()
+        .register::<ScopeChange>()
+        .register::<MigrationPlan>()
+        .register::<MigrationResult>()
+        .register::<BulkEdit>()
+        .register::<BulkEditChange>()
+        .register::<BulkEditResult>()
+        .register::<Taxonomy>()
+        .register::<TagMerge>();
+    types
+}
+
+struct Arg {
+    name: String,
+    ts: String,
+    optional: bool,
+}
+
+struct Command {
+    name: String,
+    docs: Vec<String>,
+    args: Vec<Arg>,
+    returns: String,
+}
+
+/// Commands registered in `lib.rs`, in registration order.
+fn registered() -> Vec<String> {
+    let (_, handlers) = include_str!("lib.rs")
+        .split_once("generate_handler![")
+        .expect("generate_handler! not found in lib.rs");
+    let (list, _) = handlers
+        .split_once(']')
+        .expect("unterminated generate_handler!");
+    list.split(',')
+        .map(|c| c.trim().trim_start_matches("commands::").to_string())
+        .filter(|c| !c.is_empty())
+        .collect()
+}
+
+fn commands(known: &BTreeSet<String>) -> Vec<Command> {
+    let file = syn::parse_file(include_str!("commands.rs")).expect("commands.rs does not parse");
+    let mut found = Vec::new();
+    for name in registered() {
+        let func = file
+            .items
+            .iter()
+            .find_map(|item| match item {
+                Item::Fn(f) if f.sig.ident == name => Some(f),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("command `{name}` is not defined in commands.rs"));
+
+        let mut args: Vec<Arg> = func
+            .sig
+            .inputs
+            .iter()
+            .filter_map(|input| match input {
+                FnArg::Typed(arg) => Some(arg),
+                FnArg::Receiver(_) => None,
+            })
+            .filter(|arg| !INJECTED.contains(&last_ident(&arg.ty).as_str()))
+            .map(|arg| {
+                let Pat::Ident(ident) = &*arg.pat else {
+                    panic!("command `{name}` has a non-identifier argument");
+                };
+                Arg {
+                    name: camel_case(&ident.ident.to_string()),
+                    ts: ts_type(&arg.ty, known, &name),
+                    optional: last_ident(&arg.ty) == "Option",
+                }
+            })
+            .collect();
+        // Only trailing `Option`s can be left out of the wrapper's call.
+        let mut trailing = true;
+        for arg in args.iter_mut().rev() {
+            trailing &= arg.optional;
+            arg.optional = trailing;
+        }
+
+        let returns = match &func.sig.output {
+            ReturnType::Default => "void".to_string(),
+            ReturnType::Type(_, ty) if last_ident(ty) == "Result" => {
+                ts_type(generic_args(ty)[0], known, &name)
+            }
+            ReturnType::Type(_, ty) => ts_type(ty, known, &name),
+        };
+
+        found.push(Command {
+            docs: docs(&func.attrs),
+            name,
+            args,
+            returns,
+        });
+    }
+    found
+}
+
+fn docs(attrs: &[syn::Attribute]) -> Vec<String> {
+    attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(nv) if nv.path.is_ident("doc") => match &nv.value {
+                Expr::Lit(lit) => match &lit.lit {
+                    Lit::Str(s) => {
+                        let line = s.value();
+                        Some(line.strip_prefix(' ').unwrap_or(&line).to_string())
+                    }
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect()
+}
+
+fn last_ident(ty: &Type) -> String {
+    match ty {
+        Type::Path(p) => p
+            .path
+            .segments
+            .last()
+            .map(|s| s.ident.to_string())
+            .unwrap_or_default(),
+        Type::Reference(r) => last_ident(&r.elem),
+        _ => String::new(),
+    }
+}
+
+fn generic_args(ty: &Type) -> Vec<&Type> {
+    let Type::Path(p) = ty else {
+        return Vec::new();
+    };
+    match p.path.segments.last().map(|s| &s.arguments) {
+        Some(PathArguments::AngleBracketed(args)) => args
+            .args
+            .iter()
+            .filter_map(|a| match a {
+                GenericArgument::Type(t) => Some(t),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// The TypeScript spelling of a command argument or return type.
+fn ts_type(ty: &Type, known: &BTreeSet<String>, command: &str) -> String {
+    if let Type::Tuple(t) = ty {
+        assert!(t.elems.is_empty(), "command `{command}` uses a tuple type");
+        return "void".to_string();
+    }
+    if let Type::Reference(r) = ty {
+        return ts_type(&r.elem, known, command);
+    }
+    let name = last_ident(ty);
+    let inner = || ts_type(generic_args(ty)[0], known, command);
+    match name.as_str() {
+        "String" | "str" | "PathBuf" | "Path" => "string".to_string(),
+        "bool" => "boolean".to_string(),
+        "u8" | "u16" | "u32" | "u64" | "usize" | "i8" | "i16" | "i32" | "i64" | "isize" | "f32"
+        | "f64" => "number".to_string(),
+        "Option" => format!("{} | null", inner()),
+        "Vec" => match inner() {
+            t if t.contains(' ') => format!("({t})[]"),
+            t => format!("{t}[]"),
+        },
+        "Box" => inner(),
+        _ => {
+            assert!(
+                known.contains(&name),
+                "command `{command}` uses `{name}`, which is not reachable from bindings::types()"
+            );
+            name
+        }
+    }
+}
+
+fn camel_case(snake: &str) -> String {
+    let mut out = String::new();
+    let mut upper = false;
+    for c in snake.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            out.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn js_doc(lines: &[String], out: &mut String) {
+    match lines {
+        [] => {}
+        [line] => out.push_str(&format!("/** {line} */\n")),
+        _ => {
+            out.push_str("/**\n");
+            for line in lines {
+                out.push_str(format!(" * {line}").trim_end());
+                out.push('\n');
+            }
+            out.push_str(" */\n");
+        }
+    }
+}
+
+/// The full contents of `src/lib/bindings.ts`.
+fn render() -> String {
+    let types = types();
+    let ts = Typescript::default().bigint(BigIntExportBehavior::Number);
+    let mut named: Vec<_> = types.into_iter().map(|(_, ndt)| ndt).collect();
+    named.sort_by(|a, b| a.name().cmp(b.name()));
+    let known: BTreeSet<String> = named.iter().map(|ndt| ndt.name().to_string()).collect();
+
+    let mut out = HEADER.to_string();
+    out.push_str("\n// Types\n");
+    for ndt in &named {
+        let decl = specta_typescript::export_named_datatype(&ts, ndt, &types)
+            .unwrap_or_else(|e| panic!("failed to export {}: {e}", ndt.name()));
+        out.push('\n');
+        out.push_str(&decl);
+        out.push('\n');
+    }
+
+    out.push_str("\n// Commands\n");
+    for command in commands(&known) {
+        let params: Vec<String> = command
+            .args
+            .iter()
+            .map(|a| {
+                let mark = if a.optional { "?" } else { "" };
+                format!("{}{mark}: {}", a.name, a.ts)
+            })
+            .collect();
+        let payload: Vec<String> = command
+            .args
+            .iter()
+            .map(|a| match a.optional {
+                true => format!("{0}: {0} ?? null", a.name),
+                false => a.name.clone(),
+            })
+            .collect();
+
+        out.push('\n');
+        js_doc(&command.docs, &mut out);
+        out.push_str(&format!(
+            "export function {}({}): Promise<{}> {{\n",
+            camel_case(&command.name),
+            params.join(", "),
+            command.returns
+        ));
+        if payload.is_empty() {
+            out.push_str(&format!("  return invoke(\"{}\");\n", command.name));
+        } else {
+            out.push_str(&format!(
+                "  return invoke(\"{}\", {{ {} }});\n",
+                command.name,
+                payload.join(", ")
+            ));
+        }
+        out.push_str("}\n");
+    }
+    out
+}
+
+#[test]
+fn bindings_are_up_to_date() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../src/lib/bindings.ts");
+    let generated = render();
+    if std::env::var_os("UPDATE_BINDINGS").is_some() {
+        fs::write(&path, &generated).expect("failed to write bindings.ts");
+        return;
+    }
+    let current = fs::read_to_string(&path).unwrap_or_default();
+    assert!(
+        current == generated,
+        "src/lib/bindings.ts is stale; regenerate it with `UPDATE_BINDINGS=1 cargo test bindings`"
+    );
+}
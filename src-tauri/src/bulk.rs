@@ -0,0 +1,524 @@
+//! Bulk frontmatter edits — a small operation language applied to every
+//! entry matching a filter, previewed as diffs and applied in one commit.
+//!
+//! Edits are line based, like the rest of `frontmatter`: a key's block is its
+//! `key:` line plus the indented or `- ` lines that follow it, and everything
+//! outside the blocks an op touches is left byte for byte. Tag ops only edit
+//! inline `tags: [...]` lists, the form the panel itself writes.
+
+use regex::Regex;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use crate::content::{self, Rewrite};
+use crate::diff;
+use crate::error::AppError;
+use crate::frontmatter::{assemble_file, format_yaml_tags, split_frontmatter};
+use crate::git;
+use crate::index;
+use crate::operations::Operation;
+use crate::repo_config::{self, commit_message};
+use crate::security::escape_yaml_string;
+use crate::types::{
+    BulkEdit, BulkEditChange, BulkEditResult, BulkOp, ContentEntry, EntryFilter, MutationKind,
+};
+
+/// Keys a bulk edit may name: plain identifiers, as the panel's own fields are.
+static KEY_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[A-Za-z_][A-Za-z0-9_-]*$").unwrap());
+
+/// Keys owned by publishing; editing them in bulk would desync `hasChanged`.
+const RESERVED_KEYS: &[&str] = &["isDraft", "publishedHash"];
+
+/// An op checked and ready to run, with its regex compiled.
+enum Step<'a> {
+    Op(&'a BulkOp),
+    Map {
+        key: &'a str,
+        pattern: Regex,
+        replacement: &'a str,
+    },
+}
+
+fn compile(ops: &[BulkOp]) -> Result<Vec<Step<'_>>, AppError> {
+    if ops.is_empty() {
+        return Err(AppError::invalid_input(
+            "A bulk edit needs at least one operation.",
+        ));
+    }
+    ops.iter()
+        .map(|op| {
+            match op {
+                BulkOp::RenameKey { from, to } => {
+                    check_key(from)?;
+                    check_key(to)?;
+                }
+                BulkOp::SetKey { key, value } => {
+                    check_key(key)?;
+                    if value.trim().is_empty() || value.contains(['\n', '\r']) {
+                        return Err(AppError::invalid_input(format!(
+                            "The value for \"{key}\" must be a single line of YAML."
+                        )));
+                    }
+                }
+                BulkOp::RemoveKey { key } => check_key(key)?,
+                BulkOp::MapValues {
+                    key,
+                    pattern,
+                    replacement,
+                } => {
+                    check_key(key)?;
+                    let pattern = Regex::new(pattern).map_err(|e| {
+                        AppError::invalid_input(format!("Invalid pattern for \"{key}\": {e}"))
+                    })?;
+                    return Ok(Step::Map {
+                        key,
+                        pattern,
+                        replacement,
+                    });
+                }
+                BulkOp::AddTags { tags } | BulkOp::RemoveTags { tags } => {
+                    for tag in tags {
+                        check_tag(tag)?;
+                    }
+                }
+                // Only the new name is checked, so malformed tags can be renamed away.
+                BulkOp::RenameTags { from, to } => {
+                    check_tag(to)?;
+                    if from.iter().all(|f| f == to) {
+                        return Err(AppError::invalid_input(format!(
+                            "Choose tags to rename to \"{to}\"."
+                        )));
+                    }
+                }
+            }
+            Ok(Step::Op(op))
+        })
+        .collect()
+}
+
+fn check_key(key: &str) -> Result<(), AppError> {
+    if !KEY_RE.is_match(key) {
+        return Err(AppError::invalid_input(format!(
+            "\"{key}\" is not a valid frontmatter key."
+        )));
+    }
+    if RESERVED_KEYS.contains(&key) {
+        return Err(AppError::invalid_input(format!(
+            "\"{key}\" is managed by publishing and can't be edited in bulk."
+        )));
+    }
+    Ok(())
+}
+
+/// Tags are stored in inline lists, which `get_yaml_tags` splits on commas.
+pub fn check_tag(tag: &str) -> Result<(), AppError> {
+    if tag.trim().is_empty() || tag.trim() != tag || tag.contains([',', '\n', '\r']) {
+        return Err(AppError::invalid_input(format!(
+            "\"{tag}\" is not a valid tag: tags are non-empty, without commas or \
+             surrounding spaces."
+        )));
+    }
+    Ok(())
+}
+
+/// Whether `entry` satisfies every condition set in `filter`.
+pub fn matches(filter: &EntryFilter, entry: &ContentEntry) -> bool {
+    let created = entry.created_date.as_str();
+    // Bounds compare at their own precision, so `2024-12-31` includes that whole day.
+    let within = |bound: &str| created.get(..bound.len()).unwrap_or(created);
+    (filter.collections.is_empty() || filter.collections.contains(&entry.content_type))
+        && (filter.slugs.is_empty() || filter.slugs.contains(&entry.slug))
+        && filter.is_draft.is_none_or(|d| d == entry.is_draft)
+        && filter.is_pinned.is_none_or(|p| p == entry.is_pinned)
+        && (filter.tags.is_empty() || filter.tags.iter().any(|t| entry.tags.contains(t)))
+        && filter
+            .created_from
+            .as_deref()
+            .is_none_or(|from| created != "unknown" && within(from) >= from)
+        && filter
+            .created_to
+            .as_deref()
+            .is_none_or(|to| created != "unknown" && within(to) <= to)
+}
+
+// ---------------------------------------------------------------------------
+// Frontmatter lines
+// ---------------------------------------------------------------------------
+
+/// The line range of `key`'s block, if the key is set.
+fn block(lines: &[String], key: &str) -> Option<(usize, usize)> {
+    let start = lines.iter().position(|l| {
+        l.strip_prefix(key)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with([' ', '\t']))
+    })?;
+    let len = lines[start + 1..]
+        .iter()
+        .take_while(|l| l.starts_with([' ', '\t']) || *l == "-" || l.starts_with("- "))
+        .count();
+    Some((start, start + 1 + len))
+}
+
+/// The inline value on `key`'s line, when the block is that one line.
+fn inline_value<'a>(lines: &'a [String], key: &str) -> Option<(usize, &'a str)> {
+    let (start, end) = block(lines, key)?;
+    (end == start + 1).then(|| (start, lines[start][key.len() + 1..].trim()))
+}
+
+/// Items of an inline `[...]` list, unquoted.
+fn parse_list(value: &str) -> Option<Vec<String>> {
+    let inner = value.strip_prefix('[')?.strip_suffix(']')?;
+    Some(
+        inner
+            .split(',')
+            .map(|t| t.trim().trim_matches('"').trim_matches('\'').to_string())
+            .filter(|t| !t.is_empty())
+            .collect(),
+    )
+}
+
+/// Apply `pattern` to a scalar, keeping its quoting style.
+fn map_scalar(value: &str, pattern: &Regex, replacement: &str) -> String {
+    if let Some(inner) = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+        let raw = inner.replace("\\\"", "\"").replace("\\\\", "\\");
+        let mapped = pattern.replace_all(&raw, replacement);
+        if mapped == raw {
+            return value.to_string();
+        }
+        return format!("\"{}\"", escape_yaml_string(&mapped));
+    }
+    if let Some(inner) = value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+        let raw = inner.replace("''", "'");
+        let mapped = pattern.replace_all(&raw, replacement);
+        if mapped == raw {
+            return value.to_string();
+        }
+        return format!("'{}'", mapped.replace('\'', "''"));
+    }
+    let mapped = pattern.replace_all(value, replacement);
+    let plain = !mapped.is_empty()
+        && !mapped.starts_with([
+            '-', '?', ':', ',', '[', ']', '{', '}', '#', '&', '*', '!', '|', '>', '\'', '"', '%',
+            '@', '`', ' ',
+        ])
+        && !mapped.ends_with([' ', ':'])
+        && !mapped.contains(": ")
+        && !mapped.contains(" #");
+    if plain || mapped == value {
+        mapped.into_owned()
+    } else {
+        format!("\"{}\"", escape_yaml_string(&mapped))
+    }
+}
+
+fn set_line(lines: &mut Vec<String>, key: &str, value: &str) {
+    let line = format!("{key}: {value}");
+    match block(lines, key) {
+        Some((start, end)) => {
+            lines.splice(start..end, [line]);
+        }
+        None => lines.push(line),
+    }
+}
+
+/// The tags on an entry, or `None` when they aren't an inline list.
+fn tags(lines: &[String]) -> Option<Vec<String>> {
+    match inline_value(lines, "tags") {
+        Some((_, "")) => Some(Vec::new()),
+        Some((_, value)) => parse_list(value),
+        None if block(lines, "tags").is_none() => Some(Vec::new()),
+        None => None,
+    }
+}
+
+fn run(step: &Step, lines: &mut Vec<String>) {
+    match step {
+        Step::Map {
+            key,
+            pattern,
+            replacement,
+        } => {
+            let Some((line, value)) = inline_value(lines, key) else {
+                return;
+            };
+            let mapped = match parse_list(value) {
+                Some(items) => format_yaml_tags(
+                    &items
+                        .iter()
+                        .map(|i| pattern.replace_all(i, *replacement).into_owned())
+                        .collect::<Vec<_>>(),
+                ),
+                None if value.is_empty() => return,
+                None => map_scalar(value, pattern, replacement),
+            };
+            if mapped != value {
+                lines[line] = format!("{key}: {mapped}");
+            }
+        }
+        Step::Op(BulkOp::RenameKey { from, to }) => {
+            if block(lines, to).is_some() {
+                return;
+            }
+            if let Some((start, _)) = block(lines, from) {
+                lines[start] = format!("{to}{}", &lines[start][from.len()..]);
+            }
+        }
+        Step::Op(BulkOp::SetKey { key, value }) => set_line(lines, key, value),
+        Step::Op(BulkOp::RemoveKey { key }) => {
+            if let Some((start, end)) = block(lines, key) {
+                lines.drain(start..end);
+            }
+        }
+        Step::Op(BulkOp::AddTags { tags: add }) => {
+            let Some(mut current) = tags(lines) else {
+                return;
+            };
+            let before = current.len();
+            for tag in add {
+                if !current.contains(tag) {
+                    current.push(tag.clone());
+                }
+            }
+            if current.len() != before {
+                set_line(lines, "tags", &format_yaml_tags(&current));
+            }
+        }
+        Step::Op(BulkOp::RemoveTags { tags: remove }) => {
+            let Some(mut current) = tags(lines) else {
+                return;
+            };
+            let before = current.len();
+            current.retain(|t| !remove.contains(t));
+            if current.len() != before {
+                set_line(lines, "tags", &format_yaml_tags(&current));
+            }
+        }
+        Step::Op(BulkOp::RenameTags { from, to }) => {
+            let Some(current) = tags(lines) else {
+                return;
+            };
+            let mut renamed: Vec<String> = Vec::with_capacity(current.len());
+            for tag in &current {
+                let tag = if from.contains(tag) { to } else { tag };
+                if !renamed.contains(tag) {
+                    renamed.push(tag.clone());
+                }
+            }
+            if renamed != current {
+                set_line(lines, "tags", &format_yaml_tags(&renamed));
+            }
+        }
+        // `compile` turns every `MapValues` into `Step::Map`.
+        Step::Op(BulkOp::MapValues { .. }) => {}
+    }
+}
+
+/// `content` after `steps`, or `None` if they change nothing.
+fn rewrite(steps: &[Step], content: &str) -> Option<String> {
+    let (yaml, rest) = split_frontmatter(content)?;
+    let original: Vec<String> = yaml.lines().map(str::to_string).collect();
+    let mut lines = original.clone();
+    for step in steps {
+        run(step, &mut lines);
+    }
+    (lines != original).then(|| assemble_file(&lines.join("\n"), &rest))
+}
+
+// ---------------------------------------------------------------------------
+// Plan and apply
+// ---------------------------------------------------------------------------
+
+/// Every file `edit` would change, with the rewrite to apply.
+pub fn plan_files(
+    app: &tauri::AppHandle,
+    repo_path: &str,
+    edit: &BulkEdit,
+) -> Result<Vec<(BulkEditChange, Rewrite)>, AppError> {
+    let steps = compile(&edit.ops)?;
+    let base = Path::new(repo_path);
+    let uncommitted = git::uncommitted_paths(repo_path)?;
+
+    let mut planned = Vec::new();
+    for entry in index::list(app, repo_path)? {
+        if !matches(&edit.filter, &entry) {
+            continue;
+        }
+        let file = PathBuf::from(&entry.file_path);
+        let Ok(original) = fs::read_to_string(&file) else {
+            continue;
+        };
+        let Some(content) = rewrite(&steps, &original) else {
+            continue;
+        };
+        let rel = file
+            .strip_prefix(base)
+            .unwrap_or(&file)
+            .to_string_lossy()
+            .to_string();
+        let change = BulkEditChange {
+            collection: entry.content_type,
+            slug: entry.slug,
+            diff: diff::unified(&rel, &original, &content),
+            path: rel.clone(),
+            uncommitted: content::is_uncommitted(&uncommitted, &rel),
+        };
+        planned.push((
+            change,
+            Rewrite {
+                file,
+                rel_path: rel,
+                content,
+            },
+        ));
+    }
+    Ok(planned)
+}
+
+/// Every file `edit` would change in `repo_path`, with its diff. Writes nothing.
+pub fn plan(
+    app: &tauri::AppHandle,
+    repo_path: &str,
+    edit: &BulkEdit,
+) -> Result<Vec<BulkEditChange>, AppError> {
+    Ok(plan_files(app, repo_path, edit)?
+        .into_iter()
+        .map(|(change, _)| change)
+        .collect())
+}
+
+/// Apply `edit` to every matching file except those with uncommitted edits
+/// and commit them in one commit (see `content::commit_rewrites`).
+///
+/// Callers run this through the repository's operation queue (see `queue`).
+pub fn apply(
+    app: &tauri::AppHandle,
+    repo_path: &str,
+    edit: &BulkEdit,
+    op: &Operation,
+) -> Result<BulkEditResult, AppError> {
+    let repo = repo_config::load(repo_path)?;
+    let (changes, rewrites): (Vec<_>, Vec<_>) = plan_files(app, repo_path, edit)?
+        .into_iter()
+        .filter(|(change, _)| !change.uncommitted)
+        .unzip();
+
+    let summary: Vec<String> = edit.ops.iter().map(describe).collect();
+    let subject = content::files_subject(&summary.join("; "), changes.len());
+    let commit = content::commit_rewrites(
+        app,
+        repo_path,
+        &rewrites,
+        &commit_message(&repo.commit.bulk_edit, &subject),
+        MutationKind::BulkEdit,
+        op,
+    )?;
+
+    Ok(BulkEditResult {
+        applied: changes,
+        commit,
+    })
+}
+
+/// A short description of `op` for commit subjects.
+fn describe(op: &BulkOp) -> String {
+    match op {
+        BulkOp::RenameKey { from, to } => format!("rename {from} to {to}"),
+        BulkOp::SetKey { key, .. } => format!("set {key}"),
+        BulkOp::RemoveKey { key } => format!("remove {key}"),
+        BulkOp::MapValues { key, .. } => format!("map {key}"),
+        BulkOp::AddTags { tags } => format!("tag {}", tags.join(", ")),
+        BulkOp::RemoveTags { tags } => format!("untag {}", tags.join(", ")),
+        BulkOp::RenameTags { from, to } => format!("rename tags {} to {to}", from.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edit(ops: &[BulkOp], content: &str) -> Option<String> {
+        rewrite(&compile(ops).unwrap(), content)
+    }
+
+    fn tagged(tags: &str) -> String {
+        format!("---\ntitle: A\ntags: {tags}\n---\nBody\n")
+    }
+
+    fn names(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|t| t.to_string()).collect()
+    }
+
+    #[test]
+    fn rename_key_keeps_block_and_existing_target() {
+        let content = "---\ntitle: A\nsummary: >\n  Folded\n  text\n---\nBody\n";
+        let rename = |to: &str| BulkOp::RenameKey {
+            from: "summary".into(),
+            to: to.into(),
+        };
+        assert_eq!(
+            edit(&[rename("description")], content).unwrap(),
+            "---\ntitle: A\ndescription: >\n  Folded\n  text\n---\nBody\n"
+        );
+        assert_eq!(edit(&[rename("title")], content), None);
+    }
+
+    #[test]
+    fn add_tags_skips_existing_and_keeps_order() {
+        let add = BulkOp::AddTags {
+            tags: names(&["b", "c"]),
+        };
+        assert_eq!(
+            edit(&[add], &tagged(r#"["a", "b"]"#)).unwrap(),
+            tagged(r#"["a", "b", "c"]"#)
+        );
+        let add = BulkOp::AddTags {
+            tags: names(&["a"]),
+        };
+        assert_eq!(edit(&[add], &tagged(r#"["a", "b"]"#)), None);
+    }
+
+    #[test]
+    fn remove_tags_keeps_order() {
+        let remove = BulkOp::RemoveTags {
+            tags: names(&["b"]),
+        };
+        assert_eq!(
+            edit(&[remove], &tagged(r#"["a", "b", "c"]"#)).unwrap(),
+            tagged(r#"["a", "c"]"#)
+        );
+    }
+
+    #[test]
+    fn rename_tags_in_place_without_duplicates() {
+        let rename = |from: &[&str], to: &str| BulkOp::RenameTags {
+            from: names(from),
+            to: to.into(),
+        };
+        assert_eq!(
+            edit(&[rename(&["b"], "z")], &tagged(r#"["a", "b", "c"]"#)).unwrap(),
+            tagged(r#"["a", "z", "c"]"#)
+        );
+        assert_eq!(
+            edit(&[rename(&["a", "c"], "b")], &tagged(r#"["a", "b", "c"]"#)).unwrap(),
+            tagged(r#"["b"]"#)
+        );
+        assert_eq!(edit(&[rename(&["x"], "y")], &tagged(r#"["a"]"#)), None);
+    }
+
+    #[test]
+    fn files_without_frontmatter_are_left_alone() {
+        let ops = [
+            BulkOp::SetKey {
+                key: "author".into(),
+                value: "\"Me\"".into(),
+            },
+            BulkOp::AddTags {
+                tags: names(&["a"]),
+            },
+        ];
+        assert_eq!(edit(&ops, "Just a body\n"), None);
+        assert_eq!(edit(&ops, "title: A\n---\nBody\n"), None);
+    }
+}
@@ -2,34 +2,76 @@
 //!
 //! All functions accept a shared `&reqwest::Client` to reuse connections.
 
+use crate::analytics;
+use crate::error::{AppError, ErrorCode};
+use crate::operations::Operation;
 use crate::types::{
     CfAnalytics, CfBrowserCount, CfCountryCount, CfDailyCount, CfDeploymentInfo, CfPathCount,
-    CfStatusCount,
+    CfPercentiles, CfRumDailyCount, CfRumPathMetrics, CfStatusCount, CfWebAnalytics, OperationStep,
 };
 
+/// Days per chunked adaptive-groups query (see `fetch_analytics_window`).
+const CHUNK_DAYS: u32 = 5; // 5 days × 2 aliases = 10 fields per chunk (well under 30)
+
+/// Send a Cloudflare API request and parse the JSON body, classifying failures:
+/// rejected credentials, rate limits, server errors and unreachable network.
+async fn send_json(
+    request: reqwest::RequestBuilder,
+    what: &str,
+) -> Result<serde_json::Value, AppError> {
+    let resp = request.send().await.map_err(|e| {
+        AppError::new(ErrorCode::Network, format!("Failed to fetch {what}: {e}")).retryable()
+    })?;
+    let status = resp.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+        return Err(AppError::new(
+            ErrorCode::CloudflareAuth,
+            format!("Cloudflare rejected the API token while fetching {what} ({status})."),
+        ));
+    }
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Err(AppError::new(
+            ErrorCode::CloudflareRateLimited,
+            format!("Cloudflare rate limit reached while fetching {what}."),
+        )
+        .retryable());
+    }
+    if status.is_server_error() {
+        return Err(cf_error(format!(
+            "Cloudflare returned {status} while fetching {what}."
+        ))
+        .retryable());
+    }
+    resp.json()
+        .await
+        .map_err(|e| cf_error(format!("Failed to parse {what} response: {e}")))
+}
+
+fn cf_error(message: impl Into<String>) -> AppError {
+    AppError::new(ErrorCode::Cloudflare, message)
+}
+
 /// Look up the zone ID for a domain via the Cloudflare Zones API.
 pub async fn fetch_zone_id(
     client: &reqwest::Client,
     api_token: &str,
     domain: &str,
-) -> Result<String, String> {
-    let resp: serde_json::Value = client
-        .get(format!(
-            "https://api.cloudflare.com/client/v4/zones?name={domain}"
-        ))
-        .bearer_auth(api_token)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch zones: {e}"))?
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse zones response: {e}"))?;
+) -> Result<String, AppError> {
+    let resp = send_json(
+        client
+            .get(format!(
+                "https://api.cloudflare.com/client/v4/zones?name={domain}"
+            ))
+            .bearer_auth(api_token),
+        "zones",
+    )
+    .await?;
 
     let id = resp["result"]
         .as_array()
         .and_then(|arr| arr.first())
         .and_then(|z| z["id"].as_str())
-        .ok_or_else(|| format!("No zone found for domain '{domain}'"))?;
+        .ok_or_else(|| AppError::not_found(format!("No zone found for domain '{domain}'")))?;
 
     Ok(id.to_string())
 }
@@ -40,23 +82,15 @@ pub async fn fetch_last_deployment(
     account_id: &str,
     project_name: &str,
     api_token: &str,
-) -> Result<CfDeploymentInfo, String> {
+) -> Result<CfDeploymentInfo, AppError> {
     let url = format!(
         "https://api.cloudflare.com/client/v4/accounts/{account_id}/pages/projects/{project_name}/deployments?env=production&per_page=5"
     );
-    let resp: serde_json::Value = client
-        .get(&url)
-        .bearer_auth(api_token)
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch deployments: {e}"))?
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse deployments response: {e}"))?;
+    let resp = send_json(client.get(&url).bearer_auth(api_token), "deployments").await?;
 
     let deployments = resp["result"]
         .as_array()
-        .ok_or("Unexpected deployments response format")?;
+        .ok_or_else(|| cf_error("Unexpected deployments response format"))?;
 
     for dep in deployments {
         let latest_stage = &dep["latest_stage"];
@@ -85,7 +119,9 @@ pub async fn fetch_last_deployment(
         }
     }
 
-    Err("No successful production deployment found".to_string())
+    Err(AppError::not_found(
+        "No successful production deployment found",
+    ))
 }
 
 /// Whether a path looks like a real content page (blog, app, about, tags, home).
@@ -100,35 +136,37 @@ fn is_content_path(path: &str) -> bool {
         || p.starts_with("/tags")
 }
 
-/// Execute a Cloudflare GraphQL query and return the parsed JSON response.
+/// Execute a Cloudflare GraphQL query with `variables` and return the parsed JSON response.
 async fn graphql_query(
     client: &reqwest::Client,
     api_token: &str,
     query: &str,
-) -> Result<serde_json::Value, String> {
-    let resp: serde_json::Value = client
-        .post("https://api.cloudflare.com/client/v4/graphql")
-        .bearer_auth(api_token)
-        .json(&serde_json::json!({ "query": query }))
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch analytics: {e}"))?
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse analytics response: {e}"))?;
+    variables: serde_json::Value,
+) -> Result<serde_json::Value, AppError> {
+    let resp = send_json(
+        client
+            .post("https://api.cloudflare.com/client/v4/graphql")
+            .bearer_auth(api_token)
+            .json(&serde_json::json!({ "query": query, "variables": variables })),
+        "analytics",
+    )
+    .await?;
 
     if let Some(errors) = resp["errors"].as_array() {
         if !errors.is_empty() {
             let msg = errors[0]["message"]
                 .as_str()
                 .unwrap_or("Unknown GraphQL error");
-            return Err(format!("Analytics query failed: {msg}"));
+            return Err(cf_error(format!("Analytics query failed: {msg}")));
         }
     }
 
     Ok(resp)
 }
 
+/// Longest window `fetch_analytics` accepts, in days.
+pub const MAX_ANALYTICS_DAYS: u32 = 90;
+
 /// Fetch traffic analytics from Cloudflare's GraphQL Analytics API.
 ///
 /// Uses `httpRequests1dGroups` (or `1hGroups` for 24h) for daily/hourly totals,
@@ -139,14 +177,90 @@ async fn graphql_query(
 ///
 /// When `engagement` is true, daily counts use `pageViews` instead of `requests`
 /// and paths are filtered to content pages only (blog, apps, about, tags).
+///
+/// When `compare` is true, the previous equal-length window is fetched as well
+/// and returned as deltas in `comparison`; it also seeds the anomaly baseline
+/// so the first days of the selected window can be flagged.
+///
+/// Each query is reported on `op` as query N of M; cancelling stops before the next one.
 pub async fn fetch_analytics(
     client: &reqwest::Client,
     api_token: &str,
     zone_id: &str,
     days: u32,
     engagement: bool,
-) -> Result<CfAnalytics, String> {
+    compare: bool,
+    op: &Operation,
+) -> Result<CfAnalytics, AppError> {
     let now = chrono::Utc::now();
+    // Each window is one totals query plus one per chunk.
+    let per_window = 1 + days.div_ceil(CHUNK_DAYS);
+    let mut progress = QueryProgress {
+        op,
+        done: 0,
+        total: if compare { 2 * per_window } else { per_window },
+    };
+    let mut current = fetch_analytics_window(
+        client,
+        api_token,
+        zone_id,
+        days,
+        engagement,
+        now,
+        &mut progress,
+    )
+    .await?;
+
+    if compare {
+        let previous_end = now - chrono::Duration::days(days as i64);
+        let previous = fetch_analytics_window(
+            client,
+            api_token,
+            zone_id,
+            days,
+            engagement,
+            previous_end,
+            &mut progress,
+        )
+        .await?;
+        current.anomalies =
+            analytics::detect_anomalies(&current.daily_requests, &previous.daily_requests);
+        current.comparison = Some(analytics::compare(&current, &previous));
+    } else {
+        current.anomalies = analytics::detect_anomalies(&current.daily_requests, &[]);
+    }
+
+    Ok(current)
+}
+
+/// Counts the GraphQL queries of one `fetch_analytics` call across its windows.
+struct QueryProgress<'a> {
+    op: &'a Operation,
+    done: u32,
+    total: u32,
+}
+
+impl QueryProgress<'_> {
+    /// Report the next query, or fail if the operation was cancelled.
+    fn next(&mut self) -> Result<(), AppError> {
+        self.op.checkpoint()?;
+        self.done += 1;
+        self.op
+            .step_of(OperationStep::Querying, self.done, self.total);
+        Ok(())
+    }
+}
+
+/// Fetch analytics for the `days`-long window ending at `now`.
+async fn fetch_analytics_window(
+    client: &reqwest::Client,
+    api_token: &str,
+    zone_id: &str,
+    days: u32,
+    engagement: bool,
+    now: chrono::DateTime<chrono::Utc>,
+    progress: &mut QueryProgress<'_>,
+) -> Result<CfAnalytics, AppError> {
     let is_hourly = days == 1;
 
     // Engagement uses pageViews; full uses requests
@@ -223,18 +337,18 @@ pub async fn fetch_analytics(
   }}
 }}"#
     );
-    let main_resp = graphql_query(client, api_token, &main_query).await?;
+    progress.next()?;
+    let main_resp = graphql_query(client, api_token, &main_query, serde_json::json!({})).await?;
     let main_zone = main_resp["data"]["viewer"]["zones"]
         .as_array()
         .and_then(|arr| arr.first())
-        .ok_or("No zone data returned")?;
+        .ok_or_else(|| cf_error("No zone data returned"))?;
 
     // --- Query 2: chunked adaptive groups for paths + countries ---
     // Free zones cap each adaptive group at 86400s (24h) and limit ~30 fields per
     // query. We chunk the period into CHUNK_DAYS-day batches, each chunk generating
     // 2 aliases per day (paths + countries = 2 × CHUNK_DAYS fields per query).
     // Chunks are executed sequentially and results merged.
-    const CHUNK_DAYS: u32 = 5; // 5 days × 2 aliases = 10 fields per chunk (well under 30)
     let mut path_map: std::collections::HashMap<String, u64> = std::collections::HashMap::new();
     let mut adaptive_country_map: std::collections::HashMap<String, u64> =
         std::collections::HashMap::new();
@@ -281,7 +395,10 @@ pub async fn fetch_analytics(
 }}"#
         );
 
-        if let Ok(resp) = graphql_query(client, api_token, &chunk_query).await {
+        progress.next()?;
+        if let Ok(resp) =
+            graphql_query(client, api_token, &chunk_query, serde_json::json!({})).await
+        {
             if let Some(zone) = resp["data"]["viewer"]["zones"]
                 .as_array()
                 .and_then(|arr| arr.first())
@@ -433,14 +550,14 @@ pub async fn fetch_analytics(
         .into_iter()
         .map(|(status, count)| CfStatusCount { status, count })
         .collect();
-    status_codes.sort_by(|a, b| b.count.cmp(&a.count));
+    status_codes.sort_by_key(|x| std::cmp::Reverse(x.count));
     status_codes.truncate(10);
 
     let mut browsers: Vec<CfBrowserCount> = browser_map
         .into_iter()
         .map(|(browser, page_views)| CfBrowserCount { browser, page_views })
         .collect();
-    browsers.sort_by(|a, b| b.page_views.cmp(&a.page_views));
+    browsers.sort_by_key(|x| std::cmp::Reverse(x.page_views));
     browsers.truncate(10);
 
     // Countries from chunked adaptive groups (engagement-filtered, period-respecting)
@@ -448,7 +565,7 @@ pub async fn fetch_analytics(
         .into_iter()
         .map(|(country, count)| CfCountryCount { country, count })
         .collect();
-    top_countries.sort_by(|a, b| b.count.cmp(&a.count));
+    top_countries.sort_by_key(|x| std::cmp::Reverse(x.count));
     top_countries.truncate(10);
 
     // Top paths from chunked adaptive groups (engagement-filtered, period-respecting)
@@ -456,7 +573,7 @@ pub async fn fetch_analytics(
         .into_iter()
         .map(|(path, count)| CfPathCount { path, count })
         .collect();
-    top_paths.sort_by(|a, b| b.count.cmp(&a.count));
+    top_paths.sort_by_key(|x| std::cmp::Reverse(x.count));
     top_paths.truncate(10);
 
     Ok(CfAnalytics {
@@ -467,5 +584,239 @@ pub async fn fetch_analytics(
         top_countries,
         status_codes,
         browsers,
+        comparison: None,
+        anomalies: Vec::new(),
+    })
+}
+
+/// Read P50/P75/P90 quantiles for `metric` from a RUM group, scaled by `scale`.
+/// Returns `None` when the group carries no samples for that metric.
+fn rum_percentiles(
+    quantiles: &serde_json::Value,
+    metric: &str,
+    scale: f64,
+) -> Option<CfPercentiles> {
+    let read = |suffix: &str| quantiles[format!("{metric}{suffix}")].as_f64();
+    Some(CfPercentiles {
+        p50: read("P50")? * scale,
+        p75: read("P75")? * scale,
+        p90: read("P90")? * scale,
+    })
+}
+
+/// Longest window `fetch_web_analytics` accepts, in days.
+pub const MAX_WEB_ANALYTICS_DAYS: u32 = 90;
+
+/// How many paths `fetch_web_analytics` ranks per dataset.
+const RUM_PATH_LIMIT: u32 = 50;
+
+/// Fetch browser-side analytics from Cloudflare Web Analytics (RUM).
+///
+/// Page views and visits come from `rumPageloadEventsAdaptiveGroups`; LCP, INP
+/// and CLS from `rumWebVitalsEventsAdaptiveGroups`; full page load time from
+/// `rumPerformanceEventsAdaptiveGroups`. All three are account-scoped datasets
+/// filtered by the site tag of the Web Analytics beacon. Cloudflare reports
+/// timings in microseconds; they are converted to milliseconds here.
+///
+/// Only the `RUM_PATH_LIMIT` most viewed paths are returned. Vitals and load
+/// times are ranked separately (by sample count), so a listed path may have
+/// none when it falls outside their top `RUM_PATH_LIMIT`; timings for paths
+/// outside the page view ranking are not reported at all.
+pub async fn fetch_web_analytics(
+    client: &reqwest::Client,
+    api_token: &str,
+    account_id: &str,
+    site_tag: &str,
+    days: u32,
+) -> Result<CfWebAnalytics, AppError> {
+    const US_TO_MS: f64 = 0.001;
+    let now = chrono::Utc::now();
+    let is_hourly = days == 1;
+
+    let period_start = (now - chrono::Duration::days(days as i64))
+        .format("%Y-%m-%dT%H:%M:%SZ")
+        .to_string();
+    let period_end = now.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+    // User-supplied values go in as variables, never into the query text.
+    let filter = "filter: { siteTag: $siteTag, datetime_geq: $start, datetime_leq: $end }";
+    let (time_dim, time_order) = if is_hourly {
+        ("datetimeHour", "datetimeHour_ASC")
+    } else {
+        ("date", "date_ASC")
+    };
+
+    let query = format!(
+        r#"query ($accountTag: string!, $siteTag: string!, $start: Time!, $end: Time!) {{
+  viewer {{
+    accounts(filter: {{ accountTag: $accountTag }}) {{
+      series: rumPageloadEventsAdaptiveGroups(
+        {filter}
+        limit: 1000
+        orderBy: [{time_order}]
+      ) {{
+        count
+        sum {{ visits }}
+        dimensions {{ {time_dim} }}
+      }}
+      paths: rumPageloadEventsAdaptiveGroups(
+        {filter}
+        limit: {RUM_PATH_LIMIT}
+        orderBy: [count_DESC]
+      ) {{
+        count
+        sum {{ visits }}
+        dimensions {{ requestPath }}
+      }}
+      vitals: rumWebVitalsEventsAdaptiveGroups(
+        {filter}
+        limit: {RUM_PATH_LIMIT}
+        orderBy: [count_DESC]
+      ) {{
+        count
+        dimensions {{ requestPath }}
+        quantiles {{
+          largestContentfulPaintP50
+          largestContentfulPaintP75
+          largestContentfulPaintP90
+          interactionToNextPaintP50
+          interactionToNextPaintP75
+          interactionToNextPaintP90
+          cumulativeLayoutShiftP50
+          cumulativeLayoutShiftP75
+          cumulativeLayoutShiftP90
+        }}
+      }}
+      performance: rumPerformanceEventsAdaptiveGroups(
+        {filter}
+        limit: {RUM_PATH_LIMIT}
+        orderBy: [count_DESC]
+      ) {{
+        count
+        dimensions {{ requestPath }}
+        quantiles {{
+          pageLoadTimeP50
+          pageLoadTimeP75
+          pageLoadTimeP90
+        }}
+      }}
+    }}
+  }}
+}}"#
+    );
+
+    let variables = serde_json::json!({
+        "accountTag": account_id,
+        "siteTag": site_tag,
+        "start": period_start,
+        "end": period_end,
+    });
+    let resp = graphql_query(client, api_token, &query, variables).await?;
+    let account = resp["data"]["viewer"]["accounts"]
+        .as_array()
+        .and_then(|arr| arr.first())
+        .ok_or_else(|| cf_error("No account data returned"))?;
+
+    // --- Daily/hourly page views and visits ---
+    let mut series_map: std::collections::HashMap<String, (u64, u64)> =
+        std::collections::HashMap::new();
+    if let Some(arr) = account["series"].as_array() {
+        for entry in arr {
+            let raw = entry["dimensions"][time_dim].as_str().unwrap_or_default();
+            let key = if is_hourly {
+                chrono::DateTime::parse_from_rfc3339(raw)
+                    .map(|dt| dt.format("%Y-%m-%dT%H:00:00Z").to_string())
+                    .unwrap_or_else(|_| raw.to_string())
+            } else {
+                raw.to_string()
+            };
+            let slot = series_map.entry(key).or_default();
+            slot.0 += entry["count"].as_u64().unwrap_or(0);
+            slot.1 += entry["sum"]["visits"].as_u64().unwrap_or(0);
+        }
+    }
+
+    // Contiguous series so every slot (hour or day) has an entry (0 if missing)
+    let keys: Vec<String> = if is_hourly {
+        (0..24_i64)
+            .map(|i| {
+                (now - chrono::Duration::hours(23 - i))
+                    .format("%Y-%m-%dT%H:00:00Z")
+                    .to_string()
+            })
+            .collect()
+    } else {
+        let start = now - chrono::Duration::days(i64::from(days) - 1);
+        (0..days)
+            .map(|i| {
+                (start + chrono::Duration::days(i as i64))
+                    .format("%Y-%m-%d")
+                    .to_string()
+            })
+            .collect()
+    };
+    let daily: Vec<CfRumDailyCount> = keys
+        .into_iter()
+        .map(|date| {
+            let (page_views, visits) = series_map.get(&date).copied().unwrap_or((0, 0));
+            CfRumDailyCount {
+                date,
+                page_views,
+                visits,
+            }
+        })
+        .collect();
+
+    // --- Per-path counts, merged with web vitals and performance quantiles ---
+    // Quantiles for paths missing from the page view ranking are dropped;
+    // ranked paths missing from the quantile rankings keep `None`.
+    let mut paths: Vec<CfRumPathMetrics> = account["paths"]
+        .as_array()
+        .map(|arr| {
+            arr.iter()
+                .map(|entry| CfRumPathMetrics {
+                    path: entry["dimensions"]["requestPath"]
+                        .as_str()
+                        .unwrap_or("/")
+                        .to_string(),
+                    page_views: entry["count"].as_u64().unwrap_or(0),
+                    visits: entry["sum"]["visits"].as_u64().unwrap_or(0),
+                    lcp: None,
+                    inp: None,
+                    cls: None,
+                    page_load: None,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if let Some(arr) = account["vitals"].as_array() {
+        for entry in arr {
+            let path = entry["dimensions"]["requestPath"].as_str().unwrap_or("/");
+            if let Some(metrics) = paths.iter_mut().find(|p| p.path == path) {
+                let q = &entry["quantiles"];
+                metrics.lcp = rum_percentiles(q, "largestContentfulPaint", US_TO_MS);
+                metrics.inp = rum_percentiles(q, "interactionToNextPaint", US_TO_MS);
+                metrics.cls = rum_percentiles(q, "cumulativeLayoutShift", 1.0);
+            }
+        }
+    }
+
+    if let Some(arr) = account["performance"].as_array() {
+        for entry in arr {
+            let path = entry["dimensions"]["requestPath"].as_str().unwrap_or("/");
+            if let Some(metrics) = paths.iter_mut().find(|p| p.path == path) {
+                metrics.page_load = rum_percentiles(&entry["quantiles"], "pageLoadTime", US_TO_MS);
+            }
+        }
+    }
+
+    paths.sort_by_key(|x| std::cmp::Reverse(x.page_views));
+
+    Ok(CfWebAnalytics {
+        period: format!("{days}d"),
+        total_page_views: daily.iter().map(|d| d.page_views).sum(),
+        total_visits: daily.iter().map(|d| d.visits).sum(),
+        daily,
+        paths,
     })
 }
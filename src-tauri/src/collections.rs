@@ -0,0 +1,345 @@
+//! Content collections — locating entries from configured file patterns,
+//! change-tracking hashes, and new-entry scaffolds.
+//!
+//! A collection's entry patterns all start with `{slug}`. A pattern continuing
+//! with `/` (e.g. `{slug}/index.mdx`) means one directory per entry; anything
+//! else (e.g. `{slug}.md`) means one file per entry.
+
+use regex::Regex;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::LazyLock;
+
+use crate::frontmatter::{calculate_content_hash, calculate_directory_hash, format_yaml_tags};
+use crate::security::escape_yaml_string;
+use crate::types::{CollectionConfig, HashMode};
+
+/// Placeholder for the entry slug in patterns, scaffold paths and routes.
+const SLUG: &str = "{slug}";
+
+/// Matches `{{name}}` template placeholders.
+static PLACEHOLDER_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\{\{(\w+)\}\}").unwrap());
+
+/// Frontmatter every new entry starts with.
+const FRONTMATTER_TEMPLATE: &str = "---\ntitle: \"{{title}}\"\nsummary: \"{{summary}}\"\ncreatedDate: \"{{date}}\"\nisDraft: true\ntags: {{tags}}\n---\n";
+
+/// Astro component scaffold for app entries.
+const APP_ASTRO_TEMPLATE: &str = r#"---
+/**
+ * {{slug}} -- App Component
+ */
+---
+
+<div class="{{slug}}-root" id="{{slug}}-root">
+  <p class="{{slug}}-placeholder">App goes here.</p>
+</div>
+
+<style>
+  .{{slug}}-root {
+    display: flex;
+    align-items: center;
+    justify-content: center;
+    width: 100%;
+    height: 100%;
+    color: var(--color-text);
+  }
+
+  .{{slug}}-placeholder {
+    font-family: var(--font-serif);
+    font-size: var(--font-size-h3);
+    color: var(--color-text-muted);
+  }
+</style>
+
+<script>
+  import { onPageReady } from "../../../utils/lifecycle";
+
+  onPageReady((signal) => {
+    const root = document.getElementById("{{slug}}-root");
+    if (!root) return;
+
+    const themeObserver = new MutationObserver(() => {});
+    themeObserver.observe(document.documentElement, {
+      attributes: true,
+      attributeFilter: ["data-theme"],
+    });
+    signal.addEventListener("abort", () => themeObserver.disconnect());
+  });
+</script>
+"#;
+
+/// Values substituted into scaffold templates. `title`, `summary` and `tags`
+/// are rendered YAML-safe for use inside double-quoted frontmatter values.
+pub struct ScaffoldVars<'a> {
+    pub slug: &'a str,
+    pub title: &'a str,
+    pub summary: &'a str,
+    pub date: &'a str,
+    pub tags: &'a [String],
+}
+
+/// Whether entries are directories (`{slug}/…`) rather than single files.
+pub fn is_directory_layout(collection: &CollectionConfig) -> bool {
+    collection
+        .entry_files
+        .first()
+        .and_then(|p| p.strip_prefix(SLUG))
+        .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// Check a collection definition for patterns the resolver can't handle.
+pub fn validate(collection: &CollectionConfig) -> Result<(), String> {
+    let id = &collection.id;
+    if id.is_empty() {
+        return Err("collection id cannot be empty".to_string());
+    }
+    if !is_relative_inside(&collection.dir) {
+        return Err(format!(
+            "collection \"{id}\": dir \"{}\" must be a path inside the repository",
+            collection.dir
+        ));
+    }
+    if collection.entry_files.is_empty() {
+        return Err(format!("collection \"{id}\": entry_files cannot be empty"));
+    }
+    let dir_layout = is_directory_layout(collection);
+    for pattern in &collection.entry_files {
+        let valid = pattern.strip_prefix(SLUG).is_some_and(|rest| {
+            !rest.is_empty() && !rest.contains(SLUG) && rest.starts_with('/') == dir_layout
+        }) && is_relative_inside(pattern);
+        if !valid {
+            return Err(format!(
+                "collection \"{id}\": entry file pattern \"{pattern}\" must start with {{slug}} \
+                 and use the same layout as the other patterns"
+            ));
+        }
+    }
+    if collection.hash == HashMode::Directory && !dir_layout {
+        return Err(format!(
+            "collection \"{id}\": hash = \"directory\" needs one directory per entry"
+        ));
+    }
+    for file in &collection.scaffold {
+        if !file.path.contains(SLUG) || !is_relative_inside(&file.path) {
+            return Err(format!(
+                "collection \"{id}\": scaffold path \"{}\" must contain {{slug}} and stay inside the collection",
+                file.path
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// A non-empty relative path without `..` components.
+pub fn is_relative_inside(path: &str) -> bool {
+    let path = Path::new(path);
+    !path.as_os_str().is_empty()
+        && path
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+/// Resolve a slug to its entry file, trying patterns in priority order.
+pub fn find_entry(base: &Path, collection: &CollectionConfig, slug: &str) -> Option<PathBuf> {
+    let dir = base.join(&collection.dir);
+    collection
+        .entry_files
+        .iter()
+        .map(|pattern| dir.join(pattern.replace(SLUG, slug)))
+        .find(|path| path.is_file())
+}
+
+/// List every entry of a collection as `(slug, entry file)`.
+pub fn list_entries(base: &Path, collection: &CollectionConfig) -> Vec<(String, PathBuf)> {
+    let dir = base.join(&collection.dir);
+    let Ok(readdir) = fs::read_dir(&dir) else {
+        return Vec::new();
+    };
+    let dir_layout = is_directory_layout(collection);
+
+    let mut seen = HashSet::new();
+    let mut entries = Vec::new();
+    for entry in readdir.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with('.') {
+            continue;
+        }
+        let slug = if dir_layout {
+            entry.path().is_dir().then(|| name.clone())
+        } else {
+            file_slug(collection, &name)
+        };
+        let Some(slug) = slug else { continue };
+        if !seen.insert(slug.clone()) {
+            continue;
+        }
+        if let Some(path) = find_entry(base, collection, &slug) {
+            entries.push((slug, path));
+        }
+    }
+    entries
+}
+
+/// The slug of a one-file entry named `name`, if it matches an entry pattern.
+fn file_slug(collection: &CollectionConfig, name: &str) -> Option<String> {
+    collection
+        .entry_files
+        .iter()
+        .filter_map(|pattern| pattern.strip_prefix(SLUG))
+        .filter_map(|suffix| name.strip_suffix(suffix))
+        .find(|stem| !stem.is_empty())
+        .map(str::to_string)
+}
+
+/// Where a path sits within a collection's entries (see `locate`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntryPath {
+    pub slug: String,
+    /// Relative to the entry root, `/`-separated; empty for the root itself.
+    pub subfile: String,
+    /// The path is the entry's content file.
+    pub entry_file: bool,
+}
+
+/// The entry of `collection` that `path` belongs to. Paths outside the
+/// collection directory, hidden files and files no entry pattern matches
+/// give `None`. The path doesn't need to exist, so deletions resolve too.
+pub fn locate(base: &Path, collection: &CollectionConfig, path: &Path) -> Option<EntryPath> {
+    let rel = path.strip_prefix(base.join(&collection.dir)).ok()?;
+    let parts = rel
+        .components()
+        .map(|c| match c {
+            Component::Normal(part) => Some(part.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    if parts.iter().any(|part| part.starts_with('.')) {
+        return None;
+    }
+    let (first, rest) = parts.split_first()?;
+
+    if is_directory_layout(collection) {
+        let subfile = rest.join("/");
+        let entry_file = collection
+            .entry_files
+            .iter()
+            .filter_map(|pattern| pattern.strip_prefix(SLUG)?.strip_prefix('/'))
+            .any(|tail| tail == subfile);
+        Some(EntryPath {
+            slug: first.clone(),
+            subfile,
+            entry_file,
+        })
+    } else if rest.is_empty() {
+        Some(EntryPath {
+            slug: file_slug(collection, first)?,
+            subfile: String::new(),
+            entry_file: true,
+        })
+    } else {
+        None
+    }
+}
+
+/// The path that represents an entry as a whole: its directory, or the entry file itself.
+pub fn entry_root(base: &Path, collection: &CollectionConfig, slug: &str, file: &Path) -> PathBuf {
+    if is_directory_layout(collection) {
+        base.join(&collection.dir).join(slug)
+    } else {
+        file.to_path_buf()
+    }
+}
+
+/// Repo-relative form of `entry_root`, as passed to git.
+pub fn rel_entry_root(
+    base: &Path,
+    collection: &CollectionConfig,
+    slug: &str,
+    file: &Path,
+) -> String {
+    let root = entry_root(base, collection, slug, file);
+    root.strip_prefix(base)
+        .unwrap_or(&root)
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Current change-tracking hash of an entry, given its file and body.
+pub fn content_hash(collection: &CollectionConfig, file: &Path, body: &str) -> Option<String> {
+    match collection.hash {
+        HashMode::Body => Some(calculate_content_hash(body)),
+        HashMode::Directory => {
+            entry_dir_of(collection, file).map(|dir| calculate_directory_hash(&dir, file))
+        }
+    }
+}
+
+/// The `{slug}` directory containing an entry file (directory layout only).
+pub fn entry_dir_of(collection: &CollectionConfig, file: &Path) -> Option<PathBuf> {
+    collection
+        .entry_files
+        .iter()
+        .filter_map(|pattern| pattern.strip_prefix(SLUG)?.strip_prefix('/'))
+        .find(|rest| file.ends_with(rest))
+        .and_then(|rest| file.ancestors().nth(Path::new(rest).components().count()))
+        .map(Path::to_path_buf)
+}
+
+/// Render the files of a new entry as `(path relative to the collection dir, contents)`.
+/// Collections without a scaffold get a frontmatter-only file at their first pattern.
+pub fn render_scaffold(
+    base: &Path,
+    collection: &CollectionConfig,
+    vars: &ScaffoldVars,
+) -> Result<Vec<(String, String)>, String> {
+    let default_file;
+    let files: Vec<(&str, &str)> = if collection.scaffold.is_empty() {
+        default_file = collection.entry_files[0].clone();
+        vec![(default_file.as_str(), "builtin:frontmatter")]
+    } else {
+        collection
+            .scaffold
+            .iter()
+            .map(|f| (f.path.as_str(), f.template.as_str()))
+            .collect()
+    };
+
+    files
+        .into_iter()
+        .map(|(path, template)| {
+            let source = load_template(base, template)?;
+            Ok((path.replace(SLUG, vars.slug), render(&source, vars)))
+        })
+        .collect()
+}
+
+/// Resolve a `builtin:<name>` template or read a repo-relative template file.
+fn load_template(base: &Path, template: &str) -> Result<String, String> {
+    match template.strip_prefix("builtin:") {
+        Some("frontmatter") => Ok(FRONTMATTER_TEMPLATE.to_string()),
+        Some("app-astro") => Ok(APP_ASTRO_TEMPLATE.to_string()),
+        Some(other) => Err(format!("Unknown built-in template \"{other}\".")),
+        None if is_relative_inside(template) => fs::read_to_string(base.join(template))
+            .map_err(|e| format!("Failed to read template '{template}': {e}")),
+        None => Err(format!(
+            "Template \"{template}\" must be a path inside the repository."
+        )),
+    }
+}
+
+/// Substitute `{{slug}}`, `{{title}}`, `{{summary}}`, `{{date}}` and `{{tags}}` in one
+/// pass, so values containing placeholders aren't expanded again. Unknown names are kept.
+fn render(template: &str, vars: &ScaffoldVars) -> String {
+    let tags = format_yaml_tags(vars.tags);
+    PLACEHOLDER_RE
+        .replace_all(template, |caps: &regex::Captures| match &caps[1] {
+            "slug" => vars.slug.to_string(),
+            "title" => escape_yaml_string(vars.title),
+            "summary" => escape_yaml_string(vars.summary),
+            "date" => vars.date.to_string(),
+            "tags" => tags.clone(),
+            _ => caps[0].to_string(),
+        })
+        .into_owned()
+}
@@ -3,15 +3,32 @@ use std::path::Path;
 use std::process::Command;
 use tauri::Manager;
 
+use crate::bulk;
 use crate::cloudflare;
+use crate::collections;
 use crate::config;
 use crate::content;
+use crate::error::AppError;
+use crate::export;
+use crate::files;
 use crate::frontmatter;
 use crate::git;
+use crate::history;
+use crate::index;
+use crate::migrations;
+use crate::operations::{self, Operation};
+use crate::push_queue;
+use crate::queue;
+use crate::repo_config::{self, commit_message};
+use crate::scan;
+use crate::secrets;
 use crate::security;
+use crate::snapshots;
+use crate::taxonomy;
 use crate::types::*;
 
-/// Shared HTTP client — reuses connections across all Cloudflare + health check calls.
+/// Shared HTTP client — reuses connections across all Cloudflare calls.
+/// Health checks build per-request clients pinned to the addresses they validated.
 pub struct HttpClient(pub reqwest::Client);
 
 // ---------------------------------------------------------------------------
@@ -19,26 +36,115 @@ pub struct HttpClient(pub reqwest::Client);
 // ---------------------------------------------------------------------------
 
 #[tauri::command]
-pub fn get_config(app: tauri::AppHandle) -> Result<AppConfig, String> {
-    Ok(config::load_config(&app))
+pub fn get_config(app: tauri::AppHandle) -> Result<AppConfig, AppError> {
+    config::load_config(&app)
 }
 
 #[tauri::command]
-pub fn set_config(app: tauri::AppHandle, config: AppConfig) -> Result<(), String> {
+pub fn set_config(app: tauri::AppHandle, config: AppConfig) -> Result<(), AppError> {
     config::save_config(&app, &config)
 }
 
+#[tauri::command]
+pub fn reset_config(app: tauri::AppHandle) -> Result<AppConfig, AppError> {
+    let cfg = config::reset_config(&app)?;
+    restart_repo_services(&app, &cfg)?;
+    Ok(cfg)
+}
+
+/// Stop the watcher and dev server, then restart both for the profile's repo (if any).
+fn restart_repo_services(app: &tauri::AppHandle, cfg: &AppConfig) -> Result<(), AppError> {
+    let state = app.state::<crate::devserver::DevServerState>();
+    crate::watcher::stop_watching(app)?;
+    crate::devserver::stop_dev_server(&state)?;
+    if let Some(ref repo_path) = cfg.repo_path {
+        crate::watcher::start_watching(app, repo_path)?;
+        crate::devserver::start_dev_server(&state, repo_path)?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn switch_profile(app: tauri::AppHandle, name: String) -> Result<AppConfig, AppError> {
+    let cfg = config::switch_profile(&app, &name)?;
+    restart_repo_services(&app, &cfg)?;
+    Ok(cfg)
+}
+
+#[tauri::command]
+pub fn create_profile(app: tauri::AppHandle, name: String) -> Result<AppConfig, AppError> {
+    let cfg = config::create_profile(&app, &name)?;
+    restart_repo_services(&app, &cfg)?;
+    Ok(cfg)
+}
+
+#[tauri::command]
+pub fn delete_profile(app: tauri::AppHandle, name: String) -> Result<AppConfig, AppError> {
+    let previous = config::load_config(&app)?.active_profile;
+    let cfg = config::delete_profile(&app, &name)?;
+    if cfg.active_profile != previous {
+        restart_repo_services(&app, &cfg)?;
+    }
+    Ok(cfg)
+}
+
+// ---------------------------------------------------------------------------
+// Credentials
+// ---------------------------------------------------------------------------
+
+/// Store or rotate the Cloudflare API token for the active profile.
+#[tauri::command]
+pub fn set_cf_api_token(app: tauri::AppHandle, token: String) -> Result<AppConfig, AppError> {
+    config::set_cf_api_token(&app, &token)
+}
+
+#[tauri::command]
+pub fn clear_cf_api_token(app: tauri::AppHandle) -> Result<AppConfig, AppError> {
+    config::clear_cf_api_token(&app)
+}
+
+#[tauri::command]
+pub fn get_secrets_status(app: tauri::AppHandle) -> Result<SecretsStatus, AppError> {
+    Ok(secrets::status(&app)?)
+}
+
+#[tauri::command]
+pub fn unlock_secrets(
+    app: tauri::AppHandle,
+    passphrase: String,
+) -> Result<SecretsStatus, AppError> {
+    secrets::unlock(&app, &passphrase)?;
+    Ok(secrets::status(&app)?)
+}
+
+/// Re-encrypt all secrets under a passphrase, or under a key file when `None`.
+#[tauri::command]
+pub fn rekey_secrets(
+    app: tauri::AppHandle,
+    passphrase: Option<String>,
+) -> Result<SecretsStatus, AppError> {
+    secrets::rekey(&app, passphrase.as_deref())?;
+    Ok(secrets::status(&app)?)
+}
+
 // ---------------------------------------------------------------------------
 // Validation
 // ---------------------------------------------------------------------------
 
 #[tauri::command]
-pub fn validate_repo_path(path: String) -> Result<bool, String> {
+pub fn validate_repo_path(path: String) -> Result<bool, AppError> {
     let base = Path::new(&path);
-    let has_blog = base.join("src/content/blog").is_dir();
-    let has_apps = base.join("src/content/apps").is_dir();
+    let repo = repo_config::load(base)?;
+    let has_collections = repo.collections.iter().all(|c| base.join(&c.dir).is_dir());
     let has_pkg = base.join("package.json").is_file();
-    Ok(has_blog && has_apps && has_pkg)
+    Ok(has_collections && has_pkg)
+}
+
+/// Settings from the repo's `.panel.toml` (defaults if the file is absent).
+#[tauri::command]
+pub fn get_repo_config(app: tauri::AppHandle, repo_path: String) -> Result<RepoConfig, AppError> {
+    let repo_path = security::repo_root(&app, &repo_path)?;
+    Ok(repo_config::load(&repo_path)?)
 }
 
 // ---------------------------------------------------------------------------
@@ -46,74 +152,235 @@ pub fn validate_repo_path(path: String) -> Result<bool, String> {
 // ---------------------------------------------------------------------------
 
 #[tauri::command]
-pub fn list_content(repo_path: String) -> Result<Vec<ContentEntry>, String> {
-    content::list_content(&repo_path)
+pub fn list_content(
+    app: tauri::AppHandle,
+    repo_path: String,
+) -> Result<Vec<ContentEntry>, AppError> {
+    let repo_path = security::repo_root(&app, &repo_path)?;
+    let mut entries = index::list(&app, &repo_path)?;
+    let pending = push_queue::pending_slugs(&app, &repo_path)?;
+    for entry in &mut entries {
+        entry.pending_push = pending.contains(&entry.slug);
+    }
+    Ok(entries)
 }
 
 #[tauri::command]
-pub fn read_file(app: tauri::AppHandle, path: String) -> Result<String, String> {
-    let cfg = config::load_config(&app);
-    if let Some(ref repo_path) = cfg.repo_path {
-        let base = Path::new(repo_path);
-        let target = Path::new(&path);
-        security::ensure_within(target, base)?;
-    }
-    fs::read_to_string(&path).map_err(|e| format!("Failed to read {}: {}", path, e))
+pub fn read_file(app: tauri::AppHandle, path: String) -> Result<FileContents, AppError> {
+    let repo_path = security::configured_repo(&app)?;
+    security::ensure_within(Path::new(&path), Path::new(&repo_path))?;
+    let raw = fs::read_to_string(&path)
+        .map_err(|e| AppError::io(format!("Failed to read {}: {}", path, e)).with_path(&path))?;
+    Ok(FileContents {
+        version: frontmatter::calculate_content_hash(&raw),
+        // The BOM is restored on write; the editor never sees it.
+        content: files::strip_bom(&raw).to_string(),
+    })
 }
 
+/// Write a file, refusing if it no longer matches `expected_version` (as returned
+/// by `read_file`). Without an expected version the write is unconditional.
 #[tauri::command]
-pub fn write_file(app: tauri::AppHandle, path: String, content: String) -> Result<(), String> {
-    let cfg = config::load_config(&app);
-    if let Some(ref repo_path) = cfg.repo_path {
-        let base = Path::new(repo_path);
-        let target = Path::new(&path);
-        security::ensure_within(target, base)?;
+pub fn write_file(
+    app: tauri::AppHandle,
+    path: String,
+    content: String,
+    expected_version: Option<String>,
+) -> Result<WriteResult, AppError> {
+    let repo_path = security::configured_repo(&app)?;
+    security::ensure_writable(Path::new(&path), Path::new(&repo_path))?;
+
+    if let Some(expected) = expected_version {
+        let current = match fs::read_to_string(&path) {
+            Ok(current) => Some(current),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => {
+                return Err(AppError::io(format!("Failed to read {}: {}", path, e)).with_path(&path))
+            }
+        };
+        let actual = current.as_deref().map(frontmatter::calculate_content_hash);
+        if actual.as_deref() != Some(expected.as_str()) {
+            return Ok(WriteResult::Conflict {
+                expected,
+                actual,
+                content: current,
+            });
+        }
     }
-    fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", path, e))
+
+    save_content(&app, &repo_path, Path::new(&path), &content)?;
+    let written = fs::read_to_string(&path)
+        .map_err(|e| AppError::io(format!("Failed to read {}: {}", path, e)).with_path(&path))?;
+    Ok(WriteResult::Written {
+        version: frontmatter::calculate_content_hash(&written),
+    })
 }
 
 #[tauri::command]
-pub fn create_post(repo_path: String, args: CreatePostArgs) -> Result<ContentEntry, String> {
+pub fn create_post(
+    app: tauri::AppHandle,
+    repo_path: String,
+    args: CreateEntryArgs,
+) -> Result<ContentEntry, AppError> {
+    let repo_path = security::repo_root(&app, &repo_path)?;
     content::create_post(&repo_path, args)
 }
 
 #[tauri::command]
-pub fn create_app(repo_path: String, args: CreateAppArgs) -> Result<ContentEntry, String> {
+pub fn create_app(
+    app: tauri::AppHandle,
+    repo_path: String,
+    args: CreateEntryArgs,
+) -> Result<ContentEntry, AppError> {
+    let repo_path = security::repo_root(&app, &repo_path)?;
     content::create_app(&repo_path, args)
 }
 
+/// Create an entry in any configured collection.
 #[tauri::command]
-pub async fn delete_content(repo_path: String, slug: String) -> Result<(), String> {
-    content::delete_content(&repo_path, &slug)
+pub fn create_entry(
+    app: tauri::AppHandle,
+    repo_path: String,
+    collection: String,
+    args: CreateEntryArgs,
+) -> Result<ContentEntry, AppError> {
+    let repo_path = security::repo_root(&app, &repo_path)?;
+    content::create_entry(&repo_path, &collection, args)
+}
+
+/// Write a content file, snapshotting the new contents first (best-effort).
+fn save_content(
+    app: &tauri::AppHandle,
+    repo_path: &str,
+    file: &Path,
+    content: &str,
+) -> Result<(), AppError> {
+    let _ = snapshots::record(app, repo_path, file, content);
+    files::write_text(file, content).map_err(|e| AppError::io(e).with_path(file))
+}
+
+// ---------------------------------------------------------------------------
+// Snapshots
+// ---------------------------------------------------------------------------
+
+#[tauri::command]
+pub fn list_snapshots(app: tauri::AppHandle, path: String) -> Result<Vec<SnapshotInfo>, AppError> {
+    let repo_path = security::configured_repo(&app)?;
+    security::ensure_within(Path::new(&path), Path::new(&repo_path))?;
+    Ok(snapshots::list(&app, &repo_path, Path::new(&path)))
+}
+
+/// Restore a snapshot over its file, returning the restored contents.
+#[tauri::command]
+pub fn restore_snapshot(
+    app: tauri::AppHandle,
+    path: String,
+    id: String,
+) -> Result<FileContents, AppError> {
+    let repo_path = security::configured_repo(&app)?;
+    security::ensure_writable(Path::new(&path), Path::new(&repo_path))?;
+    snapshots::restore(&app, &repo_path, Path::new(&path), &id)?;
+    read_file(app, path)
+}
+
+/// Snapshots newer than their files on disk — unsaved work from an interrupted session.
+#[tauri::command]
+pub fn get_recoverable_snapshots(app: tauri::AppHandle) -> Result<Vec<SnapshotInfo>, AppError> {
+    let repo_path = security::configured_repo(&app)?;
+    Ok(snapshots::recoverable(&app, &repo_path))
+}
+
+/// Delete an entry, reporting progress under `operation_id`.
+#[tauri::command]
+pub async fn delete_content(
+    app: tauri::AppHandle,
+    repo_path: String,
+    slug: String,
+    operation_id: Option<String>,
+) -> Result<(), AppError> {
+    let repo_path = security::repo_root(&app, &repo_path)?;
+    let op = Operation::begin(&app, operation_id);
+    queue::run(
+        app,
+        repo_path,
+        MutationKind::Delete,
+        slug,
+        move |app, repo_path, slug| {
+            let commit = content::delete_content(repo_path, slug, &op)?;
+            push_queue::enqueue_and_push(app, repo_path, MutationKind::Delete, slug, commit, &op)
+                .map_err(|e| e.with_slug(slug))
+        },
+    )
+    .await
 }
 
 // ---------------------------------------------------------------------------
 // Publish / unpublish
 // ---------------------------------------------------------------------------
 
+/// Mark an entry returned by a mutation with its repository's push state. The
+/// mutation already happened, so an unreadable queue only leaves it unmarked.
+fn with_push_state(
+    app: &tauri::AppHandle,
+    repo_path: &str,
+    mut entry: ContentEntry,
+) -> ContentEntry {
+    entry.pending_push =
+        push_queue::pending_slugs(app, repo_path).is_ok_and(|slugs| slugs.contains(&entry.slug));
+    entry
+}
+
+/// Publish an entry, reporting progress under `operation_id`. Cancelling stops
+/// it before the entry file is written, or after the commit and before the push.
+/// A push that fails leaves the commit queued and the entry `pending_push`.
 #[tauri::command]
-pub async fn publish(repo_path: String, slug: String) -> Result<ContentEntry, String> {
-    let base = Path::new(&repo_path);
-    let (file_path, content_type) = content::find_content_file(base, &slug)?;
-    let file_content = fs::read_to_string(&file_path).map_err(|e| e.to_string())?;
+pub async fn publish(
+    app: tauri::AppHandle,
+    repo_path: String,
+    slug: String,
+    allow_findings: Option<bool>,
+    operation_id: Option<String>,
+) -> Result<ContentEntry, AppError> {
+    let repo_path = security::repo_root(&app, &repo_path)?;
+    let op = Operation::begin(&app, operation_id);
+    queue::run(
+        app,
+        repo_path,
+        MutationKind::Publish,
+        slug,
+        move |app, repo_path, slug| {
+            publish_entry(app, repo_path, slug, allow_findings.unwrap_or(false), &op)
+                .map(|entry| with_push_state(app, repo_path, entry))
+        },
+    )
+    .await
+}
+
+fn publish_entry(
+    app: &tauri::AppHandle,
+    repo_path: &str,
+    slug: &str,
+    allow_findings: bool,
+    op: &Operation,
+) -> Result<ContentEntry, AppError> {
+    let base = Path::new(repo_path);
+    let repo = repo_config::load(base)?;
+    let (file_path, collection) = content::find_content_file(base, &repo, slug)?;
+    let file_content = fs::read_to_string(&file_path)
+        .map_err(|e| AppError::io(e.to_string()).with_path(&file_path))?;
     let (yaml, rest) = frontmatter::split_frontmatter(&file_content)
-        .ok_or_else(|| "Could not parse frontmatter.".to_string())?;
+        .ok_or_else(|| AppError::from("Could not parse frontmatter.").with_slug(slug))?;
 
-    let is_app = content_type == "app";
+    let tracks_directory = collection.hash == HashMode::Directory;
 
-    // Compute hash: directory hash for apps, body hash for posts.
-    let hash = if is_app {
-        file_path
-            .parent()
-            .map(frontmatter::calculate_directory_hash)
-            .ok_or_else(|| "Could not determine app directory.".to_string())?
-    } else {
-        frontmatter::calculate_content_hash(&rest)
-    };
+    // Compute hash per the collection: whole entry directory, or the body alone.
+    op.step(OperationStep::Hashing);
+    let hash = collections::content_hash(collection, &file_path, &rest)
+        .ok_or_else(|| AppError::from("Could not determine entry directory.").with_slug(slug))?;
 
-    // For apps, commit the whole directory; for posts, just the index file.
-    let rel_commit_path = if is_app {
-        format!("src/content/apps/{}", slug)
+    // Directory-hashed entries commit the whole entry; others just the entry file.
+    let rel_commit_path = if tracks_directory {
+        collections::rel_entry_root(base, collection, slug, &file_path)
     } else {
         file_path
             .strip_prefix(base)
@@ -122,25 +389,40 @@ pub async fn publish(repo_path: String, slug: String) -> Result<ContentEntry, St
             .to_string()
     };
 
+    // Scan before touching frontmatter so a blocked publish leaves the entry as it was.
+    if !allow_findings {
+        op.step(OperationStep::Scanning);
+        scan::check(repo_path, &rel_commit_path, &repo.scan)?;
+    }
+    op.checkpoint()?;
+    op.step(OperationStep::Writing);
+
     if frontmatter::get_yaml_bool(&yaml, "isDraft") == Some(false) {
         // Already published — update the baseline hash if content has changed.
         let current_hash = frontmatter::get_yaml_field(&yaml, "publishedHash");
         if current_hash.as_deref() == Some(&hash) {
-            return frontmatter::parse_content_entry(&slug, &content_type, &file_path)
-                .ok_or_else(|| "Failed to parse entry.".to_string());
+            return frontmatter::parse_content_entry(slug, collection, &file_path)
+                .ok_or_else(|| AppError::from("Failed to parse entry.").with_slug(slug));
         }
 
         let new_yaml =
             frontmatter::set_frontmatter_field(&yaml, "publishedHash", &format!("\"{}\"", hash));
         let new_content = frontmatter::assemble_file(&new_yaml, &rest);
-        fs::write(&file_path, &new_content).map_err(|e| e.to_string())?;
+        save_content(app, repo_path, &file_path, &new_content)?;
 
         let title =
-            frontmatter::get_yaml_field(&new_yaml, "title").unwrap_or_else(|| slug.clone());
-        git::git_add_commit_push(&repo_path, &rel_commit_path, &format!("publish: {}", title))?;
-
-        return frontmatter::parse_content_entry(&slug, &content_type, &file_path)
-            .ok_or_else(|| "Failed to parse entry.".to_string());
+            frontmatter::get_yaml_field(&new_yaml, "title").unwrap_or_else(|| slug.to_string());
+        let commit = git::git_add_commit(
+            repo_path,
+            &rel_commit_path,
+            &commit_message(&repo.commit.publish, &title),
+            None,
+            op,
+        )?;
+        push_queue::enqueue_and_push(app, repo_path, MutationKind::Publish, slug, commit, op)?;
+
+        return frontmatter::parse_content_entry(slug, collection, &file_path)
+            .ok_or_else(|| AppError::from("Failed to parse entry.").with_slug(slug));
     }
 
     let mut new_yaml = frontmatter::set_frontmatter_field(&yaml, "isDraft", "false");
@@ -158,61 +440,138 @@ pub async fn publish(repo_path: String, slug: String) -> Result<ContentEntry, St
     }
 
     let new_content = frontmatter::assemble_file(&new_yaml, &rest);
-    fs::write(&file_path, &new_content).map_err(|e| e.to_string())?;
-
-    let title = frontmatter::get_yaml_field(&new_yaml, "title").unwrap_or_else(|| slug.clone());
-    git::git_add_commit_push(&repo_path, &rel_commit_path, &format!("publish: {}", title))?;
-
-    frontmatter::parse_content_entry(&slug, &content_type, &file_path)
-        .ok_or_else(|| "Failed to parse entry after publish.".to_string())
+    save_content(app, repo_path, &file_path, &new_content)?;
+
+    let title = frontmatter::get_yaml_field(&new_yaml, "title").unwrap_or_else(|| slug.to_string());
+    let commit = git::git_add_commit(
+        repo_path,
+        &rel_commit_path,
+        &commit_message(&repo.commit.publish, &title),
+        None,
+        op,
+    )?;
+    push_queue::enqueue_and_push(app, repo_path, MutationKind::Publish, slug, commit, op)?;
+
+    frontmatter::parse_content_entry(slug, collection, &file_path)
+        .ok_or_else(|| AppError::from("Failed to parse entry after publish.").with_slug(slug))
 }
 
+/// Unpublish an entry, reporting progress under `operation_id`. Like `publish`,
+/// the scan runs before the entry is written and `allow_findings` overrides it.
 #[tauri::command]
-pub async fn unpublish(repo_path: String, slug: String) -> Result<ContentEntry, String> {
-    let base = Path::new(&repo_path);
-    let (file_path, content_type) = content::find_content_file(base, &slug)?;
-    let file_content = fs::read_to_string(&file_path).map_err(|e| e.to_string())?;
+pub async fn unpublish(
+    app: tauri::AppHandle,
+    repo_path: String,
+    slug: String,
+    allow_findings: Option<bool>,
+    operation_id: Option<String>,
+) -> Result<ContentEntry, AppError> {
+    let repo_path = security::repo_root(&app, &repo_path)?;
+    let op = Operation::begin(&app, operation_id);
+    queue::run(
+        app,
+        repo_path,
+        MutationKind::Unpublish,
+        slug,
+        move |app, repo_path, slug| {
+            unpublish_entry(app, repo_path, slug, allow_findings.unwrap_or(false), &op)
+                .map(|entry| with_push_state(app, repo_path, entry))
+        },
+    )
+    .await
+}
+
+fn unpublish_entry(
+    app: &tauri::AppHandle,
+    repo_path: &str,
+    slug: &str,
+    allow_findings: bool,
+    op: &Operation,
+) -> Result<ContentEntry, AppError> {
+    let base = Path::new(repo_path);
+    let repo = repo_config::load(base)?;
+    let (file_path, collection) = content::find_content_file(base, &repo, slug)?;
+    let file_content = fs::read_to_string(&file_path)
+        .map_err(|e| AppError::io(e.to_string()).with_path(&file_path))?;
     let (yaml, rest) = frontmatter::split_frontmatter(&file_content)
-        .ok_or_else(|| "Could not parse frontmatter.".to_string())?;
+        .ok_or_else(|| AppError::from("Could not parse frontmatter.").with_slug(slug))?;
 
     if frontmatter::get_yaml_bool(&yaml, "isDraft") == Some(true) {
-        return frontmatter::parse_content_entry(&slug, &content_type, &file_path)
-            .ok_or_else(|| "Failed to parse entry.".to_string());
+        return frontmatter::parse_content_entry(slug, collection, &file_path)
+            .ok_or_else(|| AppError::from("Failed to parse entry.").with_slug(slug));
     }
 
-    let new_yaml = frontmatter::set_frontmatter_field(&yaml, "isDraft", "true");
-    let new_content = frontmatter::assemble_file(&new_yaml, &rest);
-    fs::write(&file_path, &new_content).map_err(|e| e.to_string())?;
-
     let rel_path = file_path
         .strip_prefix(base)
         .unwrap_or(&file_path)
         .to_string_lossy()
         .to_string();
-    let title = frontmatter::get_yaml_field(&new_yaml, "title").unwrap_or_else(|| slug.clone());
-    git::git_add_commit_push(&repo_path, &rel_path, &format!("unpublish: {}", title))?;
 
-    frontmatter::parse_content_entry(&slug, &content_type, &file_path)
-        .ok_or_else(|| "Failed to parse entry after unpublish.".to_string())
+    // Scan before touching frontmatter so a blocked unpublish leaves the entry as it was.
+    if !allow_findings {
+        op.step(OperationStep::Scanning);
+        scan::check(repo_path, &rel_path, &repo.scan)?;
+    }
+    op.checkpoint()?;
+    op.step(OperationStep::Writing);
+
+    let new_yaml = frontmatter::set_frontmatter_field(&yaml, "isDraft", "true");
+    let new_content = frontmatter::assemble_file(&new_yaml, &rest);
+    save_content(app, repo_path, &file_path, &new_content)?;
+
+    let title = frontmatter::get_yaml_field(&new_yaml, "title").unwrap_or_else(|| slug.to_string());
+    let commit = git::git_add_commit(
+        repo_path,
+        &rel_path,
+        &commit_message(&repo.commit.unpublish, &title),
+        None,
+        op,
+    )?;
+    push_queue::enqueue_and_push(app, repo_path, MutationKind::Unpublish, slug, commit, op)?;
+
+    frontmatter::parse_content_entry(slug, collection, &file_path)
+        .ok_or_else(|| AppError::from("Failed to parse entry after unpublish.").with_slug(slug))
 }
 
 #[tauri::command]
 pub async fn set_pinned(
+    app: tauri::AppHandle,
     repo_path: String,
     slug: String,
     pinned: bool,
-) -> Result<ContentEntry, String> {
-    let base = Path::new(&repo_path);
-    let (file_path, content_type) = content::find_content_file(base, &slug)?;
-    let file_content =
-        fs::read_to_string(&file_path).map_err(|e| format!("Failed to read file: {e}"))?;
+) -> Result<ContentEntry, AppError> {
+    let repo_path = security::repo_root(&app, &repo_path)?;
+    queue::run(
+        app,
+        repo_path,
+        MutationKind::SetPinned,
+        slug,
+        move |app, repo_path, slug| {
+            set_pinned_entry(app, repo_path, slug, pinned)
+                .map(|entry| with_push_state(app, repo_path, entry))
+        },
+    )
+    .await
+}
+
+fn set_pinned_entry(
+    app: &tauri::AppHandle,
+    repo_path: &str,
+    slug: &str,
+    pinned: bool,
+) -> Result<ContentEntry, AppError> {
+    let base = Path::new(repo_path);
+    let repo = repo_config::load(base)?;
+    let (file_path, collection) = content::find_content_file(base, &repo, slug)?;
+    let file_content = fs::read_to_string(&file_path)
+        .map_err(|e| AppError::io(format!("Failed to read file: {e}")).with_path(&file_path))?;
     let (yaml, rest) = frontmatter::split_frontmatter(&file_content)
-        .ok_or_else(|| "Could not parse frontmatter.".to_string())?;
+        .ok_or_else(|| AppError::from("Could not parse frontmatter.").with_slug(slug))?;
 
     let current = frontmatter::get_yaml_bool(&yaml, "isPinned").unwrap_or(false);
     if current == pinned {
-        return frontmatter::parse_content_entry(&slug, &content_type, &file_path)
-            .ok_or_else(|| "Failed to parse entry.".to_string());
+        return frontmatter::parse_content_entry(slug, collection, &file_path)
+            .ok_or_else(|| AppError::from("Failed to parse entry.").with_slug(slug));
     }
 
     let new_yaml = if frontmatter::get_yaml_bool(&yaml, "isPinned").is_some() {
@@ -221,50 +580,261 @@ pub async fn set_pinned(
         frontmatter::insert_field_after(&yaml, "isDraft", "isPinned", &pinned.to_string())
     };
     let new_content = frontmatter::assemble_file(&new_yaml, &rest);
-    fs::write(&file_path, &new_content).map_err(|e| format!("Failed to write file: {e}"))?;
+    save_content(app, repo_path, &file_path, &new_content)?;
 
-    frontmatter::parse_content_entry(&slug, &content_type, &file_path)
-        .ok_or_else(|| "Failed to parse entry after pin toggle.".to_string())
+    frontmatter::parse_content_entry(slug, collection, &file_path)
+        .ok_or_else(|| AppError::from("Failed to parse entry after pin toggle.").with_slug(slug))
 }
 
 #[tauri::command]
-pub async fn rollback(repo_path: String, slug: String) -> Result<ContentEntry, String> {
-    let base = Path::new(&repo_path);
-    let (file_path, content_type) = content::find_content_file(base, &slug)?;
+pub async fn rollback(
+    app: tauri::AppHandle,
+    repo_path: String,
+    slug: String,
+) -> Result<ContentEntry, AppError> {
+    let repo_path = security::repo_root(&app, &repo_path)?;
+    queue::run(
+        app,
+        repo_path,
+        MutationKind::Rollback,
+        slug,
+        |app, repo_path, slug| {
+            rollback_entry(repo_path, slug).map(|entry| with_push_state(app, repo_path, entry))
+        },
+    )
+    .await
+}
+
+fn rollback_entry(repo_path: &str, slug: &str) -> Result<ContentEntry, AppError> {
+    let base = Path::new(repo_path);
+    let repo = repo_config::load(base)?;
+    let (file_path, collection) = content::find_content_file(base, &repo, slug)?;
     let rel_path = file_path
         .strip_prefix(base)
         .unwrap_or(&file_path)
         .to_string_lossy()
         .to_string();
 
-    let commit_hash = git::find_last_publish_commit(&repo_path, &rel_path)?;
+    let commit_hash = git::find_last_publish_commit(repo_path, &rel_path, &repo.commit.publish)?;
 
-    if content_type == "app" {
-        let rel_dir = format!("src/content/apps/{}", slug);
-        git::rollback_directory(&repo_path, &commit_hash, &rel_dir)?;
+    if collection.hash == HashMode::Directory {
+        let rel_dir = collections::rel_entry_root(base, collection, slug, &file_path);
+        git::rollback_directory(repo_path, &commit_hash, &rel_dir)?;
     } else {
-        git::rollback_file(&repo_path, &commit_hash, &rel_path)?;
+        git::rollback_file(repo_path, &commit_hash, &rel_path)?;
     }
 
-    frontmatter::parse_content_entry(&slug, &content_type, &file_path)
-        .ok_or_else(|| "Failed to parse entry after rollback.".to_string())
+    frontmatter::parse_content_entry(slug, collection, &file_path)
+        .ok_or_else(|| AppError::from("Failed to parse entry after rollback.").with_slug(slug))
+}
+
+// ---------------------------------------------------------------------------
+// Operations
+// ---------------------------------------------------------------------------
+
+/// Mutations queued or running for `repo_path`, oldest first. Changes are also
+/// pushed as `operation-queue-changed` events.
+#[tauri::command]
+pub fn get_operation_queue(
+    app: tauri::AppHandle,
+    repo_path: String,
+) -> Result<OperationQueue, AppError> {
+    let repo_path = security::repo_root(&app, &repo_path)?;
+    Ok(queue::snapshot(&app, &repo_path))
+}
+
+/// Ask a running operation to stop at its next safe point. Returns false if
+/// it already finished.
+#[tauri::command]
+pub fn cancel_operation(app: tauri::AppHandle, id: String) -> Result<bool, AppError> {
+    Ok(operations::cancel(&app, &id)?)
 }
 
 // ---------------------------------------------------------------------------
 // Git
 // ---------------------------------------------------------------------------
 
+/// Commits waiting to be pushed in `repo_path`, oldest first.
+#[tauri::command]
+pub fn list_pending_pushes(
+    app: tauri::AppHandle,
+    repo_path: String,
+) -> Result<Vec<PendingPush>, AppError> {
+    let repo_path = security::repo_root(&app, &repo_path)?;
+    push_queue::pending(&app, &repo_path)
+}
+
+/// Push queued commits now instead of waiting for the next automatic retry,
+/// e.g. when the network comes back. Returns what is still queued.
+#[tauri::command]
+pub async fn retry_pending_pushes(
+    app: tauri::AppHandle,
+    repo_path: String,
+    operation_id: Option<String>,
+) -> Result<Vec<PendingPush>, AppError> {
+    let repo_path = security::repo_root(&app, &repo_path)?;
+    let op = Operation::begin(&app, operation_id);
+    queue::run(
+        app,
+        repo_path,
+        MutationKind::Push,
+        String::new(),
+        move |app, repo_path, _| {
+            push_queue::flush(app, repo_path, &op)?;
+            push_queue::pending(app, repo_path)
+        },
+    )
+    .await
+}
+
 #[tauri::command]
-pub fn git_status(repo_path: String) -> Result<String, String> {
+pub fn git_status(app: tauri::AppHandle, repo_path: String) -> Result<String, AppError> {
+    let repo_path = security::repo_root(&app, &repo_path)?;
     git::git_status_porcelain(&repo_path)
 }
 
+// ---------------------------------------------------------------------------
+// Migrations
+// ---------------------------------------------------------------------------
+
+/// Every registered migration and the files it would change, with diffs.
+/// Writes nothing.
+#[tauri::command]
+pub fn plan_migrations(
+    app: tauri::AppHandle,
+    repo_path: String,
+) -> Result<MigrationPlan, AppError> {
+    let repo_path = security::repo_root(&app, &repo_path)?;
+    migrations::plan(&repo_path)
+}
+
+/// Apply the current plan in one commit, reporting progress under
+/// `operation_id`. Files with uncommitted edits are skipped.
+#[tauri::command]
+pub async fn apply_migrations(
+    app: tauri::AppHandle,
+    repo_path: String,
+    operation_id: Option<String>,
+) -> Result<MigrationResult, AppError> {
+    let repo_path = security::repo_root(&app, &repo_path)?;
+    let op = Operation::begin(&app, operation_id);
+    queue::run(
+        app,
+        repo_path,
+        MutationKind::Migrate,
+        String::new(),
+        move |app, repo_path, _| migrations::apply(app, repo_path, &op),
+    )
+    .await
+}
+
+// ---------------------------------------------------------------------------
+// Bulk edits
+// ---------------------------------------------------------------------------
+
+/// Every file `edit` would change, with its diff. Writes nothing.
+#[tauri::command]
+pub fn plan_bulk_edit(
+    app: tauri::AppHandle,
+    repo_path: String,
+    edit: BulkEdit,
+) -> Result<Vec<BulkEditChange>, AppError> {
+    let repo_path = security::repo_root(&app, &repo_path)?;
+    bulk::plan(&app, &repo_path, &edit)
+}
+
+/// Apply `edit` in one commit, reporting progress under `operation_id`.
+/// Files with uncommitted edits are skipped.
+#[tauri::command]
+pub async fn apply_bulk_edit(
+    app: tauri::AppHandle,
+    repo_path: String,
+    edit: BulkEdit,
+    operation_id: Option<String>,
+) -> Result<BulkEditResult, AppError> {
+    let repo_path = security::repo_root(&app, &repo_path)?;
+    let op = Operation::begin(&app, operation_id);
+    queue::run(
+        app,
+        repo_path,
+        MutationKind::BulkEdit,
+        String::new(),
+        move |app, repo_path, _| bulk::apply(app, repo_path, &edit, &op),
+    )
+    .await
+}
+
+// ---------------------------------------------------------------------------
+// Tags
+// ---------------------------------------------------------------------------
+
+#[tauri::command]
+pub fn get_taxonomy(app: tauri::AppHandle, repo_path: String) -> Result<Taxonomy, AppError> {
+    let repo_path = security::repo_root(&app, &repo_path)?;
+    taxonomy::taxonomy(&app, &repo_path)
+}
+
+/// Every file a tag rename or merge would change, with its diff. Writes nothing.
+#[tauri::command]
+pub fn plan_tag_merge(
+    app: tauri::AppHandle,
+    repo_path: String,
+    merge: TagMerge,
+) -> Result<Vec<BulkEditChange>, AppError> {
+    let repo_path = security::repo_root(&app, &repo_path)?;
+    taxonomy::plan_merge(&app, &repo_path, &merge)
+}
+
+/// Rename or merge tags in one commit, reporting progress under `operation_id`.
+/// Files with uncommitted edits are skipped.
+#[tauri::command]
+pub async fn apply_tag_merge(
+    app: tauri::AppHandle,
+    repo_path: String,
+    merge: TagMerge,
+    operation_id: Option<String>,
+) -> Result<BulkEditResult, AppError> {
+    let repo_path = security::repo_root(&app, &repo_path)?;
+    let op = Operation::begin(&app, operation_id);
+    queue::run(
+        app,
+        repo_path,
+        MutationKind::Tags,
+        String::new(),
+        move |app, repo_path, _| taxonomy::apply_merge(app, repo_path, &merge, &op),
+    )
+    .await
+}
+
+/// Set or clear a tag's description in the tags metadata file and commit it.
+#[tauri::command]
+pub async fn set_tag_description(
+    app: tauri::AppHandle,
+    repo_path: String,
+    tag: String,
+    description: String,
+    operation_id: Option<String>,
+) -> Result<Option<String>, AppError> {
+    let repo_path = security::repo_root(&app, &repo_path)?;
+    let op = Operation::begin(&app, operation_id);
+    queue::run(
+        app,
+        repo_path,
+        MutationKind::Tags,
+        String::new(),
+        move |app, repo_path, _| taxonomy::describe(app, repo_path, &tag, &description, &op),
+    )
+    .await
+}
+
 // ---------------------------------------------------------------------------
 // VS Code
 // ---------------------------------------------------------------------------
 
 #[tauri::command]
-pub fn open_in_vscode(path: String) -> Result<(), String> {
+pub fn open_in_vscode(app: tauri::AppHandle, path: String) -> Result<(), AppError> {
+    let repo_path = security::configured_repo(&app)?;
+    security::ensure_within(Path::new(&path), Path::new(&repo_path))?;
     if Command::new("code").arg(&path).spawn().is_ok() {
         return Ok(());
     }
@@ -274,53 +844,91 @@ pub fn open_in_vscode(path: String) -> Result<(), String> {
         Command::new("open")
             .args(["-a", "Visual Studio Code", &path])
             .spawn()
-            .map_err(|e| format!("Failed to open VS Code: {}", e))?;
+            .map_err(|e| AppError::not_found(format!("Failed to open VS Code: {}", e)))?;
         Ok(())
     }
 
     #[cfg(not(target_os = "macos"))]
-    Err("VS Code CLI ('code') not found in PATH.".to_string())
+    Err(AppError::not_found(
+        "VS Code CLI ('code') not found in PATH.",
+    ))
 }
 
 // ---------------------------------------------------------------------------
 // URL health check
 // ---------------------------------------------------------------------------
 
-#[tauri::command]
-pub async fn check_url_health(app: tauri::AppHandle, url: String) -> Result<HealthStatus, String> {
-    if !url.starts_with("http://") && !url.starts_with("https://") {
-        return Err("Only HTTP(S) URLs are allowed".to_string());
-    }
-    let lower = url.to_lowercase();
-    let is_dev_server = lower.starts_with("http://localhost:4322");
-    if !is_dev_server
-        && (lower.contains("://localhost")
-            || lower.contains("://127.")
-            || lower.contains("://0.0.0.0")
-            || lower.contains("://[::1]")
-            || lower.contains("://10.")
-            || lower.contains("://192.168."))
-    {
-        return Err("Requests to local/private addresses are not allowed".to_string());
-    }
+/// Redirect hops followed before a health check gives up.
+const MAX_REDIRECTS: usize = 5;
 
-    let client = &app.state::<HttpClient>().0;
+#[tauri::command]
+pub async fn check_url_health(
+    app: tauri::AppHandle,
+    url: String,
+) -> Result<HealthStatus, AppError> {
+    let mut current = reqwest::Url::parse(&url)
+        .map_err(|e| AppError::invalid_input(format!("Invalid URL: {}", e)))?;
+    let dev_port = config::load_config(&app)?
+        .repo_path
+        .and_then(|repo_path| repo_config::load(repo_path).ok())
+        .unwrap_or_default()
+        .dev_server
+        .port;
+
+    let unreachable = |url: String| HealthStatus {
+        url,
+        ok: false,
+        status_code: None,
+    };
 
-    match client.head(&url).send().await {
-        Ok(resp) => {
-            let status = resp.status().as_u16();
-            Ok(HealthStatus {
+    // Every hop is resolved and checked before connecting, and the connection is
+    // pinned to the checked addresses, so neither DNS nor a redirect can steer
+    // the request to a private host.
+    for _ in 0..=MAX_REDIRECTS {
+        if !matches!(current.scheme(), "http" | "https") {
+            return Err(AppError::invalid_input("Only HTTP(S) URLs are allowed"));
+        }
+        let mut builder = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .timeout(std::time::Duration::from_secs(15));
+        if !security::is_dev_server_url(&current, dev_port) {
+            let addrs = security::resolve_public(&current)
+                .await
+                .map_err(AppError::invalid_input)?;
+            if let Some(host) = current.domain() {
+                builder = builder.resolve_to_addrs(host, &addrs);
+            }
+        }
+        let client = builder.build().map_err(|e| e.to_string())?;
+
+        let resp = match client.head(current.clone()).send().await {
+            Ok(resp) => resp,
+            Err(_) => return Ok(unreachable(url)),
+        };
+        let status = resp.status();
+        if !status.is_redirection() {
+            return Ok(HealthStatus {
                 url,
-                ok: resp.status().is_success(),
-                status_code: Some(status),
-            })
+                ok: status.is_success(),
+                status_code: Some(status.as_u16()),
+            });
         }
-        Err(_) => Ok(HealthStatus {
-            url,
-            ok: false,
-            status_code: None,
-        }),
+        let Some(next) = resp
+            .headers()
+            .get(reqwest::header::LOCATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|loc| current.join(loc).ok())
+        else {
+            return Ok(HealthStatus {
+                url,
+                ok: false,
+                status_code: Some(status.as_u16()),
+            });
+        };
+        current = next;
     }
+
+    Ok(unreachable(url))
 }
 
 // ---------------------------------------------------------------------------
@@ -328,69 +936,75 @@ pub async fn check_url_health(app: tauri::AppHandle, url: String) -> Result<Heal
 // ---------------------------------------------------------------------------
 
 #[tauri::command]
-pub fn start_watcher(app: tauri::AppHandle, repo_path: String) -> Result<(), String> {
-    crate::watcher::start_watching(&app, &repo_path)
+pub fn start_watcher(app: tauri::AppHandle, repo_path: String) -> Result<(), AppError> {
+    let repo_path = security::repo_root(&app, &repo_path)?;
+    Ok(crate::watcher::start_watching(&app, &repo_path)?)
 }
 
 #[tauri::command]
-pub fn stop_watcher(app: tauri::AppHandle) -> Result<(), String> {
-    crate::watcher::stop_watching(&app)
+pub fn stop_watcher(app: tauri::AppHandle) -> Result<(), AppError> {
+    Ok(crate::watcher::stop_watching(&app)?)
 }
 
 #[tauri::command]
-pub fn start_dev_server(app: tauri::AppHandle, repo_path: String) -> Result<(), String> {
+pub fn start_dev_server(app: tauri::AppHandle, repo_path: String) -> Result<(), AppError> {
+    let repo_path = security::repo_root(&app, &repo_path)?;
     let state = app.state::<crate::devserver::DevServerState>();
-    crate::devserver::start_dev_server(&state, &repo_path)
+    Ok(crate::devserver::start_dev_server(&state, &repo_path)?)
 }
 
 #[tauri::command]
-pub fn stop_dev_server(app: tauri::AppHandle) -> Result<(), String> {
+pub fn stop_dev_server(app: tauri::AppHandle) -> Result<(), AppError> {
     let state = app.state::<crate::devserver::DevServerState>();
-    crate::devserver::stop_dev_server(&state)
+    Ok(crate::devserver::stop_dev_server(&state)?)
 }
 
 // ---------------------------------------------------------------------------
 // Cloudflare
 // ---------------------------------------------------------------------------
 
+/// A Cloudflare setting the command can't run without.
+fn required<'a>(value: Option<&'a str>, what: &str) -> Result<&'a str, AppError> {
+    value
+        .filter(|v| !v.is_empty())
+        .ok_or_else(|| AppError::not_configured(format!("{what} not configured")))
+}
+
 #[tauri::command]
-pub async fn fetch_last_deployment(app: tauri::AppHandle) -> Result<CfDeploymentInfo, String> {
-    let cfg = config::load_config(&app);
-    let account_id = cfg
-        .cf_account_id
-        .as_deref()
-        .ok_or("Cloudflare account ID not configured")?;
-    let project_name = cfg
-        .cf_project_name
-        .as_deref()
-        .ok_or("Cloudflare project name not configured")?;
-    let api_token = cfg
-        .cf_api_token
-        .as_deref()
-        .ok_or("Cloudflare API token not configured")?;
+pub async fn fetch_last_deployment(app: tauri::AppHandle) -> Result<CfDeploymentInfo, AppError> {
+    let cfg = config::load_config(&app)?;
+    let account_id = required(cfg.cf_account_id.as_deref(), "Cloudflare account ID")?;
+    let project_name = required(cfg.cf_project_name.as_deref(), "Cloudflare project name")?;
+    let api_token = config::cf_api_token(&app)?;
     let client = &app.state::<HttpClient>().0;
 
-    cloudflare::fetch_last_deployment(client, account_id, project_name, api_token).await
+    cloudflare::fetch_last_deployment(client, account_id, project_name, &api_token).await
 }
 
+/// Fetch traffic analytics, reporting each GraphQL query under `operation_id`.
 #[tauri::command]
-pub async fn fetch_analytics(app: tauri::AppHandle, days: u32, engagement: bool) -> Result<CfAnalytics, String> {
-    let mut cfg = config::load_config(&app);
-    let api_token = cfg
-        .cf_api_token
-        .as_deref()
-        .ok_or("Cloudflare API token not configured")?
-        .to_string();
+pub async fn fetch_analytics(
+    app: tauri::AppHandle,
+    days: u32,
+    engagement: bool,
+    compare: bool,
+    operation_id: Option<String>,
+) -> Result<CfAnalytics, AppError> {
+    if !(1..=cloudflare::MAX_ANALYTICS_DAYS).contains(&days) {
+        return Err(AppError::invalid_input(format!(
+            "Days must be between 1 and {}.",
+            cloudflare::MAX_ANALYTICS_DAYS
+        )));
+    }
+    let mut cfg = config::load_config(&app)?;
+    let api_token = config::cf_api_token(&app)?;
     let client = &app.state::<HttpClient>().0;
 
     // Auto-discover zone_id if missing
     let zone_id = match cfg.cf_zone_id.as_deref() {
         Some(id) if !id.is_empty() => id.to_string(),
         _ => {
-            let domain = cfg
-                .cf_domain
-                .as_deref()
-                .ok_or("Cloudflare domain not configured")?;
+            let domain = required(cfg.cf_domain.as_deref(), "Cloudflare domain")?;
             let id = cloudflare::fetch_zone_id(client, &api_token, domain).await?;
             // Cache the discovered zone_id
             cfg.cf_zone_id = Some(id.clone());
@@ -399,35 +1013,76 @@ pub async fn fetch_analytics(app: tauri::AppHandle, days: u32, engagement: bool)
         }
     };
 
-    cloudflare::fetch_analytics(client, &api_token, &zone_id, days, engagement).await
+    let op = Operation::begin(&app, operation_id);
+    let analytics =
+        cloudflare::fetch_analytics(client, &api_token, &zone_id, days, engagement, compare, &op)
+            .await?;
+    let _ = history::record(&app, &analytics, engagement); // best-effort
+    Ok(analytics)
+}
+
+#[tauri::command]
+pub fn export_analytics(
+    app: tauri::AppHandle,
+    source: AnalyticsExportSource,
+    format: ExportFormat,
+    dir: String,
+) -> Result<Vec<String>, AppError> {
+    let analytics = match source {
+        AnalyticsExportSource::Analytics { analytics } => *analytics,
+        AnalyticsExportSource::History {
+            from,
+            to,
+            engagement,
+        } => history::range(&app, &from, &to, engagement)?,
+    };
+    Ok(export::export_analytics(
+        &analytics,
+        format,
+        Path::new(&dir),
+    )?)
+}
+
+#[tauri::command]
+pub async fn fetch_web_analytics(
+    app: tauri::AppHandle,
+    days: u32,
+) -> Result<CfWebAnalytics, AppError> {
+    if !(1..=cloudflare::MAX_WEB_ANALYTICS_DAYS).contains(&days) {
+        return Err(AppError::invalid_input(format!(
+            "Days must be between 1 and {}.",
+            cloudflare::MAX_WEB_ANALYTICS_DAYS
+        )));
+    }
+    let cfg = config::load_config(&app)?;
+    let api_token = config::cf_api_token(&app)?;
+    let account_id = required(cfg.cf_account_id.as_deref(), "Cloudflare account ID")?;
+    let site_tag = required(
+        cfg.cf_site_tag.as_deref(),
+        "Cloudflare Web Analytics site tag",
+    )?;
+    let client = &app.state::<HttpClient>().0;
+
+    cloudflare::fetch_web_analytics(client, &api_token, account_id, site_tag, days).await
 }
 
 #[tauri::command]
-pub async fn test_cf_connection(app: tauri::AppHandle) -> Result<String, String> {
-    let cfg = config::load_config(&app);
-    let api_token = cfg
-        .cf_api_token
-        .as_deref()
-        .ok_or("Cloudflare API token not configured")?;
-    let account_id = cfg
-        .cf_account_id
-        .as_deref()
-        .ok_or("Cloudflare account ID not configured")?;
-    let project_name = cfg
-        .cf_project_name
-        .as_deref()
-        .ok_or("Cloudflare project name not configured")?;
+pub async fn test_cf_connection(app: tauri::AppHandle) -> Result<String, AppError> {
+    let cfg = config::load_config(&app)?;
+    let api_token = config::cf_api_token(&app)?;
+    let account_id = required(cfg.cf_account_id.as_deref(), "Cloudflare account ID")?;
+    let project_name = required(cfg.cf_project_name.as_deref(), "Cloudflare project name")?;
     let client = &app.state::<HttpClient>().0;
 
     // Validate zone lookup if domain is set
     if let Some(domain) = cfg.cf_domain.as_deref() {
         if !domain.is_empty() {
-            cloudflare::fetch_zone_id(client, api_token, domain).await?;
+            cloudflare::fetch_zone_id(client, &api_token, domain).await?;
         }
     }
 
     // Validate deployment access
-    cloudflare::fetch_last_deployment(client, account_id, project_name, api_token).await?;
+    cloudflare::fetch_last_deployment(client, account_id, project_name, &api_token).await?;
 
     Ok("Connection successful — deployment data accessible.".to_string())
 }
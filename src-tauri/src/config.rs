@@ -1,11 +1,135 @@
 //! Application configuration — persistence via JSON in the Tauri app data directory.
+//!
+//! On disk, settings are grouped into named site profiles (one per repo and
+//! Cloudflare project). Across IPC, `AppConfig` is a flat view of the active
+//! profile plus the list of profile names.
+//!
+//! The file carries a schema `version`. Older files are upgraded through the
+//! explicit steps in `MIGRATIONS`; an unreadable file is backed up and reported
+//! as an error rather than silently replaced with defaults. A migration that
+//! must move tokens into a secret store that can't take them yet (locked)
+//! leaves the file at its old version until a later load.
+//!
+//! Credentials are not stored here: see `secrets.rs`.
 
+use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
 use tauri::Manager;
 
+use crate::error::{AppError, ErrorCode};
+use crate::files;
+use crate::frontmatter::calculate_content_hash;
+use crate::secrets;
 use crate::types::AppConfig;
 
+/// Name given to the profile created when migrating a pre-profile config file.
+const DEFAULT_PROFILE: &str = "default";
+
+/// Current config schema version.
+/// 1: flat settings (no `version` field). 2: named site profiles.
+/// 3: API tokens moved out to the encrypted secret store.
+const CONFIG_VERSION: u32 = 3;
+
+/// Side effects a migration step asks the loader to perform once the
+/// migrated config is about to be persisted.
+#[derive(Default)]
+struct MigrationContext {
+    /// `(profile, secret name, value)` triples to move into the secret store.
+    secrets: Vec<(String, String, String)>,
+}
+
+type Migration = fn(serde_json::Value, &mut MigrationContext) -> Result<serde_json::Value, String>;
+
+/// Upgrade steps: `MIGRATIONS[n - 1]` turns a version `n` document into version `n + 1`.
+/// Steps operate on raw JSON so they don't depend on the current struct layout.
+const MIGRATIONS: &[Migration] = &[migrate_v1_to_v2, migrate_v2_to_v3];
+
+/// Settings for a single site: its repository and Cloudflare project.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SiteProfile {
+    pub name: String,
+    pub repo_path: Option<String>,
+    pub cf_account_id: Option<String>,
+    pub cf_project_name: Option<String>,
+    pub cf_domain: Option<String>,
+    pub cf_zone_id: Option<String>,
+    pub cf_site_tag: Option<String>,
+}
+
+/// The config file as stored on disk.
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredConfig {
+    #[serde(default)]
+    version: u32,
+    theme: Option<String>,
+    active_profile: String,
+    profiles: Vec<SiteProfile>,
+    /// Secrets the version 2 → 3 migration couldn't move into the store yet
+    /// (e.g. it is locked), as `MigrationContext::secrets`. While any are
+    /// left the file is saved as version 2 with them in place.
+    #[serde(skip)]
+    deferred_secrets: Vec<(String, String, String)>,
+}
+
+impl Default for StoredConfig {
+    fn default() -> Self {
+        Self {
+            version: CONFIG_VERSION,
+            theme: Some("light".into()),
+            active_profile: DEFAULT_PROFILE.into(),
+            profiles: vec![SiteProfile {
+                name: DEFAULT_PROFILE.into(),
+                ..Default::default()
+            }],
+            deferred_secrets: Vec::new(),
+        }
+    }
+}
+
+impl StoredConfig {
+    fn active(&self) -> Option<&SiteProfile> {
+        self.profiles.iter().find(|p| p.name == self.active_profile)
+    }
+
+    /// Return the active profile, creating it if the name doesn't match any profile.
+    fn active_mut(&mut self) -> &mut SiteProfile {
+        let idx = match self
+            .profiles
+            .iter()
+            .position(|p| p.name == self.active_profile)
+        {
+            Some(idx) => idx,
+            None => {
+                self.profiles.push(SiteProfile {
+                    name: self.active_profile.clone(),
+                    ..Default::default()
+                });
+                self.profiles.len() - 1
+            }
+        };
+        &mut self.profiles[idx]
+    }
+
+    /// Flatten the active profile into the IPC-facing `AppConfig`.
+    fn to_app_config(&self) -> AppConfig {
+        let profile = self.active().cloned().unwrap_or_default();
+        AppConfig {
+            repo_path: profile.repo_path,
+            theme: self.theme.clone(),
+            cf_account_id: profile.cf_account_id,
+            cf_project_name: profile.cf_project_name,
+            cf_api_token: None,
+            cf_domain: profile.cf_domain,
+            cf_zone_id: profile.cf_zone_id,
+            cf_site_tag: profile.cf_site_tag,
+            active_profile: Some(self.active_profile.clone()),
+            profiles: self.profiles.iter().map(|p| p.name.clone()).collect(),
+        }
+    }
+}
+
 /// Return the path to the config JSON file in the platform app-data directory.
 pub fn config_path(app: &tauri::AppHandle) -> PathBuf {
     let dir = app
@@ -15,41 +139,423 @@ pub fn config_path(app: &tauri::AppHandle) -> PathBuf {
     dir.join("config.json")
 }
 
-/// Load the persisted config, falling back to defaults if the file is missing or corrupt.
-pub fn load_config(app: &tauri::AppHandle) -> AppConfig {
+/// Version 1 → 2: wrap the flat settings into a single "default" profile.
+fn migrate_v1_to_v2(
+    value: serde_json::Value,
+    _ctx: &mut MigrationContext,
+) -> Result<serde_json::Value, String> {
+    let mut profile = value.as_object().cloned().ok_or("expected a JSON object")?;
+    let theme = profile.remove("theme").unwrap_or(serde_json::Value::Null);
+    profile.insert("name".into(), DEFAULT_PROFILE.into());
+    Ok(serde_json::json!({
+        "version": 2,
+        "theme": theme,
+        "active_profile": DEFAULT_PROFILE,
+        "profiles": [profile],
+    }))
+}
+
+/// Version 2 → 3: move each profile's plaintext `cf_api_token` into the secret store.
+fn migrate_v2_to_v3(
+    mut value: serde_json::Value,
+    ctx: &mut MigrationContext,
+) -> Result<serde_json::Value, String> {
+    if let Some(profiles) = value["profiles"].as_array_mut() {
+        for profile in profiles.iter_mut().filter_map(|p| p.as_object_mut()) {
+            let name = profile["name"]
+                .as_str()
+                .unwrap_or(DEFAULT_PROFILE)
+                .to_string();
+            if let Some(serde_json::Value::String(token)) = profile.remove(secrets::CF_API_TOKEN) {
+                if !token.is_empty() {
+                    ctx.secrets
+                        .push((name, secrets::CF_API_TOKEN.to_string(), token));
+                }
+            }
+        }
+    }
+    value["version"] = 3.into();
+    Ok(value)
+}
+
+/// Strip credentials from a raw config document (any version) before it is
+/// copied anywhere, so backups never hold plaintext tokens.
+fn redact_secrets(value: &mut serde_json::Value) {
+    if let Some(obj) = value.as_object_mut() {
+        obj.remove(secrets::CF_API_TOKEN);
+    }
+    if let Some(profiles) = value["profiles"].as_array_mut() {
+        for profile in profiles.iter_mut().filter_map(|p| p.as_object_mut()) {
+            profile.remove(secrets::CF_API_TOKEN);
+        }
+    }
+}
+
+/// Files written before versioning have no `version` field; tell them apart by shape.
+fn detect_version(value: &serde_json::Value) -> u32 {
+    match value.get("version").and_then(|v| v.as_u64()) {
+        Some(v) => v as u32,
+        None if value.get("profiles").is_some() => 2,
+        None => 1,
+    }
+}
+
+/// Parse a config file and run any pending migrations.
+/// Returns the config, the version the file was written with, and the
+/// side effects requested by the migrations that ran.
+fn parse_stored(data: &str) -> Result<(StoredConfig, u32, MigrationContext), String> {
+    let mut value: serde_json::Value =
+        serde_json::from_str(data).map_err(|e| format!("invalid JSON: {e}"))?;
+    let from_version = detect_version(&value);
+    if from_version == 0 {
+        return Err("invalid schema version 0".to_string());
+    }
+    if from_version > CONFIG_VERSION {
+        return Err(format!(
+            "written by a newer panel (schema version {from_version}, this build supports {CONFIG_VERSION})"
+        ));
+    }
+
+    let mut ctx = MigrationContext::default();
+    for step in &MIGRATIONS[(from_version - 1) as usize..(CONFIG_VERSION - 1) as usize] {
+        value = step(value, &mut ctx)?;
+    }
+
+    let mut stored: StoredConfig =
+        serde_json::from_value(value).map_err(|e| format!("unexpected layout: {e}"))?;
+    stored.version = CONFIG_VERSION;
+    Ok((stored, from_version, ctx))
+}
+
+/// Copy an unreadable config file next to the original. The name includes a
+/// content hash so repeated loads of the same broken file don't pile up copies.
+fn backup_corrupt(path: &Path, data: &str) -> Result<PathBuf, String> {
+    let hash = calculate_content_hash(data);
+    let backup = path.with_file_name(format!("config.corrupt-{}.json", &hash[..8]));
+    if !backup.exists() {
+        fs::write(&backup, data).map_err(|e| format!("Failed to back up config file: {e}"))?;
+    }
+    Ok(backup)
+}
+
+/// Load the stored config, migrating older schema versions in place.
+/// A missing file yields defaults; an unreadable one is backed up and reported.
+fn load_stored(app: &tauri::AppHandle) -> Result<StoredConfig, AppError> {
     let path = config_path(app);
-    if path.exists() {
-        let data = fs::read_to_string(&path).unwrap_or_default();
-        serde_json::from_str(&data).unwrap_or(AppConfig {
-            repo_path: None,
-            theme: Some("light".into()),
-            cf_account_id: None,
-            cf_project_name: None,
-            cf_api_token: None,
-            cf_domain: None,
-            cf_zone_id: None,
-        })
-    } else {
-        AppConfig {
-            repo_path: None,
-            theme: Some("light".into()),
-            cf_account_id: None,
-            cf_project_name: None,
-            cf_api_token: None,
-            cf_domain: None,
-            cf_zone_id: None,
+    let data = match fs::read_to_string(&path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(StoredConfig::default()),
+        Err(e) => {
+            return Err(AppError::io(format!("Failed to read config file: {e}")).with_path(&path))
+        }
+    };
+
+    match parse_stored(&data) {
+        Ok((mut stored, from_version, ctx)) => {
+            if from_version < CONFIG_VERSION {
+                // Move extracted secrets into the store first. If it can't take
+                // them yet, nothing is written and a later load migrates again.
+                let moved = ctx
+                    .secrets
+                    .iter()
+                    .try_for_each(|(profile, name, value)| secrets::set(app, profile, name, value));
+                if moved.is_err() {
+                    stored.deferred_secrets = ctx.secrets;
+                    return Ok(stored);
+                }
+                // Keep the pre-migration file around (minus credentials), then
+                // persist the upgrade.
+                let mut original: serde_json::Value =
+                    serde_json::from_str(&data).map_err(|e| e.to_string())?;
+                redact_secrets(&mut original);
+                let backup = path.with_file_name(format!("config.v{from_version}.json"));
+                let backup_data =
+                    serde_json::to_string_pretty(&original).map_err(|e| e.to_string())?;
+                fs::write(&backup, backup_data)
+                    .map_err(|e| format!("Failed to back up config before migration: {e}"))?;
+                save_stored(app, &stored)?;
+            }
+            Ok(stored)
+        }
+        Err(reason) => {
+            let backup = backup_corrupt(&path, &data)?;
+            Err(AppError::new(
+                ErrorCode::ConfigCorrupt,
+                format!(
+                    "Config file could not be loaded ({reason}). A copy was saved to {}. \
+                     Fix the file or reset settings to continue.",
+                    backup.display()
+                ),
+            )
+            .with_path(&backup))
         }
     }
 }
 
-/// Persist the config to disk, creating the parent directory if needed.
-pub fn save_config(app: &tauri::AppHandle, config: &AppConfig) -> Result<(), String> {
+/// Write the config atomically (see `files::write_atomic`).
+fn save_stored(app: &tauri::AppHandle, stored: &StoredConfig) -> Result<(), String> {
     let path = config_path(app);
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent)
             .map_err(|e| format!("Failed to create config directory: {e}"))?;
     }
-    let data = serde_json::to_string_pretty(config)
+    let data = to_document(stored)
+        .and_then(|value| serde_json::to_string_pretty(&value))
         .map_err(|e| format!("Failed to serialize config: {e}"))?;
-    fs::write(&path, data).map_err(|e| format!("Failed to write config file: {e}"))
+    files::write_atomic(&path, data.as_bytes())
+}
+
+/// The JSON written for `stored`: the current version, or version 2 with its
+/// tokens back in their profiles while their migration is deferred.
+fn to_document(stored: &StoredConfig) -> serde_json::Result<serde_json::Value> {
+    let mut value = serde_json::to_value(stored)?;
+    if stored.deferred_secrets.is_empty() {
+        return Ok(value);
+    }
+    value["version"] = 2.into();
+    if let Some(profiles) = value["profiles"].as_array_mut() {
+        for (profile, name, secret) in &stored.deferred_secrets {
+            if let Some(p) = profiles.iter_mut().find(|p| p["name"] == profile.as_str()) {
+                p[name] = secret.as_str().into();
+            }
+        }
+    }
+    Ok(value)
+}
+
+/// Flat view of the active profile for the frontend. `cf_api_token` is only
+/// ever a masked placeholder, present when a token is stored.
+fn view(app: &tauri::AppHandle, stored: &StoredConfig) -> AppConfig {
+    let mut cfg = stored.to_app_config();
+    let deferred = stored.deferred_secrets.iter().any(|(profile, name, _)| {
+        *profile == stored.active_profile && name == secrets::CF_API_TOKEN
+    });
+    if deferred || secrets::has(app, &stored.active_profile, secrets::CF_API_TOKEN) {
+        cfg.cf_api_token = Some(secrets::MASKED.to_string());
+    }
+    cfg
+}
+
+/// Load the persisted config as a flat view of the active profile.
+pub fn load_config(app: &tauri::AppHandle) -> Result<AppConfig, AppError> {
+    load_stored(app).map(|stored| view(app, &stored))
+}
+
+/// Replace the config with defaults. The only path that discards an unreadable
+/// file, and only on explicit request — `load_stored` has already backed it up.
+pub fn reset_config(app: &tauri::AppHandle) -> Result<AppConfig, AppError> {
+    let path = config_path(app);
+    if let Ok(data) = fs::read_to_string(&path) {
+        if parse_stored(&data).is_err() {
+            backup_corrupt(&path, &data)?;
+        }
+    }
+    let stored = StoredConfig::default();
+    save_stored(app, &stored)?;
+    Ok(view(app, &stored))
+}
+
+/// Persist the config: the theme globally, everything else into the active profile.
+/// `cf_api_token` is ignored; tokens are managed through `secrets.rs`.
+pub fn save_config(app: &tauri::AppHandle, config: &AppConfig) -> Result<(), AppError> {
+    let mut stored = load_stored(app)?;
+    stored.theme = config.theme.clone();
+    let profile = stored.active_mut();
+    profile.repo_path = config.repo_path.clone();
+    profile.cf_account_id = config.cf_account_id.clone();
+    profile.cf_project_name = config.cf_project_name.clone();
+    profile.cf_domain = config.cf_domain.clone();
+    profile.cf_zone_id = config.cf_zone_id.clone();
+    profile.cf_site_tag = config.cf_site_tag.clone();
+    Ok(save_stored(app, &stored)?)
+}
+
+/// Make `name` the active profile.
+pub fn switch_profile(app: &tauri::AppHandle, name: &str) -> Result<AppConfig, AppError> {
+    let mut stored = load_stored(app)?;
+    if !stored.profiles.iter().any(|p| p.name == name) {
+        return Err(AppError::not_found(format!("No profile named \"{name}\".")));
+    }
+    stored.active_profile = name.to_string();
+    save_stored(app, &stored)?;
+    Ok(view(app, &stored))
+}
+
+/// Create an empty profile and make it active.
+pub fn create_profile(app: &tauri::AppHandle, name: &str) -> Result<AppConfig, AppError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(AppError::invalid_input("Profile name cannot be empty."));
+    }
+    let mut stored = load_stored(app)?;
+    if stored.profiles.iter().any(|p| p.name == name) {
+        return Err(AppError::new(
+            ErrorCode::AlreadyExists,
+            format!("Profile \"{}\" already exists.", name),
+        ));
+    }
+    stored.profiles.push(SiteProfile {
+        name: name.to_string(),
+        ..Default::default()
+    });
+    stored.active_profile = name.to_string();
+    save_stored(app, &stored)?;
+    Ok(view(app, &stored))
+}
+
+/// Delete a profile and its stored credentials. If it was active, the first remaining profile becomes active.
+pub fn delete_profile(app: &tauri::AppHandle, name: &str) -> Result<AppConfig, AppError> {
+    let mut stored = load_stored(app)?;
+    if stored.profiles.len() <= 1 {
+        return Err(AppError::invalid_input("Cannot delete the only profile."));
+    }
+    let before = stored.profiles.len();
+    stored.profiles.retain(|p| p.name != name);
+    if stored.profiles.len() == before {
+        return Err(AppError::not_found(format!("No profile named \"{name}\".")));
+    }
+    if stored.active_profile == name {
+        stored.active_profile = stored.profiles[0].name.clone();
+    }
+    save_stored(app, &stored)?;
+    // The profile is gone either way; a locked store just keeps an orphaned entry.
+    let _ = secrets::remove_profile(app, name);
+    Ok(view(app, &stored))
+}
+
+/// Decrypt the active profile's Cloudflare API token (backend use only).
+/// Only a token that was never stored is `NotConfigured`; a locked store or a
+/// token that won't decrypt keeps its own code (see `secrets::get`).
+pub fn cf_api_token(app: &tauri::AppHandle) -> Result<String, AppError> {
+    let stored = load_stored(app)?;
+    secrets::get(app, &stored.active_profile, secrets::CF_API_TOKEN)?
+        .filter(|t| !t.is_empty())
+        .ok_or_else(|| AppError::not_configured("Cloudflare API token not configured"))
+}
+
+/// Store (or rotate) the active profile's Cloudflare API token.
+pub fn set_cf_api_token(app: &tauri::AppHandle, token: &str) -> Result<AppConfig, AppError> {
+    let token = token.trim();
+    if token.is_empty() || token == secrets::MASKED {
+        return Err(AppError::invalid_input("API token cannot be empty."));
+    }
+    let stored = load_stored(app)?;
+    secrets::set(app, &stored.active_profile, secrets::CF_API_TOKEN, token)?;
+    Ok(view(app, &stored))
+}
+
+/// Remove the active profile's Cloudflare API token.
+pub fn clear_cf_api_token(app: &tauri::AppHandle) -> Result<AppConfig, AppError> {
+    let stored = load_stored(app)?;
+    secrets::remove(app, &stored.active_profile, secrets::CF_API_TOKEN)?;
+    Ok(view(app, &stored))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn v1_file_becomes_a_default_profile() {
+        let (stored, from, ctx) =
+            parse_stored(r#"{"repo_path": "/site", "theme": "dark", "cf_api_token": "secret"}"#)
+                .unwrap();
+        assert_eq!(from, 1);
+        assert_eq!(stored.version, CONFIG_VERSION);
+        assert_eq!(stored.theme.as_deref(), Some("dark"));
+        assert_eq!(stored.active_profile, DEFAULT_PROFILE);
+        assert_eq!(stored.profiles.len(), 1);
+        assert_eq!(stored.profiles[0].repo_path.as_deref(), Some("/site"));
+        assert_eq!(
+            ctx.secrets,
+            [(
+                DEFAULT_PROFILE.to_string(),
+                secrets::CF_API_TOKEN.to_string(),
+                "secret".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn v2_tokens_are_extracted_per_profile() {
+        let (stored, from, ctx) = parse_stored(
+            r#"{"version": 2, "theme": null, "active_profile": "b", "profiles": [
+                {"name": "a", "cf_api_token": "token-a"},
+                {"name": "b", "cf_api_token": ""},
+                {"name": "c"}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(from, 2);
+        assert_eq!(stored.profiles.len(), 3);
+        assert_eq!(stored.active_profile, "b");
+        // Empty tokens are dropped rather than stored.
+        assert_eq!(
+            ctx.secrets,
+            [(
+                "a".to_string(),
+                secrets::CF_API_TOKEN.to_string(),
+                "token-a".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn unversioned_profiles_file_is_v2() {
+        let (_, from, _) =
+            parse_stored(r#"{"active_profile": "default", "profiles": [{"name": "default"}]}"#)
+                .unwrap();
+        assert_eq!(from, 2);
+    }
+
+    #[test]
+    fn current_file_needs_no_migration() {
+        let data = serde_json::to_string(&StoredConfig::default()).unwrap();
+        let (stored, from, ctx) = parse_stored(&data).unwrap();
+        assert_eq!(from, CONFIG_VERSION);
+        assert_eq!(stored.profiles[0].name, DEFAULT_PROFILE);
+        assert!(ctx.secrets.is_empty());
+    }
+
+    #[test]
+    fn unreadable_files_are_rejected() {
+        assert!(parse_stored("{").is_err());
+        assert!(parse_stored(r#"{"version": 0}"#).is_err());
+        let newer = format!(r#"{{"version": {}}}"#, CONFIG_VERSION + 1);
+        assert!(matches!(parse_stored(&newer), Err(e) if e.contains("newer panel")));
+        assert!(parse_stored(r#"{"version": 3, "profiles": 7}"#).is_err());
+    }
+
+    #[test]
+    fn redaction_strips_tokens_at_every_level() {
+        let mut value = serde_json::json!({
+            "cf_api_token": "flat",
+            "profiles": [{"name": "a", "cf_api_token": "nested", "repo_path": "/site"}],
+        });
+        redact_secrets(&mut value);
+        assert!(!value.to_string().contains("flat"));
+        assert!(!value.to_string().contains("nested"));
+        assert_eq!(value["profiles"][0]["repo_path"], "/site");
+    }
+
+    #[test]
+    fn deferred_secrets_are_saved_as_v2() {
+        let mut stored = StoredConfig::default();
+        assert_eq!(to_document(&stored).unwrap()["version"], CONFIG_VERSION);
+
+        stored.deferred_secrets = vec![(
+            DEFAULT_PROFILE.to_string(),
+            secrets::CF_API_TOKEN.to_string(),
+            "token".to_string(),
+        )];
+        let value = to_document(&stored).unwrap();
+        assert_eq!(value["version"], 2);
+        assert_eq!(value["profiles"][0][secrets::CF_API_TOKEN], "token");
+        assert!(value.get("deferred_secrets").is_none());
+
+        // Loading it again picks the migration back up.
+        let (_, from, ctx) = parse_stored(&value.to_string()).unwrap();
+        assert_eq!(from, 2);
+        assert_eq!(ctx.secrets, stored.deferred_secrets);
+    }
 }
@@ -1,301 +1,218 @@
-//! Content CRUD — listing, creating, and deleting blog posts and apps.
+//! Content CRUD — listing, creating, and deleting entries in the configured collections.
 
+use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::collections::{self, ScaffoldVars};
+use crate::error::{AppError, ErrorCode};
+use crate::files;
 use crate::frontmatter::{now_iso, parse_content_entry, to_slug};
-use crate::git::git_add_commit_push;
-use crate::security::escape_yaml_string;
-use crate::types::{ContentEntry, CreateAppArgs, CreatePostArgs};
-
-/// Scan the blog and apps content directories, returning all entries sorted by creation date.
-pub fn list_content(repo_path: &str) -> Result<Vec<ContentEntry>, String> {
+use crate::git::{self, git_add_commit};
+use crate::operations::Operation;
+use crate::push_queue;
+use crate::repo_config::{self, commit_message};
+use crate::security::{ensure_within, validate_slug};
+use crate::types::{
+    CollectionConfig, ContentEntry, CreateEntryArgs, MutationKind, OperationStep, RepoConfig,
+};
+use crate::watcher;
+
+/// Create a new entry in a collection from its scaffold.
+pub fn create_entry(
+    repo_path: &str,
+    collection_id: &str,
+    args: CreateEntryArgs,
+) -> Result<ContentEntry, AppError> {
+    let repo = repo_config::load(repo_path)?;
+    let collection = repo_config::collection(&repo, collection_id).map_err(AppError::not_found)?;
     let base = Path::new(repo_path);
-    let blog_dir = base.join("src/content/blog");
-    let apps_dir = base.join("src/content/apps");
-    let mut entries: Vec<ContentEntry> = Vec::new();
-
-    // Scan blog posts
-    if blog_dir.is_dir() {
-        if let Ok(readdir) = fs::read_dir(&blog_dir) {
-            for entry in readdir.flatten() {
-                if entry.path().is_dir() {
-                    let slug = entry.file_name().to_string_lossy().to_string();
-                    let mdx_path = entry.path().join("index.mdx");
-                    let md_path = entry.path().join("index.md");
-                    let file_path = if mdx_path.exists() {
-                        mdx_path
-                    } else if md_path.exists() {
-                        md_path
-                    } else {
-                        continue;
-                    };
-                    if let Some(e) = parse_content_entry(&slug, "post", &file_path) {
-                        entries.push(e);
-                    }
-                }
-            }
-        }
-    }
-
-    // Scan apps
-    if apps_dir.is_dir() {
-        if let Ok(readdir) = fs::read_dir(&apps_dir) {
-            for entry in readdir.flatten() {
-                if entry.path().is_dir() {
-                    let slug = entry.file_name().to_string_lossy().to_string();
-                    let md_path = entry.path().join("index.md");
-                    if md_path.exists() {
-                        if let Some(e) = parse_content_entry(&slug, "app", &md_path) {
-                            entries.push(e);
-                        }
-                    }
-                }
-            }
-        }
-    }
-
-    // Sort by created_date descending
-    entries.sort_by(|a, b| b.created_date.cmp(&a.created_date));
-    Ok(entries)
-}
-
-/// Create a new blog post directory with frontmatter scaffolding.
-pub fn create_post(repo_path: &str, args: CreatePostArgs) -> Result<ContentEntry, String> {
+    let dir = base.join(&collection.dir);
     let slug = if args.slug.is_empty() {
         to_slug(&args.title)
     } else {
         args.slug
     };
-    let post_dir = Path::new(repo_path)
-        .join("src/content/blog")
-        .join(&slug);
-
-    if post_dir.exists() {
-        return Err(format!("Post \"{}\" already exists.", slug));
+    validate_slug(&slug)?;
+
+    let exists = collections::find_entry(base, collection, &slug).is_some()
+        || (collections::is_directory_layout(collection) && dir.join(&slug).exists());
+    if exists {
+        return Err(AppError::new(
+            ErrorCode::AlreadyExists,
+            format!("Entry \"{}\" already exists in {}.", slug, collection.id),
+        )
+        .with_slug(&slug));
     }
 
-    fs::create_dir_all(&post_dir)
-        .map_err(|e| format!("Failed to create post directory '{}': {e}", slug))?;
-
-    let tag_list = if args.tags.is_empty() {
-        "[]".to_string()
-    } else {
-        let inner: Vec<String> = args
-            .tags
-            .iter()
-            .map(|t| format!("\"{}\"", escape_yaml_string(t)))
-            .collect();
-        format!("[{}]", inner.join(", "))
-    };
+    let mut tags = args.tags;
+    for tag in collection.default_tags.iter().rev() {
+        if !tags.contains(tag) {
+            tags.insert(0, tag.clone());
+        }
+    }
 
     let date = now_iso();
-    let content = format!(
-        "---\ntitle: \"{}\"\nsummary: \"{}\"\ncreatedDate: \"{}\"\nisDraft: true\ntags: {}\n---\n",
-        escape_yaml_string(&args.title),
-        escape_yaml_string(&args.summary),
-        date,
-        tag_list
-    );
-
-    let file_path = post_dir.join("index.mdx");
-    fs::write(&file_path, &content)
-        .map_err(|e| format!("Failed to write post file: {e}"))?;
-
-    Ok(ContentEntry {
-        slug,
-        content_type: "post".into(),
-        title: args.title,
-        summary: args.summary,
-        tags: args.tags,
-        is_draft: true,
-        is_pinned: false,
-        created_date: date,
-        publication_date: None,
-        author: Some("Filipe Lima".into()),
-        image: None,
-        file_path: file_path.to_string_lossy().to_string(),
-        modified_date: None,
-        published_hash: None,
-        has_changed: false,
-    })
-}
-
-/// Create a new app directory with frontmatter and an Astro component scaffold.
-pub fn create_app(repo_path: &str, args: CreateAppArgs) -> Result<ContentEntry, String> {
-    let slug = if args.slug.is_empty() {
-        to_slug(&args.title)
-    } else {
-        args.slug
-    };
-    let app_dir = Path::new(repo_path)
-        .join("src/content/apps")
-        .join(&slug);
-
-    if app_dir.exists() {
-        return Err(format!("App \"{}\" already exists.", slug));
+    let files = collections::render_scaffold(
+        base,
+        collection,
+        &ScaffoldVars {
+            slug: &slug,
+            title: &args.title,
+            summary: &args.summary,
+            date: &date,
+            tags: &tags,
+        },
+    )?;
+
+    for (rel, contents) in &files {
+        let path = dir.join(rel);
+        ensure_within(&path, base)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                AppError::io(format!("Failed to create directory for '{}': {e}", slug))
+                    .with_slug(&slug)
+            })?;
+        }
+        files::write_atomic(&path, contents.as_bytes())
+            .map_err(|e| AppError::io(e).with_slug(&slug).with_path(&path))?;
     }
 
-    fs::create_dir_all(&app_dir)
-        .map_err(|e| format!("Failed to create app directory '{}': {e}", slug))?;
+    let file_path = collections::find_entry(base, collection, &slug).ok_or_else(|| {
+        format!(
+            "Scaffold for {} did not create an entry file.",
+            collection.id
+        )
+    })?;
+    parse_content_entry(&slug, collection, &file_path)
+        .ok_or_else(|| AppError::from("Failed to parse new entry.").with_slug(&slug))
+}
 
-    let mut tags = args.tags.clone();
-    if !tags.contains(&"app".to_string()) {
-        tags.insert(0, "app".to_string());
+/// Create a new blog post with frontmatter scaffolding.
+pub fn create_post(repo_path: &str, args: CreateEntryArgs) -> Result<ContentEntry, AppError> {
+    let mut entry = create_entry(repo_path, "post", args)?;
+    if entry.author.is_none() {
+        entry.author = repo_config::load(repo_path)?.posts.author;
     }
-
-    let tag_list = if tags.is_empty() {
-        "[]".to_string()
-    } else {
-        let inner: Vec<String> = tags
-            .iter()
-            .map(|t| format!("\"{}\"", escape_yaml_string(t)))
-            .collect();
-        format!("[{}]", inner.join(", "))
-    };
-
-    let date = now_iso();
-    let index_content = format!(
-        "---\ntitle: \"{}\"\nsummary: \"{}\"\ncreatedDate: \"{}\"\nisDraft: true\ntags: {}\n---\n",
-        escape_yaml_string(&args.title),
-        escape_yaml_string(&args.summary),
-        date,
-        tag_list
-    );
-
-    let app_astro = format!(
-        r#"---
-/**
- * {} -- App Component
- */
----
-
-<div class="{}-root" id="{}-root">
-  <p class="{}-placeholder">App goes here.</p>
-</div>
-
-<style>
-  .{}-root {{
-    display: flex;
-    align-items: center;
-    justify-content: center;
-    width: 100%;
-    height: 100%;
-    color: var(--color-text);
-  }}
-
-  .{}-placeholder {{
-    font-family: var(--font-serif);
-    font-size: var(--font-size-h3);
-    color: var(--color-text-muted);
-  }}
-</style>
-
-<script>
-  import {{ onPageReady }} from "../../../utils/lifecycle";
-
-  onPageReady((signal) => {{
-    const root = document.getElementById("{}-root");
-    if (!root) return;
-
-    const themeObserver = new MutationObserver(() => {{}});
-    themeObserver.observe(document.documentElement, {{
-      attributes: true,
-      attributeFilter: ["data-theme"],
-    }});
-    signal.addEventListener("abort", () => themeObserver.disconnect());
-  }});
-</script>
-"#,
-        slug, slug, slug, slug, slug, slug, slug
-    );
-
-    let index_path = app_dir.join("index.md");
-    let astro_path = app_dir.join("App.astro");
-    fs::write(&index_path, &index_content)
-        .map_err(|e| format!("Failed to write app index file: {e}"))?;
-    fs::write(&astro_path, &app_astro)
-        .map_err(|e| format!("Failed to write App.astro: {e}"))?;
-
-    Ok(ContentEntry {
-        slug,
-        content_type: "app".into(),
-        title: args.title,
-        summary: args.summary,
-        tags,
-        is_draft: true,
-        is_pinned: false,
-        created_date: date,
-        publication_date: None,
-        author: None,
-        image: None,
-        file_path: index_path.to_string_lossy().to_string(),
-        modified_date: None,
-        published_hash: None,
-        has_changed: false,
-    })
+    Ok(entry)
 }
 
-/// Delete a content entry by slug, removing its directory and committing via git.
-pub fn delete_content(repo_path: &str, slug: &str) -> Result<(), String> {
-    if slug.contains("..") || slug.contains('/') || slug.contains('\\') {
-        return Err("Invalid slug".to_string());
-    }
+/// Create a new app directory with frontmatter and an Astro component scaffold.
+pub fn create_app(repo_path: &str, args: CreateEntryArgs) -> Result<ContentEntry, AppError> {
+    create_entry(repo_path, "app", args)
+}
 
+/// Delete a content entry by slug, removing its directory (or file) and committing via git.
+/// Returns the commit for `push_queue`. Cancelling `op` stops the delete only
+/// before anything is removed.
+pub fn delete_content(
+    repo_path: &str,
+    slug: &str,
+    op: &Operation,
+) -> Result<Option<String>, AppError> {
+    let repo = repo_config::load(repo_path)?;
     let base = Path::new(repo_path);
-
-    // Check blog dir
-    let blog_dir = base.join("src/content/blog").join(slug);
-    if blog_dir.is_dir() {
-        fs::remove_dir_all(&blog_dir)
-            .map_err(|e| format!("Failed to delete blog directory '{}': {e}", slug))?;
-        git_add_commit_push(
-            repo_path,
-            &format!("src/content/blog/{}", slug),
-            &format!("delete: {}", slug),
-        )?;
-        return Ok(());
+    let (file_path, collection) = find_content_file(base, &repo, slug)?;
+
+    let root = collections::entry_root(base, collection, slug, &file_path);
+    ensure_within(&root, base)?;
+    op.checkpoint()?;
+    op.step(OperationStep::Deleting);
+    if root.is_dir() {
+        fs::remove_dir_all(&root)
+    } else {
+        fs::remove_file(&root)
     }
+    .map_err(|e| {
+        AppError::io(format!(
+            "Failed to delete {} '{}': {e}",
+            collection.id, slug
+        ))
+        .with_slug(slug)
+        .with_path(&root)
+    })?;
+    watcher::note_own_tree(&root);
+
+    git_add_commit(
+        repo_path,
+        &collections::rel_entry_root(base, collection, slug, &file_path),
+        &commit_message(&repo.commit.delete, slug),
+        Some(&repo.scan),
+        op,
+    )
+    .map_err(|e| e.with_slug(slug))
+}
 
-    // Check apps dir
-    let app_dir = base.join("src/content/apps").join(slug);
-    if app_dir.is_dir() {
-        fs::remove_dir_all(&app_dir)
-            .map_err(|e| format!("Failed to delete app directory '{}': {e}", slug))?;
-        git_add_commit_push(
-            repo_path,
-            &format!("src/content/apps/{}", slug),
-            &format!("delete: {}", slug),
-        )?;
-        return Ok(());
-    }
+/// Resolve a slug to its content file path and collection, checking collections in order.
+/// The slug is validated and the resolved file must stay inside the repository.
+pub fn find_content_file<'a>(
+    base: &Path,
+    repo: &'a RepoConfig,
+    slug: &str,
+) -> Result<(PathBuf, &'a CollectionConfig), AppError> {
+    validate_slug(slug)?;
+    let (path, collection) = repo
+        .collections
+        .iter()
+        .find_map(|c| collections::find_entry(base, c, slug).map(|path| (path, c)))
+        .ok_or_else(|| {
+            AppError::not_found(format!("No content found for slug \"{}\".", slug)).with_slug(slug)
+        })?;
+    ensure_within(&path, base).map_err(|e| e.with_slug(slug))?;
+    Ok((path, collection))
+}
 
-    Err(format!("No content found for slug \"{}\".", slug))
+/// A rewritten entry file, waiting to be written and committed.
+pub struct Rewrite {
+    pub file: PathBuf,
+    /// Repo-relative path, as passed to git.
+    pub rel_path: String,
+    pub content: String,
 }
 
-/// Resolve a slug to its content file path and type ("post" or "app").
-pub fn find_content_file(base: &Path, slug: &str) -> Result<(PathBuf, String), String> {
-    // Check blog
-    let blog_dir = base.join("src/content/blog").join(slug);
-    if blog_dir.is_dir() {
-        let mdx = blog_dir.join("index.mdx");
-        if mdx.exists() {
-            return Ok((mdx, "post".into()));
-        }
-        let md = blog_dir.join("index.md");
-        if md.exists() {
-            return Ok((md, "post".into()));
-        }
-    }
+/// Whether `rel_path` is listed by `git::uncommitted_paths`, itself or inside
+/// an untracked directory (listed once, as `dir/`).
+pub fn is_uncommitted(uncommitted: &HashSet<String>, rel_path: &str) -> bool {
+    uncommitted
+        .iter()
+        .any(|p| p == rel_path || (p.ends_with('/') && rel_path.starts_with(p.as_str())))
+}
 
-    // Check apps
-    let app_dir = base.join("src/content/apps").join(slug);
-    if app_dir.is_dir() {
-        let md = app_dir.join("index.md");
-        if md.exists() {
-            return Ok((md, "app".into()));
+/// Write `rewrites`, commit them in one commit with `message` and push it
+/// through `push_queue`. Returns the commit, `None` if nothing changed.
+/// Cancelling `op` stops it before anything is written.
+///
+/// Rewrites are frontmatter edits and panel-generated files, so there is
+/// nothing to scan. Callers run this through the repository's operation queue.
+pub fn commit_rewrites(
+    app: &tauri::AppHandle,
+    repo_path: &str,
+    rewrites: &[Rewrite],
+    message: &str,
+    kind: MutationKind,
+    op: &Operation,
+) -> Result<Option<String>, AppError> {
+    if rewrites.is_empty() {
+        return Ok(None);
+    }
+    op.checkpoint()?;
+    op.step(OperationStep::Writing);
+    for r in rewrites {
+        if let Some(dir) = r.file.parent() {
+            fs::create_dir_all(dir).map_err(|e| {
+                AppError::io(format!("Failed to create directory: {e}")).with_path(dir)
+            })?;
         }
+        files::write_text(&r.file, &r.content).map_err(|e| AppError::io(e).with_path(&r.file))?;
     }
 
-    Err(format!("No content found for slug \"{}\".", slug))
+    let rel_paths: Vec<&str> = rewrites.iter().map(|r| r.rel_path.as_str()).collect();
+    let commit = git::git_add_commit_all(repo_path, &rel_paths, message, None, op)?;
+    push_queue::enqueue_and_push(app, repo_path, kind, "", commit.clone(), op)?;
+    Ok(commit)
+}
+
+/// `"<what> (N files)"`, for commit subjects of multi-file changes.
+pub fn files_subject(what: &str, count: usize) -> String {
+    format!("{what} ({count} file{})", if count == 1 { "" } else { "s" })
 }
@@ -120,14 +120,16 @@ pub fn start_dev_server(state: &DevServerState, repo_path: &str) -> Result<(), S
     }
     *guard = None;
 
+    let port = crate::repo_config::load(repo_path)?.dev_server.port;
+
     // Kill any stale orphan from a previous panel session
-    kill_stale_port_holder(4322);
+    kill_stale_port_holder(port);
 
     let bun_path = find_bun();
     let path_env = build_child_path();
 
     let mut cmd = Command::new(&bun_path);
-    cmd.args(["run", "dev", "--", "--port", "4322"])
+    cmd.args(["run", "dev", "--", "--port", &port.to_string()])
         .current_dir(repo_path)
         .env("PATH", &path_env)
         .env("INCLUDE_DRAFTS", "true");
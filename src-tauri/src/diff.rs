@@ -0,0 +1,172 @@
+//! Line diffs — unified-format previews of rewrites the panel is about to make.
+//!
+//! Inputs are whole files, but the rewrites previewed here (frontmatter edits,
+//! migrations) touch a few lines, so the common prefix and suffix are trimmed
+//! before the quadratic LCS runs on what is left.
+
+/// Lines of unchanged context around each change.
+const CONTEXT: usize = 3;
+
+enum Line<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Unified diff of `old` to `new` for `path`, or an empty string if they
+/// match. Line endings and a leading BOM are ignored.
+pub fn unified(path: &str, old: &str, new: &str) -> String {
+    let old = normalize(old);
+    let new = normalize(new);
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+    let script = edit_script(&a, &b);
+
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for (i, line) in script.iter().enumerate() {
+        if matches!(line, Line::Same(_)) {
+            continue;
+        }
+        let lo = i.saturating_sub(CONTEXT);
+        let hi = (i + CONTEXT + 1).min(script.len());
+        match hunks.last_mut() {
+            Some(last) if lo <= last.1 => last.1 = hi,
+            _ => hunks.push((lo, hi)),
+        }
+    }
+    if hunks.is_empty() {
+        return String::new();
+    }
+
+    // Line numbers (0-based) in each file at every position of the script.
+    let mut old_no = Vec::with_capacity(script.len());
+    let mut new_no = Vec::with_capacity(script.len());
+    let (mut o, mut n) = (0, 0);
+    for line in &script {
+        old_no.push(o);
+        new_no.push(n);
+        match line {
+            Line::Same(_) => (o, n) = (o + 1, n + 1),
+            Line::Removed(_) => o += 1,
+            Line::Added(_) => n += 1,
+        }
+    }
+
+    let mut out = format!("--- a/{path}\n+++ b/{path}\n");
+    for (lo, hi) in hunks {
+        let lines = &script[lo..hi];
+        let old_len = lines
+            .iter()
+            .filter(|l| !matches!(l, Line::Added(_)))
+            .count();
+        let new_len = lines
+            .iter()
+            .filter(|l| !matches!(l, Line::Removed(_)))
+            .count();
+        out.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            range(old_no[lo], old_len),
+            range(new_no[lo], new_len)
+        ));
+        for line in lines {
+            let (sign, text) = match line {
+                Line::Same(t) => (' ', t),
+                Line::Removed(t) => ('-', t),
+                Line::Added(t) => ('+', t),
+            };
+            out.push(sign);
+            out.push_str(text);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+fn normalize(text: &str) -> String {
+    crate::files::strip_bom(text).replace("\r\n", "\n")
+}
+
+/// A hunk range: 1-based start and length, or the preceding line for an empty range.
+fn range(start: usize, len: usize) -> String {
+    if len == 0 {
+        format!("{start},0")
+    } else {
+        format!("{},{len}", start + 1)
+    }
+}
+
+fn edit_script<'a>(a: &[&'a str], b: &[&'a str]) -> Vec<Line<'a>> {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (ma, mb) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+
+    // lcs[i][j]: longest common subsequence of ma[i..] and mb[j..].
+    let mut lcs = vec![vec![0u32; mb.len() + 1]; ma.len() + 1];
+    for i in (0..ma.len()).rev() {
+        for j in (0..mb.len()).rev() {
+            lcs[i][j] = if ma[i] == mb[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut script: Vec<Line> = a[..prefix].iter().map(|l| Line::Same(l)).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < ma.len() || j < mb.len() {
+        if i < ma.len() && j < mb.len() && ma[i] == mb[j] {
+            script.push(Line::Same(ma[i]));
+            (i, j) = (i + 1, j + 1);
+        } else if i < ma.len() && (j == mb.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            script.push(Line::Removed(ma[i]));
+            i += 1;
+        } else {
+            script.push(Line::Added(mb[j]));
+            j += 1;
+        }
+    }
+    script.extend(a[a.len() - suffix..].iter().map(|l| Line::Same(l)));
+    script
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD: &str = "a\nb\nc\nd\ne\nf\ng\nh\n";
+
+    #[test]
+    fn hunk_at_start_of_file() {
+        assert_eq!(
+            unified("x.md", OLD, &OLD.replacen('a', "A", 1)),
+            "--- a/x.md\n+++ b/x.md\n@@ -1,4 +1,4 @@\n-a\n+A\n b\n c\n d\n"
+        );
+    }
+
+    #[test]
+    fn hunk_at_end_of_file() {
+        assert_eq!(
+            unified("x.md", OLD, &OLD.replacen('h', "H", 1)),
+            "--- a/x.md\n+++ b/x.md\n@@ -5,4 +5,4 @@\n e\n f\n g\n-h\n+H\n"
+        );
+        assert_eq!(
+            unified("x.md", OLD, &format!("{OLD}i\n")),
+            "--- a/x.md\n+++ b/x.md\n@@ -6,3 +6,4 @@\n f\n g\n h\n+i\n"
+        );
+    }
+
+    #[test]
+    fn new_file_and_unchanged_file() {
+        assert_eq!(
+            unified("x.md", "", "a\n"),
+            "--- a/x.md\n+++ b/x.md\n@@ -0,0 +1,1 @@\n+a\n"
+        );
+        assert_eq!(unified("x.md", OLD, &OLD.replace('\n', "\r\n")), "");
+    }
+}
@@ -0,0 +1,132 @@
+//! Structured errors returned across the IPC boundary.
+//!
+//! Commands fail with an `AppError` that the frontend receives as
+//! `{ code, message, retryable, context }`, so it can branch on `code` instead
+//! of message text. Helper modules that still return `Result<_, String>` convert
+//! through `From<String>` as `ErrorCode::Internal`, keeping their message.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fmt;
+
+/// Machine-readable failure category.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// Malformed or unsafe input (slug, path, URL).
+    InvalidInput,
+    /// A setting the operation needs (repo, token, account) is missing.
+    NotConfigured,
+    NotFound,
+    AlreadyExists,
+    /// A filesystem operation failed.
+    Io,
+    /// A git command failed.
+    Git,
+    /// The remote rejected the push, even after a rebase.
+    GitPushRejected,
+    /// The pre-commit scan found secrets or oversized files.
+    ScanBlocked,
+    /// Cloudflare rejected the API token or its permissions.
+    CloudflareAuth,
+    CloudflareRateLimited,
+    /// Any other Cloudflare API error.
+    Cloudflare,
+    /// The request could not reach the server.
+    Network,
+    /// The operation was cancelled via `cancel_operation`.
+    Cancelled,
+    /// The secret store is passphrase-protected and hasn't been unlocked.
+    SecretsLocked,
+    /// The app config file is unreadable; the context path is its backup.
+    ConfigCorrupt,
+    Internal,
+}
+
+/// What the failing operation was acting on.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct ErrorContext {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slug: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// External command that failed, e.g. `git push`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AppError {
+    pub code: ErrorCode,
+    pub message: String,
+    /// Whether retrying the same operation may succeed.
+    pub retryable: bool,
+    pub context: ErrorContext,
+}
+
+impl AppError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            retryable: false,
+            context: ErrorContext::default(),
+        }
+    }
+
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::InvalidInput, message)
+    }
+
+    pub fn not_configured(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::NotConfigured, message)
+    }
+
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::NotFound, message)
+    }
+
+    pub fn io(message: impl Into<String>) -> Self {
+        Self::new(ErrorCode::Io, message)
+    }
+
+    pub fn retryable(mut self) -> Self {
+        self.retryable = true;
+        self
+    }
+
+    pub fn with_slug(mut self, slug: impl Into<String>) -> Self {
+        self.context.slug = Some(slug.into());
+        self
+    }
+
+    pub fn with_path(mut self, path: impl AsRef<std::path::Path>) -> Self {
+        self.context.path = Some(path.as_ref().to_string_lossy().to_string());
+        self
+    }
+
+    pub fn with_command(mut self, command: impl Into<String>) -> Self {
+        self.context.command = Some(command.into());
+        self
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        Self::new(ErrorCode::Internal, message)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        Self::new(ErrorCode::Internal, message)
+    }
+}
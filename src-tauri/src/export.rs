@@ -0,0 +1,313 @@
+//! Analytics export — CSV, JSON and a self-contained HTML report.
+//!
+//! Everything is rendered from an already-fetched `CfAnalytics`; no network access.
+
+use std::path::Path;
+
+use crate::files;
+use crate::types::{AnomalyKind, CfAnalytics, CfDelta, ExportFormat};
+
+/// Write `analytics` into `dir` in the requested format. Returns the written file paths.
+pub fn export_analytics(
+    analytics: &CfAnalytics,
+    format: ExportFormat,
+    dir: &Path,
+) -> Result<Vec<String>, String> {
+    if !dir.is_dir() {
+        return Err(format!(
+            "Export directory does not exist: {}",
+            dir.display()
+        ));
+    }
+
+    let stem = format!("analytics-{}", file_stamp(&analytics.period));
+    let files: Vec<(String, String)> = match format {
+        ExportFormat::Csv => vec![
+            (format!("{stem}-daily.csv"), daily_csv(analytics)),
+            (format!("{stem}-paths.csv"), paths_csv(analytics)),
+            (format!("{stem}-countries.csv"), countries_csv(analytics)),
+        ],
+        ExportFormat::Json => vec![(
+            format!("{stem}.json"),
+            serde_json::to_string_pretty(analytics)
+                .map_err(|e| format!("Failed to serialize analytics: {e}"))?,
+        )],
+        ExportFormat::Html => vec![(format!("{stem}.html"), html_report(analytics))],
+    };
+
+    let mut written = Vec::new();
+    for (name, contents) in files {
+        let path = dir.join(&name);
+        files::write_atomic(&path, contents.as_bytes())?;
+        written.push(path.to_string_lossy().to_string());
+    }
+    Ok(written)
+}
+
+/// Turn a period label ("30d", "2024-01-01..2024-01-31") into a filename-safe stamp.
+fn file_stamp(period: &str) -> String {
+    period
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect::<String>()
+        .split('-')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+// ---------------------------------------------------------------------------
+// CSV
+// ---------------------------------------------------------------------------
+
+/// Quote a CSV field if it contains a separator, quote or newline.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn daily_csv(a: &CfAnalytics) -> String {
+    let mut out = String::from("date,count,uniques,bytes,cached_bytes,cached_requests,threats\n");
+    for d in &a.daily_requests {
+        out.push_str(&format!(
+            "{},{},{},{},{},{},{}\n",
+            csv_field(&d.date),
+            d.count,
+            d.uniques,
+            d.bytes,
+            d.cached_bytes,
+            d.cached_requests,
+            d.threats
+        ));
+    }
+    out
+}
+
+fn paths_csv(a: &CfAnalytics) -> String {
+    let mut out = String::from("path,count\n");
+    for p in &a.top_paths {
+        out.push_str(&format!("{},{}\n", csv_field(&p.path), p.count));
+    }
+    out
+}
+
+fn countries_csv(a: &CfAnalytics) -> String {
+    let mut out = String::from("country,count\n");
+    for c in &a.top_countries {
+        out.push_str(&format!("{},{}\n", csv_field(&c.country), c.count));
+    }
+    out
+}
+
+// ---------------------------------------------------------------------------
+// HTML report
+// ---------------------------------------------------------------------------
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Vertical bar chart of the daily series; anomalous slots are highlighted.
+fn daily_chart(a: &CfAnalytics) -> String {
+    const WIDTH: f64 = 720.0;
+    const HEIGHT: f64 = 200.0;
+    let n = a.daily_requests.len().max(1) as f64;
+    let max = a
+        .daily_requests
+        .iter()
+        .map(|d| d.count)
+        .max()
+        .unwrap_or(0)
+        .max(1) as f64;
+    let slot = WIDTH / n;
+    let bar = (slot * 0.8).max(1.0);
+
+    let mut bars = String::new();
+    for (i, d) in a.daily_requests.iter().enumerate() {
+        let h = d.count as f64 / max * (HEIGHT - 20.0);
+        let fill = match a.anomalies.iter().find(|x| x.date == d.date) {
+            Some(x) if x.kind == AnomalyKind::Spike => "#d97706",
+            Some(_) => "#dc2626",
+            None => "#2563eb",
+        };
+        bars.push_str(&format!(
+            r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="{fill}"><title>{}: {}</title></rect>"#,
+            i as f64 * slot + (slot - bar) / 2.0,
+            HEIGHT - h,
+            bar,
+            h,
+            escape_html(&d.date),
+            d.count
+        ));
+    }
+
+    let first = a
+        .daily_requests
+        .first()
+        .map(|d| d.date.as_str())
+        .unwrap_or("");
+    let last = a
+        .daily_requests
+        .last()
+        .map(|d| d.date.as_str())
+        .unwrap_or("");
+    format!(
+        r#"<svg viewBox="0 0 {WIDTH} {}" width="100%" role="img" aria-label="Daily series">{bars}<text x="0" y="{}" class="axis">{}</text><text x="{WIDTH}" y="{}" class="axis" text-anchor="end">{}</text><text x="0" y="12" class="axis">max {}</text></svg>"#,
+        HEIGHT + 16.0,
+        HEIGHT + 14.0,
+        escape_html(first),
+        HEIGHT + 14.0,
+        escape_html(last),
+        max as u64
+    )
+}
+
+/// Horizontal bar chart for a ranked list of labelled counts.
+fn ranked_chart(rows: &[(&str, u64)]) -> String {
+    const WIDTH: f64 = 720.0;
+    const LABEL: f64 = 260.0;
+    const ROW: f64 = 22.0;
+    let max = rows.iter().map(|(_, c)| *c).max().unwrap_or(0).max(1) as f64;
+
+    let mut body = String::new();
+    for (i, (label, count)) in rows.iter().enumerate() {
+        let y = i as f64 * ROW;
+        let w = *count as f64 / max * (WIDTH - LABEL - 60.0);
+        body.push_str(&format!(
+            r##"<text x="0" y="{:.1}" class="label">{}</text><rect x="{LABEL}" y="{:.1}" width="{:.1}" height="14" fill="#2563eb"/><text x="{:.1}" y="{:.1}" class="value">{count}</text>"##,
+            y + 14.0,
+            escape_html(label),
+            y + 3.0,
+            w,
+            LABEL + w + 6.0,
+            y + 14.0
+        ));
+    }
+    format!(
+        r#"<svg viewBox="0 0 {WIDTH} {}" width="100%" role="img">{body}</svg>"#,
+        (rows.len() as f64 * ROW).max(ROW)
+    )
+}
+
+fn delta_rows(deltas: &[CfDelta]) -> String {
+    deltas
+        .iter()
+        .map(|d| {
+            let pct = d
+                .delta_pct
+                .map(|p| format!("{p:+.1}%"))
+                .unwrap_or_else(|| "new".to_string());
+            format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{:+}</td><td>{pct}</td></tr>",
+                escape_html(&d.key),
+                d.current,
+                d.previous,
+                d.delta
+            )
+        })
+        .collect()
+}
+
+fn html_report(a: &CfAnalytics) -> String {
+    let paths: Vec<(&str, u64)> = a
+        .top_paths
+        .iter()
+        .map(|p| (p.path.as_str(), p.count))
+        .collect();
+    let countries: Vec<(&str, u64)> = a
+        .top_countries
+        .iter()
+        .map(|c| (c.country.as_str(), c.count))
+        .collect();
+
+    let mut sections = vec![format!(
+        "<section><h2>Daily</h2>{}</section>",
+        daily_chart(a)
+    )];
+    if !paths.is_empty() {
+        sections.push(format!(
+            "<section><h2>Top paths</h2>{}</section>",
+            ranked_chart(&paths)
+        ));
+    }
+    if !countries.is_empty() {
+        sections.push(format!(
+            "<section><h2>Top countries</h2>{}</section>",
+            ranked_chart(&countries)
+        ));
+    }
+    if let Some(cmp) = &a.comparison {
+        let header = "<tr><th></th><th>Current</th><th>Previous</th><th>Δ</th><th>Δ %</th></tr>";
+        let group = |title: &str, deltas: &[CfDelta]| {
+            if deltas.is_empty() {
+                String::new()
+            } else {
+                format!(
+                    "<tr><th colspan=\"5\">{title}</th></tr>{}",
+                    delta_rows(deltas)
+                )
+            }
+        };
+        sections.push(format!(
+            "<section><h2>Compared to {} – {}</h2><table>{header}{}{}{}</table></section>",
+            escape_html(&cmp.previous_period_start),
+            escape_html(&cmp.previous_period_end),
+            delta_rows(&cmp.totals),
+            group("Paths", &cmp.paths),
+            group("Countries", &cmp.countries)
+        ));
+    }
+    if !a.anomalies.is_empty() {
+        let rows: String = a
+            .anomalies
+            .iter()
+            .map(|x| {
+                format!(
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{:.0}</td></tr>",
+                    escape_html(&x.date),
+                    x.kind.as_str(),
+                    x.count,
+                    x.baseline
+                )
+            })
+            .collect();
+        sections.push(format!(
+            "<section><h2>Anomalies</h2><table><tr><th>Date</th><th>Kind</th><th>Count</th><th>Baseline</th></tr>{rows}</table></section>"
+        ));
+    }
+
+    format!(
+        r#"<!doctype html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Analytics report — {period}</title>
+<style>
+  body {{ font-family: system-ui, sans-serif; max-width: 760px; margin: 2rem auto; color: #111; }}
+  h1 {{ font-size: 1.5rem; margin-bottom: 0.25rem; }}
+  h2 {{ font-size: 1.1rem; margin-top: 2rem; }}
+  .meta {{ color: #666; margin-top: 0; }}
+  .axis, .label, .value {{ font-size: 11px; fill: #444; }}
+  table {{ border-collapse: collapse; width: 100%; font-size: 0.9rem; }}
+  th, td {{ text-align: left; padding: 0.25rem 0.5rem; border-bottom: 1px solid #eee; }}
+</style>
+</head>
+<body>
+<h1>Analytics report — {period}</h1>
+<p class="meta">Total: {total} · Generated {generated}</p>
+{sections}
+</body>
+</html>
+"#,
+        period = escape_html(&a.period),
+        total = a.total_requests,
+        generated = crate::frontmatter::now_iso(),
+        sections = sections.join("\n")
+    )
+}
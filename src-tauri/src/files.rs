@@ -0,0 +1,134 @@
+//! File writes — atomic replacement that keeps a file's line endings and BOM.
+//!
+//! Content is edited internally with `\n` line endings and no BOM; `write_text`
+//! restores whatever the file on disk used, so edits made by the panel don't
+//! rewrite every line of a CRLF file in git diffs.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// UTF-8 byte order mark.
+const BOM: char = '\u{feff}';
+
+/// Encoding details of a text file that a rewrite must keep.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TextFormat {
+    pub bom: bool,
+    pub crlf: bool,
+}
+
+impl TextFormat {
+    /// Detect the format from existing content. Line endings follow the first line.
+    pub fn detect(content: &str) -> Self {
+        Self {
+            bom: content.starts_with(BOM),
+            crlf: content
+                .find('\n')
+                .is_some_and(|i| content[..i].ends_with('\r')),
+        }
+    }
+
+    /// Render `content` in this format, whatever line endings or BOM it arrives with.
+    pub fn apply(self, content: &str) -> String {
+        let content = strip_bom(content).replace("\r\n", "\n");
+        let content = if self.crlf {
+            content.replace('\n', "\r\n")
+        } else {
+            content
+        };
+        if self.bom {
+            format!("{BOM}{content}")
+        } else {
+            content
+        }
+    }
+}
+
+/// Drop a leading UTF-8 BOM, if any.
+pub fn strip_bom(content: &str) -> &str {
+    content.strip_prefix(BOM).unwrap_or(content)
+}
+
+/// Write text to `path` atomically, keeping the existing file's line endings and BOM.
+/// New (or unreadable, non-UTF-8) files are written as given.
+pub fn write_text(path: &Path, content: &str) -> Result<(), String> {
+    match fs::read_to_string(path) {
+        Ok(existing) => {
+            let format = TextFormat::detect(&existing);
+            write_atomic(path, format.apply(content).as_bytes())
+        }
+        Err(_) => write_atomic(path, content.as_bytes()),
+    }
+}
+
+/// Replace `path` with `data` via a sibling temp file, flushed and renamed over,
+/// so a crash leaves either the old file or the new one — never a truncated mix.
+/// An existing file's permissions carry over, and the watcher doesn't report
+/// the write as an external change.
+pub fn write_atomic(path: &Path, data: &[u8]) -> Result<(), String> {
+    let name = path
+        .file_name()
+        .ok_or_else(|| format!("Invalid path: {}", path.display()))?;
+    // Hidden, so collection listings and directory hashes skip it.
+    let tmp = path.with_file_name(format!(".{}.tmp", name.to_string_lossy()));
+
+    let result = (|| {
+        let mut file = fs::File::create(&tmp)?;
+        file.write_all(data)?;
+        if let Ok(meta) = fs::metadata(path) {
+            file.set_permissions(meta.permissions())?;
+        }
+        file.sync_all()?;
+        fs::rename(&tmp, path)
+    })();
+    match result {
+        Ok(()) => {
+            crate::watcher::note_own_write(path);
+            Ok(())
+        }
+        Err(e) => {
+            let _ = fs::remove_file(&tmp);
+            Err(format!("Failed to write {}: {e}", path.display()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The file as the panel edits it: no BOM, `\n` line endings.
+    fn internal(content: &str) -> String {
+        strip_bom(content).replace("\r\n", "\n")
+    }
+
+    #[test]
+    fn bom_and_crlf_round_trip() {
+        let original = "\u{feff}---\r\ntitle: A\r\n---\r\nBody\r\n";
+        let format = TextFormat::detect(original);
+        assert!(format.bom && format.crlf);
+        assert_eq!(format.apply(&internal(original)), original);
+    }
+
+    #[test]
+    fn plain_lf_round_trips() {
+        let original = "---\ntitle: A\n---\nBody\n";
+        let format = TextFormat::detect(original);
+        assert!(!format.bom && !format.crlf);
+        assert_eq!(format.apply(&internal(original)), original);
+        assert_eq!(format.apply("---\r\ntitle: A\r\n"), "---\ntitle: A\n");
+    }
+
+    #[test]
+    fn mixed_line_endings_follow_the_first_line() {
+        assert_eq!(
+            TextFormat::detect("a\r\nb\nc\r\n").apply(&internal("a\r\nb\nc\r\n")),
+            "a\r\nb\r\nc\r\n"
+        );
+        assert_eq!(
+            TextFormat::detect("a\nb\r\nc\n").apply(&internal("a\nb\r\nc\n")),
+            "a\nb\nc\n"
+        );
+    }
+}
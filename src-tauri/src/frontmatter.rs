@@ -7,7 +7,10 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
 
-use crate::types::ContentEntry;
+use crate::collections;
+use crate::files;
+use crate::security::escape_yaml_string;
+use crate::types::{CollectionConfig, ContentEntry};
 
 /// Matches non-alphanumeric runs for slug generation.
 pub static SLUG_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[^a-z0-9]+").unwrap());
@@ -38,8 +41,8 @@ pub fn calculate_content_hash(content: &str) -> String {
 }
 
 /// Calculate SHA-256 hash of all non-metadata files in a content directory.
-/// Excludes `index.md` (frontmatter managed by the panel) and hidden files.
-pub fn calculate_directory_hash(dir: &Path) -> String {
+/// Excludes the entry file (frontmatter managed by the panel) and hidden files.
+pub fn calculate_directory_hash(dir: &Path, entry_file: &Path) -> String {
     fn collect_files(dir: &Path, paths: &mut Vec<PathBuf>) {
         if let Ok(entries) = fs::read_dir(dir) {
             for entry in entries.flatten() {
@@ -59,7 +62,7 @@ pub fn calculate_directory_hash(dir: &Path) -> String {
 
     let mut paths: Vec<PathBuf> = Vec::new();
     collect_files(dir, &mut paths);
-    paths.retain(|p| *p != dir.join("index.md"));
+    paths.retain(|p| p != entry_file);
     paths.sort();
 
     let mut hasher = Sha256::new();
@@ -74,7 +77,9 @@ pub fn calculate_directory_hash(dir: &Path) -> String {
 }
 
 /// Parse frontmatter from file content. Returns (yaml_block, rest_of_file).
+/// A leading BOM is skipped; `files::write_text` puts it back.
 pub fn split_frontmatter(content: &str) -> Option<(String, String)> {
+    let content = files::strip_bom(content);
     if let Some(caps) = FRONTMATTER_RE.captures(content) {
         let full_match = caps.get(0).unwrap();
         let yaml = caps.get(1).unwrap().as_str().to_string();
@@ -130,6 +135,16 @@ pub fn get_yaml_tags(yaml: &str) -> Vec<String> {
     vec![]
 }
 
+/// Render tags as an inline YAML list of double-quoted strings, the form
+/// `get_yaml_tags` reads.
+pub fn format_yaml_tags(tags: &[String]) -> String {
+    let inner: Vec<String> = tags
+        .iter()
+        .map(|t| format!("\"{}\"", escape_yaml_string(t)))
+        .collect();
+    format!("[{}]", inner.join(", "))
+}
+
 /// Set a frontmatter field. Updates existing or appends.
 pub fn set_frontmatter_field(yaml: &str, key: &str, value: &str) -> String {
     let pattern = format!(r"(?m)^{}:.*$", regex::escape(key));
@@ -153,15 +168,17 @@ pub fn insert_field_after(yaml: &str, after_key: &str, key: &str, value: &str) -
     }
 }
 
-/// Reassemble a file from frontmatter YAML and body.
+/// Reassemble a file from frontmatter YAML and body. Always uses `\n` fences;
+/// write through `files::write_text` to keep the file's own line endings.
 pub fn assemble_file(yaml: &str, rest: &str) -> String {
     format!("---\n{}\n---{}", yaml, rest)
 }
 
-/// Parse a content file into a ContentEntry.
+/// Parse a content file into a ContentEntry. Never writes: fixes to stored
+/// content are migrations (see `migrations`).
 pub fn parse_content_entry(
     slug: &str,
-    content_type: &str,
+    collection: &CollectionConfig,
     file_path: &Path,
 ) -> Option<ContentEntry> {
     let content = fs::read_to_string(file_path).ok()?;
@@ -181,26 +198,12 @@ pub fn parse_content_entry(
     let publication_date = get_yaml_field(&yaml, "publicationDate");
     let author = get_yaml_field(&yaml, "author");
     let image = get_yaml_field(&yaml, "image");
-    let mut published_hash = get_yaml_field(&yaml, "publishedHash");
+    let published_hash = get_yaml_field(&yaml, "publishedHash");
 
-    // Compute current content hash: directory hash for apps, body hash for posts.
-    let is_app = content_type == "app";
-    let current_hash = if is_app {
-        file_path.parent().map(calculate_directory_hash)
-    } else {
-        Some(calculate_content_hash(&rest))
-    };
-
-    // One-time migration: seed publishedHash for published content that predates change tracking.
-    if !is_draft && published_hash.is_none() {
-        if let Some(ref hash) = current_hash {
-            let new_yaml =
-                set_frontmatter_field(&yaml, "publishedHash", &format!("\"{}\"", hash));
-            let new_content = assemble_file(&new_yaml, &rest);
-            let _ = fs::write(file_path, new_content); // best-effort
-            published_hash = Some(hash.clone());
-        }
-    }
+    // Compute current content hash per the collection's hash mode.
+    // Published entries without a publishedHash predate change tracking and
+    // report no changes until the `seed-published-hash` migration runs.
+    let current_hash = collections::content_hash(collection, file_path, &rest);
 
     let has_changed = if !is_draft {
         match (&published_hash, &current_hash) {
@@ -213,7 +216,7 @@ pub fn parse_content_entry(
 
     Some(ContentEntry {
         slug: slug.to_string(),
-        content_type: content_type.to_string(),
+        content_type: collection.id.clone(),
         title: get_yaml_field(&yaml, "title").unwrap_or_else(|| "(untitled)".into()),
         summary: get_yaml_field(&yaml, "summary").unwrap_or_default(),
         tags: get_yaml_tags(&yaml),
@@ -227,5 +230,6 @@ pub fn parse_content_entry(
         modified_date,
         published_hash,
         has_changed,
+        pending_push: false,
     })
 }
@@ -1,123 +1,314 @@
 //! Git operations — thin wrappers around the `git` CLI for add/commit/push and status.
 
+use std::collections::HashSet;
 use std::fs;
+use std::io::Write;
 use std::path::Path;
-use std::process::Command;
-
-/// Stage a file, commit with the given message, and push to the remote.
-pub fn git_add_commit_push(repo_path: &str, rel_path: &str, message: &str) -> Result<(), String> {
-    let run = |args: &[&str]| -> Result<(), String> {
-        let output = Command::new("git")
-            .args(args)
-            .current_dir(repo_path)
-            .output()
-            .map_err(|e| format!("Failed to run git {}: {}", args[0], e))?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            return Err(format!("git {} failed: {}", args[0], stderr.trim()));
-        }
-        Ok(())
-    };
+use std::process::{Command, Stdio};
+
+use crate::error::{AppError, ErrorCode};
+use crate::operations::Operation;
+use crate::scan;
+use crate::types::{OperationStep, ScanSettings};
+use crate::watcher;
 
-    // Clean up stale index lock from a previously interrupted git operation.
+/// Stage a file and commit it with the given message, returning the new
+/// commit's hash, or `None` when there was nothing to commit. The commit holds
+/// that path only, whatever else is staged. Pushing is left
+/// to `push_queue`, so a commit made offline is never lost track of.
+/// With `scan` set, pending files are inspected first and any finding aborts
+/// before anything is staged; callers that already ran `scan::check` pass `None`.
+///
+/// Callers run this through the repository's operation queue (see `queue`).
+pub fn git_add_commit(
+    repo_path: &str,
+    rel_path: &str,
+    message: &str,
+    scan: Option<&ScanSettings>,
+    op: &Operation,
+) -> Result<Option<String>, AppError> {
+    git_add_commit_all(repo_path, &[rel_path], message, scan, op)
+}
+
+/// `git_add_commit` for several paths at once, committed together.
+pub fn git_add_commit_all(
+    repo_path: &str,
+    rel_paths: &[&str],
+    message: &str,
+    scan: Option<&ScanSettings>,
+    op: &Operation,
+) -> Result<Option<String>, AppError> {
+    // The operation queue keeps the panel's own git commands from overlapping, so
+    // an index lock here belongs to another git process, or one that crashed.
+    // Removing it could corrupt that process's index; report it instead.
     let lock_path = Path::new(repo_path).join(".git/index.lock");
     if lock_path.exists() {
-        let _ = fs::remove_file(&lock_path);
+        return Err(AppError::new(
+            ErrorCode::Git,
+            "Another git process is using this repository. Try again once it finishes, \
+             or remove .git/index.lock if it was left behind by a crash.",
+        )
+        .with_path(&lock_path)
+        .retryable());
     }
 
+    if let Some(settings) = scan {
+        op.step(OperationStep::Scanning);
+        for rel_path in rel_paths {
+            scan::check(repo_path, rel_path, settings)?;
+        }
+    }
+
+    op.step(OperationStep::Committing);
+
     // Stage changes — --all ensures deletions are staged, not just additions.
     // "did not match any files" is non-fatal: the path was never tracked by git.
     let add = Command::new("git")
-        .args(["add", "--all", "--", rel_path])
+        .args(["add", "--all", "--"])
+        .args(rel_paths)
         .current_dir(repo_path)
         .output()
-        .map_err(|e| format!("Failed to run git add: {}", e))?;
+        .map_err(|e| git_error("add", format!("Failed to run git add: {}", e)))?;
 
     if !add.status.success() {
         let stderr = String::from_utf8_lossy(&add.stderr);
         if stderr.contains("did not match any files") {
-            return Ok(());
+            return Ok(None);
         }
-        return Err(format!("git add failed: {}", stderr.trim()));
+        return Err(
+            git_error("add", format!("git add failed: {}", stderr.trim()))
+                .with_path(rel_paths.join(", ")),
+        );
     }
 
     // Check whether anything was actually staged before committing.
     let diff = Command::new("git")
-        .args(["diff", "--cached", "--quiet"])
+        .args(["diff", "--cached", "--quiet", "--"])
+        .args(rel_paths)
         .current_dir(repo_path)
         .output()
-        .map_err(|e| format!("Failed to check staged changes: {}", e))?;
+        .map_err(|e| git_error("diff", format!("Failed to check staged changes: {}", e)))?;
 
     if diff.status.success() {
         // Exit code 0 → no staged changes, nothing to commit.
-        return Ok(());
+        return Ok(None);
     }
 
-    run(&["commit", "-m", message])?;
+    // Only `rel_paths`, which were scanned: anything else staged (from a
+    // terminal, say) stays staged and out of the commit.
+    let mut commit = vec!["commit", "-m", message, "--"];
+    commit.extend_from_slice(rel_paths);
+    run(repo_path, &commit)?;
+    let head = run(repo_path, &["rev-parse", "HEAD"])?;
+    watcher::note_own_head(repo_path);
+    Ok(Some(head.trim().to_string()))
+}
 
-    // Push, retrying once after a pull --rebase if the first attempt fails.
-    if let Err(first_err) = run(&["push"]) {
-        let _ = run(&["pull", "--rebase"]);
-        run(&["push"]).map_err(|_| {
-            format!("Failed to push after retry: {first_err}")
-        })?;
+/// Push the current branch, retrying once after a pull --rebase if the first
+/// attempt fails. Fails with `ErrorCode::Network` when the remote can't be
+/// reached, and `ErrorCode::GitPushRejected` when it refuses the push.
+///
+/// Progress is reported on `op`. Cancellation is honored before each attempt;
+/// commits stay in place either way.
+pub fn git_push(repo_path: &str, op: &Operation) -> Result<(), AppError> {
+    op.checkpoint_before_push()?;
+    op.step(OperationStep::Pushing);
+    let Err(first_err) = run(repo_path, &["push"]) else {
+        return Ok(());
+    };
+    if is_unreachable(&first_err.message) {
+        return Err(AppError::new(
+            ErrorCode::Network,
+            format!("Could not reach the git remote: {first_err}"),
+        )
+        .with_command("git push")
+        .retryable());
+    }
+
+    op.checkpoint_before_push()?;
+    op.step(OperationStep::Rebasing);
+    if run(repo_path, &["pull", "--rebase"]).is_err() {
+        // Don't leave a half-applied rebase behind for the next push to trip on.
+        let _ = run(repo_path, &["rebase", "--abort"]);
     }
+    watcher::note_own_head(repo_path);
+    op.checkpoint_before_push()?;
+    op.step(OperationStep::Pushing);
+    run(repo_path, &["push"]).map_err(|_| {
+        AppError::new(
+            ErrorCode::GitPushRejected,
+            format!("Failed to push after retry: {first_err}"),
+        )
+        .with_command("git push")
+        .retryable()
+    })?;
 
     Ok(())
 }
 
+/// Number of local commits the upstream branch doesn't have yet, or `None`
+/// if the branch has no upstream.
+pub fn unpushed_count(repo_path: &str) -> Option<usize> {
+    run(repo_path, &["rev-list", "--count", "@{upstream}..HEAD"])
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// The commit HEAD points to, or `None` in a repository without commits.
+pub fn head_commit(repo_path: &str) -> Option<String> {
+    run(repo_path, &["rev-parse", "HEAD"])
+        .ok()
+        .map(|out| out.trim().to_string())
+}
+
+/// The checked-out branch, or `None` with a detached HEAD.
+pub fn current_branch(repo_path: &str) -> Option<String> {
+    let out = run(repo_path, &["rev-parse", "--abbrev-ref", "HEAD"]).ok()?;
+    let branch = out.trim();
+    (branch != "HEAD").then(|| branch.to_string())
+}
+
+/// The subset of `rel_paths` that `.gitignore` (and other exclude files)
+/// exclude. Tracked files never are. If git can't be run, nothing is.
+pub fn ignored(repo_path: &str, rel_paths: &[String]) -> HashSet<String> {
+    if rel_paths.is_empty() {
+        return HashSet::new();
+    }
+    let child = Command::new("git")
+        .args(["check-ignore", "--stdin", "-z"])
+        .current_dir(repo_path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn();
+    let Ok(mut child) = child else {
+        return HashSet::new();
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(rel_paths.join("\0").as_bytes());
+    }
+    match child.wait_with_output() {
+        // Exit code 1 means none of the paths are ignored.
+        Ok(output) if output.status.code() == Some(0) => String::from_utf8_lossy(&output.stdout)
+            .split('\0')
+            .filter(|p| !p.is_empty())
+            .map(str::to_string)
+            .collect(),
+        _ => HashSet::new(),
+    }
+}
+
+/// Whether git's error output says the remote couldn't be reached at all.
+fn is_unreachable(stderr: &str) -> bool {
+    const MARKERS: &[&str] = &[
+        "Could not resolve host",
+        "Could not read from remote repository",
+        "unable to access",
+        "Connection refused",
+        "Connection timed out",
+        "Network is unreachable",
+        "Operation timed out",
+    ];
+    MARKERS.iter().any(|m| stderr.contains(m))
+}
+
+/// Run a git subcommand, returning its stdout.
+fn run(repo_path: &str, args: &[&str]) -> Result<String, AppError> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(repo_path)
+        .output()
+        .map_err(|e| git_error(args[0], format!("Failed to run git {}: {}", args[0], e)))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(git_error(
+            args[0],
+            format!("git {} failed: {}", args[0], stderr.trim()),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn git_error(subcommand: &str, message: String) -> AppError {
+    AppError::new(ErrorCode::Git, message).with_command(format!("git {subcommand}"))
+}
+
+/// Repo-relative paths with uncommitted changes, staged or not, including
+/// untracked files (directories show as one `dir/` path).
+pub fn uncommitted_paths(repo_path: &str) -> Result<HashSet<String>, AppError> {
+    let out = run(repo_path, &["status", "--porcelain", "-z"])?;
+    let mut paths = HashSet::new();
+    let mut records = out.split('\0').filter(|r| r.len() > 3);
+    while let Some(record) = records.next() {
+        let (status, path) = record.split_at(3);
+        paths.insert(path.to_string());
+        // Renames and copies are followed by their source path.
+        if status.starts_with(['R', 'C']) {
+            if let Some(source) = records.next() {
+                paths.insert(source.to_string());
+            }
+        }
+    }
+    Ok(paths)
+}
+
 /// Return the raw `git status --porcelain` output for change detection.
-pub fn git_status_porcelain(repo_path: &str) -> Result<String, String> {
+pub fn git_status_porcelain(repo_path: &str) -> Result<String, AppError> {
     let output = Command::new("git")
         .args(["status", "--porcelain"])
         .current_dir(repo_path)
         .output()
-        .map_err(|e| format!("Failed to run git status: {}", e))?;
+        .map_err(|e| git_error("status", format!("Failed to run git status: {}", e)))?;
 
     Ok(String::from_utf8_lossy(&output.stdout).to_string())
 }
 
-/// Find the most recent commit hash for a file where the message started with "publish:".
-pub fn find_last_publish_commit(repo_path: &str, rel_path: &str) -> Result<String, String> {
+/// Find the most recent commit hash for a file whose message starts with `prefix`
+/// (the repo's publish prefix, "publish:" by default).
+pub fn find_last_publish_commit(
+    repo_path: &str,
+    rel_path: &str,
+    prefix: &str,
+) -> Result<String, AppError> {
+    let grep = format!("--grep=^{}", escape_basic_regex(prefix.trim_end()));
     let output = Command::new("git")
-        .args([
-            "log",
-            "-1",
-            "--grep=^publish:",
-            "--format=%H",
-            "--",
-            rel_path,
-        ])
+        .args(["log", "-1", &grep, "--format=%H", "--", rel_path])
         .current_dir(repo_path)
         .output()
-        .map_err(|e| format!("Failed to run git log: {}", e))?;
+        .map_err(|e| git_error("log", format!("Failed to run git log: {}", e)))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("git log failed: {}", stderr));
+        return Err(git_error("log", format!("git log failed: {}", stderr)));
     }
 
     let hash = String::from_utf8_lossy(&output.stdout).trim().to_string();
     if hash.is_empty() {
-        return Err("No publication commit found for this file.".to_string());
+        return Err(
+            AppError::not_found("No publication commit found for this file.").with_path(rel_path),
+        );
     }
     Ok(hash)
 }
 
-/// Reset a file to its state in the given commit.
-pub fn rollback_file(repo_path: &str, commit_hash: &str, rel_path: &str) -> Result<(), String> {
-    let output = Command::new("git")
-        .args(["checkout", commit_hash, "--", rel_path])
-        .current_dir(repo_path)
-        .output()
-        .map_err(|e| format!("Failed to run git checkout: {}", e))?;
-
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("git checkout failed: {}", stderr));
+/// Escape characters special in git's default (POSIX basic) `--grep` regex.
+fn escape_basic_regex(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '.' | '[' | ']' | '*' | '^' | '$' | '\\') {
+            out.push('\\');
+        }
+        out.push(c);
     }
-    Ok(())
+    out
+}
+
+/// Reset a file to its state in the given commit.
+pub fn rollback_file(repo_path: &str, commit_hash: &str, rel_path: &str) -> Result<(), AppError> {
+    checkout(repo_path, commit_hash, rel_path)
 }
 
 /// Reset an entire directory to its state in the given commit.
@@ -126,23 +317,34 @@ pub fn rollback_directory(
     repo_path: &str,
     commit_hash: &str,
     rel_dir: &str,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     let dir_path = Path::new(repo_path).join(rel_dir);
 
     if dir_path.is_dir() {
-        fs::remove_dir_all(&dir_path)
-            .map_err(|e| format!("Failed to clean directory for rollback: {}", e))?;
+        fs::remove_dir_all(&dir_path).map_err(|e| {
+            AppError::io(format!("Failed to clean directory for rollback: {}", e))
+                .with_path(&dir_path)
+        })?;
     }
 
+    checkout(repo_path, commit_hash, rel_dir)
+}
+
+/// Restore `rel_path` from `commit_hash`.
+/// The watcher treats the restored files as the panel's own change.
+fn checkout(repo_path: &str, commit_hash: &str, rel_path: &str) -> Result<(), AppError> {
     let output = Command::new("git")
-        .args(["checkout", commit_hash, "--", rel_dir])
+        .args(["checkout", commit_hash, "--", rel_path])
         .current_dir(repo_path)
         .output()
-        .map_err(|e| format!("Failed to run git checkout: {}", e))?;
+        .map_err(|e| git_error("checkout", format!("Failed to run git checkout: {}", e)))?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("git checkout failed: {}", stderr));
+        return Err(
+            git_error("checkout", format!("git checkout failed: {}", stderr)).with_path(rel_path),
+        );
     }
+    watcher::note_own_tree(&Path::new(repo_path).join(rel_path));
     Ok(())
 }
@@ -0,0 +1,150 @@
+//! Analytics history — daily series persisted in the app data directory.
+//!
+//! Every multi-day `fetch_analytics` call merges its daily counts into the
+//! active profile's history file, so exports can cover ranges longer than a
+//! single fetch. A history file that can't be parsed is backed up and
+//! reported rather than replaced, so the next fetch doesn't wipe it.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+use crate::config;
+use crate::files;
+use crate::frontmatter::{calculate_content_hash, to_slug};
+use crate::types::{CfAnalytics, CfDailyCount};
+
+/// Daily counts keyed by date (`YYYY-MM-DD`), one map per metric.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct AnalyticsHistory {
+    #[serde(default)]
+    requests: BTreeMap<String, CfDailyCount>,
+    #[serde(default)]
+    page_views: BTreeMap<String, CfDailyCount>,
+}
+
+/// Return the path to the active profile's history JSON file in the app-data directory.
+fn history_path(app: &tauri::AppHandle) -> PathBuf {
+    let dir = app
+        .path()
+        .app_data_dir()
+        .unwrap_or_else(|_| PathBuf::from("."));
+    let profile = config::load_config(app)
+        .ok()
+        .and_then(|cfg| cfg.active_profile)
+        .map(|name| to_slug(&name))
+        .filter(|slug| !slug.is_empty())
+        .unwrap_or_else(|| "default".into());
+    dir.join("analytics-history")
+        .join(format!("{profile}.json"))
+}
+
+fn load(path: &Path) -> Result<AnalyticsHistory, String> {
+    let data = match fs::read_to_string(path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(AnalyticsHistory::default()),
+        Err(e) => return Err(format!("Failed to read analytics history: {e}")),
+    };
+    serde_json::from_str(&data).map_err(|e| match backup_corrupt(path, &data) {
+        Ok(backup) => format!(
+            "Analytics history could not be loaded ({e}). A copy was saved to {}.",
+            backup.display()
+        ),
+        Err(backup_err) => backup_err,
+    })
+}
+
+/// Copy an unreadable history file next to the original, named by content
+/// hash so repeated loads don't pile up copies (see `config::load_stored`).
+fn backup_corrupt(path: &Path, data: &str) -> Result<PathBuf, String> {
+    let hash = calculate_content_hash(data);
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let backup = path.with_file_name(format!("{stem}.corrupt-{}.json", &hash[..8]));
+    if !backup.exists() {
+        fs::write(&backup, data)
+            .map_err(|e| format!("Failed to back up analytics history: {e}"))?;
+    }
+    Ok(backup)
+}
+
+/// Merge the daily series of a fetch into the stored history.
+/// Hourly (1-day) fetches are skipped since they don't map onto dates.
+pub fn record(
+    app: &tauri::AppHandle,
+    analytics: &CfAnalytics,
+    engagement: bool,
+) -> Result<(), String> {
+    if analytics.period == "1d" {
+        return Ok(());
+    }
+
+    let path = history_path(app);
+    let mut history = load(&path)?;
+    let series = if engagement {
+        &mut history.page_views
+    } else {
+        &mut history.requests
+    };
+    for day in &analytics.daily_requests {
+        series.insert(day.date.clone(), day.clone());
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create history directory: {e}"))?;
+    }
+    let data = serde_json::to_string(&history)
+        .map_err(|e| format!("Failed to serialize analytics history: {e}"))?;
+    files::write_atomic(&path, data.as_bytes())
+}
+
+/// Build a `CfAnalytics` from the stored daily series between `from` and `to`
+/// (inclusive, `YYYY-MM-DD`). Only the daily series and total are populated.
+pub fn range(
+    app: &tauri::AppHandle,
+    from: &str,
+    to: &str,
+    engagement: bool,
+) -> Result<CfAnalytics, String> {
+    if parse_date(from)? > parse_date(to)? {
+        return Err("Start date is after end date.".to_string());
+    }
+    let history = load(&history_path(app))?;
+    let series = if engagement {
+        &history.page_views
+    } else {
+        &history.requests
+    };
+    let daily_requests: Vec<CfDailyCount> = series
+        .range(from.to_string()..=to.to_string())
+        .map(|(_, day)| day.clone())
+        .collect();
+    if daily_requests.is_empty() {
+        return Err(format!("No stored analytics between {from} and {to}."));
+    }
+
+    Ok(CfAnalytics {
+        period: format!("{from}..{to}"),
+        total_requests: daily_requests.iter().map(|d| d.count).sum(),
+        daily_requests,
+        top_paths: Vec::new(),
+        top_countries: Vec::new(),
+        status_codes: Vec::new(),
+        browsers: Vec::new(),
+        comparison: None,
+        anomalies: Vec::new(),
+    })
+}
+
+/// Parse a strict `YYYY-MM-DD` date, the form history keys are stored in.
+fn parse_date(date: &str) -> Result<NaiveDate, String> {
+    // chrono accepts unpadded fields, which would not line up with stored keys.
+    let parsed = (date.len() == 10)
+        .then(|| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+        .flatten();
+    parsed.ok_or_else(|| format!("Invalid date \"{date}\": expected YYYY-MM-DD."))
+}
@@ -0,0 +1,274 @@
+//! Content index — cached entries so `list_content` doesn't re-read and re-hash
+//! the whole repository on every call.
+//!
+//! Each entry is cached with stamps (modification time and size) of its entry
+//! file and, for directory-hashed collections, of every other file in its
+//! directory. A listing stats each entry file and reparses only entries whose
+//! stamps changed. Walking an entry directory is skipped while the watcher
+//! covers the repository and has reported nothing under it; mutations through
+//! the operation queue and watcher errors force a full stamp check instead.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tauri::Manager;
+
+use crate::collections;
+use crate::error::AppError;
+use crate::frontmatter::parse_content_entry;
+use crate::repo_config;
+use crate::types::{ContentEntry, HashMode};
+
+/// Indexes by repository root.
+pub struct IndexState(Mutex<HashMap<String, RepoIndex>>);
+
+impl IndexState {
+    pub fn new() -> Self {
+        Self(Mutex::new(HashMap::new()))
+    }
+}
+
+#[derive(Default)]
+struct RepoIndex {
+    /// The watcher reports changes under the collection directories.
+    watched: bool,
+    /// Check every stamp on the next listing, watched or not.
+    revalidate: bool,
+    /// Paths the watcher reported since the last listing.
+    dirty: Vec<PathBuf>,
+    /// Cached entries by entry file.
+    entries: HashMap<PathBuf, Indexed>,
+    /// Bumped whenever a listing stores `entries`.
+    generation: u64,
+}
+
+/// What a listing works from, copied out of the index.
+struct Snapshot {
+    generation: u64,
+    full_check: bool,
+    dirty: Vec<PathBuf>,
+    entries: HashMap<PathBuf, Indexed>,
+}
+
+impl RepoIndex {
+    fn snapshot(&mut self) -> Snapshot {
+        let full_check = !self.watched || self.revalidate;
+        self.revalidate = false;
+        Snapshot {
+            generation: self.generation,
+            full_check,
+            dirty: std::mem::take(&mut self.dirty),
+            entries: self.entries.clone(),
+        }
+    }
+
+    /// Store a listing's entries, unless another listing stored since its
+    /// snapshot: that one may have missed what this one took from `dirty`, so
+    /// the result is dropped and the checks it consumed go back for the next.
+    fn store(&mut self, snapshot: Snapshot, entries: HashMap<PathBuf, Indexed>) -> bool {
+        if snapshot.generation != self.generation {
+            self.dirty.extend(snapshot.dirty);
+            self.revalidate |= snapshot.full_check;
+            return false;
+        }
+        self.entries = entries;
+        self.generation += 1;
+        true
+    }
+}
+
+#[derive(Clone)]
+struct Indexed {
+    file: Stamp,
+    /// Other files of the entry directory, sorted (directory-hashed collections only).
+    tree: Vec<(PathBuf, Stamp)>,
+    entry: ContentEntry,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Stamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+fn stamp(path: &Path) -> Option<Stamp> {
+    let meta = fs::metadata(path).ok()?;
+    Some(Stamp {
+        modified: meta.modified().ok(),
+        len: meta.len(),
+    })
+}
+
+/// Stamps of the files `calculate_directory_hash` reads for `dir`.
+fn tree_stamps(dir: &Path, entry_file: &Path) -> Vec<(PathBuf, Stamp)> {
+    fn walk(dir: &Path, out: &mut Vec<(PathBuf, Stamp)>) {
+        let Ok(readdir) = fs::read_dir(dir) else {
+            return;
+        };
+        for entry in readdir.flatten() {
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let path = entry.path();
+            if path.is_dir() {
+                walk(&path, out);
+            } else if let Some(s) = stamp(&path) {
+                out.push((path, s));
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    walk(dir, &mut out);
+    out.retain(|(p, _)| p != entry_file);
+    out.sort_by(|a, b| a.0.cmp(&b.0));
+    out
+}
+
+fn with_index<T>(
+    app: &tauri::AppHandle,
+    repo_path: &str,
+    f: impl FnOnce(&mut RepoIndex) -> T,
+) -> T {
+    let state = app.state::<IndexState>();
+    let mut indexes = state.0.lock().unwrap_or_else(|e| e.into_inner());
+    f(indexes.entry(repo_path.to_string()).or_default())
+}
+
+/// Record whether the watcher covers `repo_path`. Starting to watch forces a
+/// full check, since changes before the watcher started were not seen.
+pub fn set_watched(app: &tauri::AppHandle, repo_path: &str, watched: bool) {
+    with_index(app, repo_path, |index| {
+        index.watched = watched;
+        index.revalidate = true;
+    });
+}
+
+/// Note paths the watcher reported as changed.
+pub fn mark_dirty(app: &tauri::AppHandle, repo_path: &str, paths: &[PathBuf]) {
+    with_index(app, repo_path, |index| index.dirty.extend_from_slice(paths));
+}
+
+/// Check every stamp on the next listing, e.g. after the panel changed files itself.
+pub fn revalidate(app: &tauri::AppHandle, repo_path: &str) {
+    with_index(app, repo_path, |index| index.revalidate = true);
+}
+
+/// Every entry of every collection, sorted by creation date (newest first),
+/// reparsing only what changed since the last listing.
+///
+/// The index lock is held only to copy the cache out and store the result;
+/// stats, walks and parses run without it (see `RepoIndex::store` for
+/// listings that overlap).
+pub fn list(app: &tauri::AppHandle, repo_path: &str) -> Result<Vec<ContentEntry>, AppError> {
+    let repo = repo_config::load(repo_path)?;
+    let base = Path::new(repo_path);
+
+    let snapshot = with_index(app, repo_path, RepoIndex::snapshot);
+    let (full_check, dirty, cache) = (snapshot.full_check, &snapshot.dirty, &snapshot.entries);
+
+    let mut indexed = HashMap::new();
+    let mut entries = Vec::new();
+    for collection in &repo.collections {
+        for (slug, file_path) in collections::list_entries(base, collection) {
+            let Some(file) = stamp(&file_path) else {
+                continue;
+            };
+            let hash_dir = match collection.hash {
+                HashMode::Directory => collections::entry_dir_of(collection, &file_path),
+                HashMode::Body => None,
+            };
+
+            let cached = cache.get(&file_path).filter(|c| {
+                c.file == file && c.entry.slug == slug && c.entry.content_type == collection.id
+            });
+            // `None` keeps the cached tree: nothing under the directory was reported.
+            let tree = match (&hash_dir, cached) {
+                (None, _) => Some(Vec::new()),
+                (Some(dir), Some(_))
+                    if !full_check && !dirty.iter().any(|p| p.starts_with(dir)) =>
+                {
+                    None
+                }
+                (Some(dir), _) => Some(tree_stamps(dir, &file_path)),
+            };
+            if let Some(c) = cached {
+                if tree.as_ref().is_none_or(|t| *t == c.tree) {
+                    entries.push(c.entry.clone());
+                    indexed.insert(file_path, c.clone());
+                    continue;
+                }
+            }
+
+            let Some(entry) = parse_content_entry(&slug, collection, &file_path) else {
+                continue;
+            };
+            let tree = tree.unwrap_or_default();
+            entries.push(entry.clone());
+            indexed.insert(file_path, Indexed { file, tree, entry });
+        }
+    }
+    with_index(app, repo_path, |index| index.store(snapshot, indexed));
+
+    entries.sort_by(|a, b| b.created_date.cmp(&a.created_date));
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn watched(dirty: &[&str]) -> RepoIndex {
+        RepoIndex {
+            watched: true,
+            dirty: dirty.iter().map(PathBuf::from).collect(),
+            ..RepoIndex::default()
+        }
+    }
+
+    #[test]
+    fn sequential_listings_store() {
+        let mut index = watched(&["content/a/cover.png"]);
+        let first = index.snapshot();
+        assert!(!first.full_check);
+        assert_eq!(first.dirty, [PathBuf::from("content/a/cover.png")]);
+        assert!(index.store(first, HashMap::new()));
+
+        let second = index.snapshot();
+        assert!(second.dirty.is_empty());
+        assert!(index.store(second, HashMap::new()));
+        assert_eq!(index.generation, 2);
+    }
+
+    #[test]
+    fn overlapping_listing_that_stores_last_gives_back_its_checks() {
+        let mut index = watched(&["content/a/cover.png"]);
+        let first = index.snapshot();
+        let second = index.snapshot();
+        assert!(second.dirty.is_empty());
+
+        // The second listing finishes first, without the dirty path the first took.
+        assert!(index.store(second, HashMap::new()));
+        assert!(!index.store(first, HashMap::new()));
+        assert_eq!(index.generation, 1);
+
+        let next = index.snapshot();
+        assert_eq!(next.dirty, [PathBuf::from("content/a/cover.png")]);
+        assert!(index.store(next, HashMap::new()));
+    }
+
+    #[test]
+    fn dropped_full_check_is_redone() {
+        let mut index = watched(&[]);
+        index.revalidate = true;
+        let first = index.snapshot();
+        let second = index.snapshot();
+        assert!(first.full_check && !second.full_check);
+
+        assert!(index.store(second, HashMap::new()));
+        assert!(!index.store(first, HashMap::new()));
+        assert!(index.snapshot().full_check);
+    }
+}
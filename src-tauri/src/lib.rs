@@ -1,13 +1,34 @@
 use tauri::Manager;
 
+mod analytics;
+#[cfg(test)]
+mod bindings;
+mod bulk;
 mod cloudflare;
+mod collections;
 mod commands;
 mod config;
 mod content;
 mod devserver;
+mod diff;
+mod error;
+mod export;
+mod files;
 mod frontmatter;
 mod git;
+mod history;
+mod index;
+mod migrations;
+mod operations;
+mod push_queue;
+mod queue;
+mod repo_config;
+mod scan;
+mod scopes;
+mod secrets;
 mod security;
+mod snapshots;
+mod taxonomy;
 mod types;
 mod watcher;
 
@@ -20,25 +41,60 @@ pub fn run() {
         .plugin(tauri_plugin_fs::init())
         .manage(watcher::WatcherState::new())
         .manage(devserver::DevServerState::new())
+        .manage(secrets::SecretsState::new())
+        .manage(operations::OperationsState::new())
+        .manage(queue::QueueState::new())
+        .manage(index::IndexState::new())
+        .manage(push_queue::PushQueueState::new())
         .manage(commands::HttpClient(
             reqwest::Client::builder()
                 .timeout(std::time::Duration::from_secs(15))
                 .build()
                 .expect("Failed to build HTTP client"),
         ))
+        .setup(|app| {
+            push_queue::spawn_retry_loop(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             commands::get_config,
             commands::set_config,
+            commands::reset_config,
+            commands::switch_profile,
+            commands::create_profile,
+            commands::delete_profile,
+            commands::set_cf_api_token,
+            commands::clear_cf_api_token,
+            commands::get_secrets_status,
+            commands::unlock_secrets,
+            commands::rekey_secrets,
             commands::validate_repo_path,
+            commands::get_repo_config,
             commands::list_content,
             commands::read_file,
             commands::write_file,
+            commands::list_snapshots,
+            commands::restore_snapshot,
+            commands::get_recoverable_snapshots,
             commands::create_post,
             commands::create_app,
+            commands::create_entry,
             commands::delete_content,
             commands::publish,
             commands::unpublish,
             commands::rollback,
+            commands::cancel_operation,
+            commands::get_operation_queue,
+            commands::list_pending_pushes,
+            commands::retry_pending_pushes,
+            commands::plan_migrations,
+            commands::apply_migrations,
+            commands::plan_bulk_edit,
+            commands::apply_bulk_edit,
+            commands::get_taxonomy,
+            commands::plan_tag_merge,
+            commands::apply_tag_merge,
+            commands::set_tag_description,
             commands::set_pinned,
             commands::git_status,
             commands::open_in_vscode,
@@ -49,6 +105,8 @@ pub fn run() {
             commands::check_url_health,
             commands::fetch_last_deployment,
             commands::fetch_analytics,
+            commands::fetch_web_analytics,
+            commands::export_analytics,
             commands::test_cf_connection,
         ])
         .build(tauri::generate_context!())
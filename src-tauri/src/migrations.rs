@@ -0,0 +1,163 @@
+//! Content migrations — explicit, reviewable fixes to stored content.
+//!
+//! Reading content never writes; anything that needs rewriting files is a
+//! migration here instead. Each migration rewrites one entry file at a time
+//! and leaves files that don't need it alone, so running the registry twice
+//! changes nothing the second time. `plan` only reads and returns a diff per
+//! file; `apply` writes the same rewrites and commits them together.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use crate::collections;
+use crate::content::{self, Rewrite};
+use crate::diff;
+use crate::error::AppError;
+use crate::frontmatter::{
+    assemble_file, get_yaml_bool, get_yaml_field, set_frontmatter_field, split_frontmatter,
+};
+use crate::git;
+use crate::operations::Operation;
+use crate::repo_config::{self, commit_message};
+use crate::types::{
+    CollectionConfig, MigrationChange, MigrationInfo, MigrationPlan, MigrationResult, MutationKind,
+};
+
+/// A named rewrite of entry files.
+struct Migration {
+    id: &'static str,
+    description: &'static str,
+    /// The rewritten file, or `None` when `content` needs no change.
+    migrate: fn(&CollectionConfig, &Path, &str) -> Option<String>,
+}
+
+/// Every migration, in the order they run on a file.
+const MIGRATIONS: &[Migration] = &[Migration {
+    id: "seed-published-hash",
+    description: "Record publishedHash on published entries that predate change tracking.",
+    migrate: seed_published_hash,
+}];
+
+/// Published entries from before change tracking have no `publishedHash`, so
+/// they can never show as changed. Their current content is taken as published.
+fn seed_published_hash(
+    collection: &CollectionConfig,
+    file: &Path,
+    content: &str,
+) -> Option<String> {
+    let (yaml, rest) = split_frontmatter(content)?;
+    let is_draft = get_yaml_bool(&yaml, "isDraft").unwrap_or(true);
+    if is_draft || get_yaml_field(&yaml, "publishedHash").is_some() {
+        return None;
+    }
+    let hash = collections::content_hash(collection, file, &rest)?;
+    let yaml = set_frontmatter_field(&yaml, "publishedHash", &format!("\"{hash}\""));
+    Some(assemble_file(&yaml, &rest))
+}
+
+fn plan_files(repo_path: &str) -> Result<Vec<(MigrationChange, Rewrite)>, AppError> {
+    let repo = repo_config::load(repo_path)?;
+    let base = Path::new(repo_path);
+    let uncommitted = git::uncommitted_paths(repo_path)?;
+
+    let mut planned = Vec::new();
+    for collection in &repo.collections {
+        for (slug, file) in collections::list_entries(base, collection) {
+            let Ok(original) = fs::read_to_string(&file) else {
+                continue;
+            };
+            let mut content = original.clone();
+            let mut applied = Vec::new();
+            for migration in MIGRATIONS {
+                if let Some(next) = (migration.migrate)(collection, &file, &content) {
+                    if next != content {
+                        content = next;
+                        applied.push(migration.id.to_string());
+                    }
+                }
+            }
+            if applied.is_empty() {
+                continue;
+            }
+
+            let rel = file
+                .strip_prefix(base)
+                .unwrap_or(&file)
+                .to_string_lossy()
+                .to_string();
+            let change = MigrationChange {
+                collection: collection.id.clone(),
+                slug,
+                diff: diff::unified(&rel, &original, &content),
+                path: rel.clone(),
+                migrations: applied,
+                uncommitted: content::is_uncommitted(&uncommitted, &rel),
+            };
+            planned.push((
+                change,
+                Rewrite {
+                    file,
+                    rel_path: rel,
+                    content,
+                },
+            ));
+        }
+    }
+    Ok(planned)
+}
+
+/// The registry and every file it would change in `repo_path`. Writes nothing.
+pub fn plan(repo_path: &str) -> Result<MigrationPlan, AppError> {
+    Ok(MigrationPlan {
+        migrations: MIGRATIONS
+            .iter()
+            .map(|m| MigrationInfo {
+                id: m.id.to_string(),
+                description: m.description.to_string(),
+            })
+            .collect(),
+        changes: plan_files(repo_path)?
+            .into_iter()
+            .map(|(change, _)| change)
+            .collect(),
+    })
+}
+
+/// Write every planned change except files with uncommitted edits and commit
+/// them in one commit (see `content::commit_rewrites`).
+///
+/// Callers run this through the repository's operation queue (see `queue`).
+pub fn apply(
+    app: &tauri::AppHandle,
+    repo_path: &str,
+    op: &Operation,
+) -> Result<MigrationResult, AppError> {
+    let repo = repo_config::load(repo_path)?;
+    let (changes, rewrites): (Vec<_>, Vec<_>) = plan_files(repo_path)?
+        .into_iter()
+        .filter(|(change, _)| !change.uncommitted)
+        .unzip();
+
+    let ids: BTreeSet<&str> = changes
+        .iter()
+        .flat_map(|c| c.migrations.iter().map(String::as_str))
+        .collect();
+    let subject = content::files_subject(
+        &ids.into_iter().collect::<Vec<_>>().join(", "),
+        changes.len(),
+    );
+    let commit = content::commit_rewrites(
+        app,
+        repo_path,
+        &rewrites,
+        &commit_message(&repo.commit.migrate, &subject),
+        MutationKind::Migrate,
+        op,
+    )?;
+
+    Ok(MigrationResult {
+        applied: changes,
+        commit,
+    })
+}
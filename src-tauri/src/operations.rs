@@ -0,0 +1,111 @@
+//! Long-running operations — progress events and cooperative cancellation.
+//!
+//! Commands that take a while (publish, delete, analytics) accept an operation id
+//! from the frontend, report each step as an `operation-progress` event carrying
+//! that id, and check for cancellation between steps. Cancellation is only
+//! honored at points where stopping leaves the repo consistent: before any file
+//! is written, or after a commit and before its push.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{Emitter, Manager};
+
+use crate::error::{AppError, ErrorCode};
+use crate::types::{OperationProgress, OperationStep};
+
+/// Event emitted for every progress step.
+const PROGRESS_EVENT: &str = "operation-progress";
+
+/// Cancellation flags of the operations in flight, by id.
+pub struct OperationsState(Mutex<HashMap<String, Arc<AtomicBool>>>);
+
+impl OperationsState {
+    pub fn new() -> Self {
+        Self(Mutex::new(HashMap::new()))
+    }
+}
+
+/// Handle for one running operation. Dropping it unregisters the id.
+pub struct Operation {
+    id: String,
+    app: tauri::AppHandle,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Operation {
+    /// Register an operation under the frontend's id, or a generated one.
+    pub fn begin(app: &tauri::AppHandle, id: Option<String>) -> Self {
+        let id = id.unwrap_or_else(|| {
+            format!(
+                "op-{}",
+                chrono::Utc::now().timestamp_nanos_opt().unwrap_or(0)
+            )
+        });
+        let cancelled = Arc::new(AtomicBool::new(false));
+        if let Ok(mut ops) = app.state::<OperationsState>().0.lock() {
+            ops.insert(id.clone(), cancelled.clone());
+        }
+        Self {
+            id,
+            app: app.clone(),
+            cancelled,
+        }
+    }
+
+    /// Report a step without a count.
+    pub fn step(&self, step: OperationStep) {
+        self.emit(step, None, None);
+    }
+
+    /// Report step `current` of `total` (1-based), e.g. query chunk N of M.
+    pub fn step_of(&self, step: OperationStep, current: u32, total: u32) {
+        self.emit(step, Some(current), Some(total));
+    }
+
+    fn emit(&self, step: OperationStep, current: Option<u32>, total: Option<u32>) {
+        let _ = self.app.emit(
+            PROGRESS_EVENT,
+            OperationProgress {
+                id: self.id.clone(),
+                step,
+                current,
+                total,
+            },
+        );
+    }
+
+    /// Fail with `ErrorCode::Cancelled` if cancellation was requested.
+    pub fn checkpoint(&self) -> Result<(), AppError> {
+        if self.cancelled.load(Ordering::SeqCst) {
+            return Err(AppError::new(ErrorCode::Cancelled, "Cancelled."));
+        }
+        Ok(())
+    }
+
+    /// Like `checkpoint`, for a stop after the commit: the work is kept locally.
+    pub fn checkpoint_before_push(&self) -> Result<(), AppError> {
+        self.checkpoint().map_err(|e| AppError {
+            message: "Cancelled before pushing. The commit is queued to push later.".to_string(),
+            ..e
+        })
+    }
+}
+
+impl Drop for Operation {
+    fn drop(&mut self) {
+        if let Ok(mut ops) = self.app.state::<OperationsState>().0.lock() {
+            ops.remove(&self.id);
+        }
+    }
+}
+
+/// Request cancellation. Returns false if no such operation is running.
+pub fn cancel(app: &tauri::AppHandle, id: &str) -> Result<bool, String> {
+    let state = app.state::<OperationsState>();
+    let ops = state.0.lock().map_err(|e| e.to_string())?;
+    Ok(ops
+        .get(id)
+        .map(|flag| flag.store(true, Ordering::SeqCst))
+        .is_some())
+}
@@ -0,0 +1,299 @@
+//! Push queue — local commits waiting to reach the remote, persisted in the app
+//! data directory.
+//!
+//! Publish, unpublish and delete commit first and push second. Each commit is
+//! recorded here before the push is attempted; a push that can't reach the
+//! remote leaves its commits queued instead of failing the operation, and a
+//! background loop retries them through the repository's operation queue. Any
+//! other push failure (a rejection, bad credentials) is returned to the caller;
+//! its commits stay queued with the error until a push goes through.
+//! `git push` sends every local commit at once, so one success clears the
+//! repository's whole queue.
+
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+use crate::error::{AppError, ErrorCode};
+use crate::files;
+use crate::frontmatter::{calculate_content_hash, now_iso};
+use crate::git;
+use crate::operations::Operation;
+use crate::queue;
+use crate::types::{MutationKind, PendingPush, PushQueueChanged};
+
+/// Event emitted whenever a repository's queue changes or a retry fails.
+const PUSHES_EVENT: &str = "pending-pushes-changed";
+/// Delay between automatic retries while commits are queued.
+const RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Serializes read-modify-write of the queue file: the retry loop and commands
+/// for different repositories may touch it at the same time.
+pub struct PushQueueState(Mutex<()>);
+
+impl PushQueueState {
+    pub fn new() -> Self {
+        Self(Mutex::new(()))
+    }
+}
+
+fn queue_path(app: &tauri::AppHandle) -> PathBuf {
+    app.path()
+        .app_data_dir()
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join("pending-pushes.json")
+}
+
+fn load(app: &tauri::AppHandle) -> Result<Vec<PendingPush>, String> {
+    let path = queue_path(app);
+    let data = match fs::read_to_string(&path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read push queue: {e}")),
+    };
+    parse(&data).map_err(|reason| match backup_corrupt(&path, &data) {
+        Ok(backup) => format!(
+            "Push queue could not be loaded ({reason}). A copy was saved to {}.",
+            backup.display()
+        ),
+        Err(e) => e,
+    })
+}
+
+fn parse(data: &str) -> Result<Vec<PendingPush>, String> {
+    if data.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    serde_json::from_str(data).map_err(|e| e.to_string())
+}
+
+/// Copy an unreadable queue file next to the original, named by content hash
+/// so repeated loads don't pile up copies (see `config::load_stored`).
+fn backup_corrupt(path: &Path, data: &str) -> Result<PathBuf, String> {
+    let hash = calculate_content_hash(data);
+    let backup = path.with_file_name(format!("pending-pushes.corrupt-{}.json", &hash[..8]));
+    if !backup.exists() {
+        fs::write(&backup, data).map_err(|e| format!("Failed to back up push queue: {e}"))?;
+    }
+    Ok(backup)
+}
+
+fn save(app: &tauri::AppHandle, pending: &[PendingPush]) -> Result<(), String> {
+    let path = queue_path(app);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create data directory: {e}"))?;
+    }
+    let data = serde_json::to_string_pretty(pending)
+        .map_err(|e| format!("Failed to serialize push queue: {e}"))?;
+    files::write_atomic(&path, data.as_bytes())
+}
+
+fn emit(app: &tauri::AppHandle, repo_path: Option<&str>, error: Option<AppError>) {
+    let _ = app.emit(
+        PUSHES_EVENT,
+        PushQueueChanged {
+            repo_path: repo_path.map(str::to_string),
+            error,
+        },
+    );
+}
+
+/// Apply `change` to the stored queue, emitting an event for `repo_path`.
+fn update(
+    app: &tauri::AppHandle,
+    repo_path: &str,
+    change: impl FnOnce(&mut Vec<PendingPush>),
+) -> Result<(), String> {
+    let state = app.state::<PushQueueState>();
+    let _guard = state.0.lock().unwrap_or_else(|e| e.into_inner());
+    let mut pending = load(app)?;
+    change(&mut pending);
+    save(app, &pending)?;
+    emit(app, Some(repo_path), None);
+    Ok(())
+}
+
+/// Commits queued for `repo_path`, oldest first.
+pub fn pending(app: &tauri::AppHandle, repo_path: &str) -> Result<Vec<PendingPush>, AppError> {
+    Ok(load(app)?
+        .into_iter()
+        .filter(|p| p.repo_path == repo_path)
+        .collect())
+}
+
+fn clear(app: &tauri::AppHandle, repo_path: &str) -> Result<(), String> {
+    update(app, repo_path, |all| {
+        all.retain(|p| p.repo_path != repo_path)
+    })
+}
+
+/// Note a failed push on every entry of `repo_path`.
+fn record_failure(all: &mut [PendingPush], repo_path: &str, error: &str) {
+    for p in all.iter_mut().filter(|p| p.repo_path == repo_path) {
+        p.attempts += 1;
+        p.last_error = Some(error.to_string());
+    }
+}
+
+/// Slugs with a queued commit in `repo_path`.
+pub fn pending_slugs(app: &tauri::AppHandle, repo_path: &str) -> Result<HashSet<String>, AppError> {
+    Ok(pending(app, repo_path)?
+        .into_iter()
+        .map(|p| p.slug)
+        .collect())
+}
+
+/// Record `commit` (if one was made) as awaiting push, then push the repository.
+/// The commit stays queued if the push fails; a remote that can't be reached
+/// is not an error, cancellation and other push failures are.
+pub fn enqueue_and_push(
+    app: &tauri::AppHandle,
+    repo_path: &str,
+    kind: MutationKind,
+    slug: &str,
+    commit: Option<String>,
+    op: &Operation,
+) -> Result<(), AppError> {
+    let Some(commit) = commit else {
+        return Ok(());
+    };
+    let entry = PendingPush {
+        repo_path: repo_path.to_string(),
+        commit,
+        slug: slug.to_string(),
+        kind,
+        committed_at: now_iso(),
+        attempts: 0,
+        last_error: None,
+    };
+    update(app, repo_path, |all| all.push(entry))?;
+    flush(app, repo_path, op)?;
+    Ok(())
+}
+
+/// Push `repo_path` and clear its queue on success. Returns whether the push
+/// went through. Entries stay queued on every failure, with the error noted,
+/// until the upstream has their commits (pushed from a terminal, say); a
+/// remote that can't be reached is not an error, anything else is returned.
+pub fn flush(app: &tauri::AppHandle, repo_path: &str, op: &Operation) -> Result<bool, AppError> {
+    if git::unpushed_count(repo_path) == Some(0) {
+        if !pending(app, repo_path)?.is_empty() {
+            clear(app, repo_path)?;
+        }
+        return Ok(true);
+    }
+    match git::git_push(repo_path, op) {
+        Ok(()) => {
+            clear(app, repo_path)?;
+            Ok(true)
+        }
+        Err(e) if e.code == ErrorCode::Cancelled => Err(e),
+        Err(e) => {
+            update(app, repo_path, |all| {
+                record_failure(all, repo_path, &e.message)
+            })?;
+            if e.code == ErrorCode::Network {
+                Ok(false)
+            } else {
+                Err(e)
+            }
+        }
+    }
+}
+
+/// Retry every repository with queued commits every `RETRY_INTERVAL`, for as
+/// long as the app runs. Pushes go through the operation queue like any
+/// mutation; failures reach the frontend with the `PUSHES_EVENT` payload.
+pub fn spawn_retry_loop(app: tauri::AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(RETRY_INTERVAL);
+        let repos: BTreeSet<String> = match load(&app) {
+            Ok(all) => all.into_iter().map(|p| p.repo_path).collect(),
+            Err(e) => {
+                emit(&app, None, Some(e.into()));
+                continue;
+            }
+        };
+        for repo_path in repos {
+            let result = tauri::async_runtime::block_on(queue::run(
+                app.clone(),
+                repo_path.clone(),
+                MutationKind::Push,
+                String::new(),
+                |app, repo_path, _| flush(app, repo_path, &Operation::begin(app, None)),
+            ));
+            if let Err(e) = result {
+                emit(&app, Some(&repo_path), Some(e));
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn push(repo_path: &str, slug: &str) -> PendingPush {
+        PendingPush {
+            repo_path: repo_path.to_string(),
+            commit: "abc123".to_string(),
+            slug: slug.to_string(),
+            kind: MutationKind::Publish,
+            committed_at: "2026-01-01T00:00:00Z".to_string(),
+            attempts: 0,
+            last_error: None,
+        }
+    }
+
+    #[test]
+    fn empty_file_is_an_empty_queue() {
+        assert!(parse("").unwrap().is_empty());
+        assert!(parse("  \n").unwrap().is_empty());
+        assert!(parse("[]").unwrap().is_empty());
+    }
+
+    #[test]
+    fn unparseable_file_is_an_error() {
+        assert!(parse("[{").is_err());
+        assert!(parse("{\"repo_path\": \"/site\"}").is_err());
+    }
+
+    #[test]
+    fn queue_round_trips() {
+        let data = serde_json::to_string(&[push("/site", "post")]).unwrap();
+        let parsed = parse(&data).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].slug, "post");
+    }
+
+    #[test]
+    fn failure_is_recorded_on_that_repository_only() {
+        let mut all = vec![push("/site", "a"), push("/other", "b"), push("/site", "c")];
+        record_failure(&mut all, "/site", "rejected");
+        record_failure(&mut all, "/site", "still rejected");
+
+        assert_eq!(all[0].attempts, 2);
+        assert_eq!(all[0].last_error.as_deref(), Some("still rejected"));
+        assert_eq!(all[2].attempts, 2);
+        assert_eq!(all[1].attempts, 0);
+        assert_eq!(all[1].last_error, None);
+    }
+
+    #[test]
+    fn corrupt_file_is_backed_up_once() {
+        let dir = std::env::temp_dir().join(format!("panel-push-queue-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("pending-pushes.json");
+
+        let backup = backup_corrupt(&path, "[{").unwrap();
+        assert_eq!(fs::read_to_string(&backup).unwrap(), "[{");
+        assert_eq!(backup_corrupt(&path, "[{").unwrap(), backup);
+        assert_ne!(backup_corrupt(&path, "{]").unwrap(), backup);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 2);
+    }
+}
@@ -0,0 +1,158 @@
+//! Per-repository operation queue — runs mutations one at a time.
+//!
+//! Publish, unpublish, pin, rollback and delete each read, rewrite and commit
+//! files; two of them overlapping could interleave a `git add` with another's
+//! `rollback_directory`. Every mutation of a repository takes a ticket and runs
+//! on the blocking thread pool once all earlier tickets have finished, so the
+//! panel never races itself for the working tree or `.git/index.lock`.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use tauri::{Emitter, Manager};
+
+use crate::error::AppError;
+use crate::frontmatter::now_iso;
+use crate::index;
+use crate::types::{MutationKind, OperationQueue, QueuedMutation};
+
+/// Event emitted whenever a repository's queue changes.
+const QUEUE_EVENT: &str = "operation-queue-changed";
+
+/// Queues by repository root.
+pub struct QueueState(Mutex<HashMap<String, Arc<RepoQueue>>>);
+
+impl QueueState {
+    pub fn new() -> Self {
+        Self(Mutex::new(HashMap::new()))
+    }
+}
+
+#[derive(Default)]
+struct RepoQueue {
+    jobs: Mutex<Jobs>,
+    /// Signalled when the front job finishes.
+    turn: Condvar,
+}
+
+#[derive(Default)]
+struct Jobs {
+    next_ticket: u64,
+    /// In ticket order; the front job is the one running (or about to).
+    queued: VecDeque<(u64, QueuedMutation)>,
+}
+
+impl RepoQueue {
+    fn jobs(&self) -> MutexGuard<'_, Jobs> {
+        // A panicking job never holds this lock, so poisoning carries no torn state.
+        self.jobs.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn enqueue(&self, kind: MutationKind, slug: &str) -> u64 {
+        let mut jobs = self.jobs();
+        let ticket = jobs.next_ticket;
+        jobs.next_ticket += 1;
+        jobs.queued.push_back((
+            ticket,
+            QueuedMutation {
+                kind,
+                slug: slug.to_string(),
+                running: false,
+                queued_at: now_iso(),
+            },
+        ));
+        ticket
+    }
+
+    /// Block until `ticket` reaches the front, then mark it running.
+    fn wait_turn(&self, ticket: u64) {
+        let mut jobs = self.jobs();
+        while jobs.queued.front().map(|(t, _)| *t) != Some(ticket) {
+            jobs = self.turn.wait(jobs).unwrap_or_else(|e| e.into_inner());
+        }
+        if let Some((_, mutation)) = jobs.queued.front_mut() {
+            mutation.running = true;
+        }
+    }
+
+    fn finish(&self, ticket: u64) {
+        self.jobs().queued.retain(|(t, _)| *t != ticket);
+        self.turn.notify_all();
+    }
+
+    fn snapshot(&self) -> Vec<QueuedMutation> {
+        self.jobs().queued.iter().map(|(_, m)| m.clone()).collect()
+    }
+}
+
+/// Releases the ticket even if the job panics, so later jobs are not stuck.
+struct Turn<'a> {
+    queue: &'a RepoQueue,
+    ticket: u64,
+}
+
+impl Drop for Turn<'_> {
+    fn drop(&mut self) {
+        self.queue.finish(self.ticket);
+    }
+}
+
+fn repo_queue(app: &tauri::AppHandle, repo_path: &str) -> Arc<RepoQueue> {
+    let state = app.state::<QueueState>();
+    let mut queues = state.0.lock().unwrap_or_else(|e| e.into_inner());
+    queues.entry(repo_path.to_string()).or_default().clone()
+}
+
+fn emit_changed(app: &tauri::AppHandle, repo_path: &str, queue: &RepoQueue) {
+    let _ = app.emit(
+        QUEUE_EVENT,
+        OperationQueue {
+            repo_path: repo_path.to_string(),
+            mutations: queue.snapshot(),
+        },
+    );
+}
+
+/// Queue a mutation of `slug` in `repo_path` and run `job` on the blocking pool
+/// once every earlier mutation of that repository has finished.
+pub async fn run<T, F>(
+    app: tauri::AppHandle,
+    repo_path: String,
+    kind: MutationKind,
+    slug: String,
+    job: F,
+) -> Result<T, AppError>
+where
+    F: FnOnce(&tauri::AppHandle, &str, &str) -> Result<T, AppError> + Send + 'static,
+    T: Send + 'static,
+{
+    let what = format!("{kind:?} of '{slug}'");
+    let queue = repo_queue(&app, &repo_path);
+    let ticket = queue.enqueue(kind, &slug);
+    emit_changed(&app, &repo_path, &queue);
+
+    tauri::async_runtime::spawn_blocking(move || {
+        queue.wait_turn(ticket);
+        emit_changed(&app, &repo_path, &queue);
+        let result = {
+            let _turn = Turn {
+                queue: &queue,
+                ticket,
+            };
+            job(&app, &repo_path, &slug)
+        };
+        // Don't wait for the watcher's debounce before listings see the changes.
+        index::revalidate(&app, &repo_path);
+        emit_changed(&app, &repo_path, &queue);
+        result
+    })
+    .await
+    .map_err(|e| AppError::from(format!("Queued {what} failed: {e}")))?
+}
+
+/// The mutations queued for `repo_path`, oldest first.
+pub fn snapshot(app: &tauri::AppHandle, repo_path: &str) -> OperationQueue {
+    OperationQueue {
+        repo_path: repo_path.to_string(),
+        mutations: repo_queue(app, repo_path).snapshot(),
+    }
+}
@@ -0,0 +1,78 @@
+//! Per-repository panel settings — `.panel.toml` at the site repo root.
+//!
+//! The file is committed with the site, so content layout, dev server port and
+//! commit conventions apply to every panel that opens the repository. A missing
+//! file means defaults; a malformed one is an error rather than silently ignored.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use crate::collections;
+use crate::scopes;
+use crate::types::{CollectionConfig, RepoConfig};
+
+/// File name of the per-repo settings file.
+pub const FILE_NAME: &str = ".panel.toml";
+
+/// Load `.panel.toml` from the repo root, falling back to defaults if absent.
+pub fn load(repo_path: impl AsRef<Path>) -> Result<RepoConfig, String> {
+    let path = repo_path.as_ref().join(FILE_NAME);
+    let data = match fs::read_to_string(&path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(RepoConfig::default()),
+        Err(e) => return Err(format!("Failed to read {FILE_NAME}: {e}")),
+    };
+    let mut config: RepoConfig =
+        toml::from_str(&data).map_err(|e| format!("Invalid {FILE_NAME}: {e}"))?;
+    config.collections = merge_collections(config.collections);
+    validate(&config).map_err(|e| format!("Invalid {FILE_NAME}: {e}"))?;
+    Ok(config)
+}
+
+/// Layer configured collections over the built-ins: same id replaces, new ids append.
+fn merge_collections(configured: Vec<CollectionConfig>) -> Vec<CollectionConfig> {
+    let mut merged = CollectionConfig::builtins();
+    for collection in configured {
+        match merged.iter_mut().find(|c| c.id == collection.id) {
+            Some(existing) => *existing = collection,
+            None => merged.push(collection),
+        }
+    }
+    merged
+}
+
+/// Reject values that would point the panel outside the repository.
+fn validate(config: &RepoConfig) -> Result<(), String> {
+    for collection in &config.collections {
+        collections::validate(collection)?;
+    }
+    for scope in &config.watch.scopes {
+        scopes::validate(scope)?;
+    }
+    if let Some(metadata) = &config.tags.metadata {
+        if !collections::is_relative_inside(metadata) {
+            return Err(format!(
+                "tags.metadata \"{metadata}\" must be a path inside the repository"
+            ));
+        }
+    }
+    if config.dev_server.port == 0 {
+        return Err("dev_server.port must be non-zero".to_string());
+    }
+    Ok(())
+}
+
+/// Look up a collection by id.
+pub fn collection<'a>(config: &'a RepoConfig, id: &str) -> Result<&'a CollectionConfig, String> {
+    config
+        .collections
+        .iter()
+        .find(|c| c.id == id)
+        .ok_or_else(|| format!("No collection \"{id}\" is configured."))
+}
+
+/// Commit message for a panel operation: `"<prefix> <subject>"`.
+pub fn commit_message(prefix: &str, subject: &str) -> String {
+    format!("{} {}", prefix.trim_end(), subject)
+}
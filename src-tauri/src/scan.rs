@@ -0,0 +1,287 @@
+//! Pre-commit inspection — catches secrets and oversized files before the panel
+//! commits and pushes them to the production branch.
+//!
+//! The scan covers everything a commit of `<path>` would include: untracked
+//! (not ignored) and modified files under the path, plus files already staged
+//! there, which `git add` leaves alone when unchanged. Deletions are never flagged.
+
+use regex::Regex;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::sync::LazyLock;
+
+use crate::error::{AppError, ErrorCode};
+use crate::types::ScanSettings;
+
+/// Leading text of the report returned when findings block a commit.
+const BLOCKED: &str = "Commit blocked by the pre-commit scan";
+
+/// Findings reported per file before the rest are summarized.
+const MAX_FINDINGS_PER_FILE: usize = 5;
+
+/// Shannon entropy (bits per char) above which a token is treated as a secret.
+/// Hex digests top out at 4.0, so SHA hashes (e.g. `publishedHash`) never trip it.
+const ENTROPY_THRESHOLD: f64 = 4.2;
+
+/// Known credential formats.
+static SECRET_PATTERNS: LazyLock<Vec<(&'static str, Regex)>> = LazyLock::new(|| {
+    [
+        ("private key", r"-----BEGIN [A-Z ]*PRIVATE KEY-----"),
+        ("AWS access key", r"\b(?:AKIA|ASIA)[0-9A-Z]{16}\b"),
+        (
+            "GitHub token",
+            r"\b(?:gh[pousr]_[A-Za-z0-9]{36,}|github_pat_[A-Za-z0-9_]{22,})",
+        ),
+        ("Slack token", r"\bxox[abprs]-[A-Za-z0-9-]{10,}"),
+        ("Stripe live key", r"\b[rs]k_live_[A-Za-z0-9]{20,}"),
+        ("Google API key", r"\bAIza[0-9A-Za-z_-]{35}"),
+        ("API secret key", r"\bsk-(?:ant-|proj-)?[A-Za-z0-9_-]{32,}"),
+    ]
+    .into_iter()
+    .map(|(name, re)| (name, Regex::new(re).unwrap()))
+    .collect()
+});
+
+/// `password = "…"`, `api_key: …` and similar assignments; the value is
+/// only reported if it also looks random.
+static ASSIGNMENT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r#"(?i)\b(?:api[_-]?key|secret|token|passw(?:or)?d|client[_-]?secret)\b\s*[:=]\s*["']?([^\s"']{12,})"#,
+    )
+    .unwrap()
+});
+
+/// Candidate tokens for the entropy check.
+static TOKEN_RE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"[A-Za-z0-9+/_=-]{32,}").unwrap());
+
+/// A single reason a file would block the commit.
+pub struct Finding {
+    pub path: String,
+    pub line: Option<usize>,
+    pub detail: String,
+}
+
+/// Scan what a commit of `rel_path` would include and fail with a readable
+/// report (`ErrorCode::ScanBlocked`) if anything is found. A disabled scan always passes.
+pub fn check(repo_path: &str, rel_path: &str, settings: &ScanSettings) -> Result<(), AppError> {
+    if !settings.enabled {
+        return Ok(());
+    }
+    let findings = scan_pending(repo_path, rel_path, settings)?;
+    if findings.is_empty() {
+        Ok(())
+    } else {
+        Err(AppError::new(ErrorCode::ScanBlocked, report(&findings)).with_path(rel_path))
+    }
+}
+
+/// Inspect every file a commit of `rel_path` would include.
+pub fn scan_pending(
+    repo_path: &str,
+    rel_path: &str,
+    settings: &ScanSettings,
+) -> Result<Vec<Finding>, String> {
+    let unstaged = git_paths(
+        repo_path,
+        &[
+            "ls-files",
+            "-z",
+            "--others",
+            "--modified",
+            "--exclude-standard",
+            "--",
+            rel_path,
+        ],
+    )?;
+    let staged = git_paths(
+        repo_path,
+        &["diff", "--cached", "--name-only", "-z", "--", rel_path],
+    )?;
+
+    let max_bytes = settings.max_file_mb.saturating_mul(1024 * 1024);
+    let mut findings = Vec::new();
+    let mut files: Vec<&str> = unstaged
+        .split('\0')
+        .chain(staged.split('\0'))
+        .filter(|p| !p.is_empty())
+        .collect();
+    files.sort_unstable();
+    files.dedup();
+
+    for rel in files {
+        if settings
+            .ignore
+            .iter()
+            .any(|prefix| rel.starts_with(prefix.as_str()))
+        {
+            continue;
+        }
+        let path = Path::new(repo_path).join(rel);
+        // Listed as modified but gone from disk: a deletion.
+        let Ok(meta) = fs::metadata(&path) else {
+            continue;
+        };
+        if !meta.is_file() {
+            continue;
+        }
+
+        if let Some(detail) = sensitive_file_name(rel) {
+            findings.push(Finding {
+                path: rel.to_string(),
+                line: None,
+                detail: detail.to_string(),
+            });
+        }
+        if meta.len() > max_bytes {
+            findings.push(Finding {
+                path: rel.to_string(),
+                line: None,
+                detail: format!(
+                    "{:.1} MB exceeds the {} MB limit",
+                    meta.len() as f64 / (1024.0 * 1024.0),
+                    settings.max_file_mb
+                ),
+            });
+            continue;
+        }
+
+        // Binary files (images, fonts, video) are only size-checked.
+        let Ok(bytes) = fs::read(&path) else { continue };
+        if bytes.iter().take(8192).any(|&b| b == 0) {
+            continue;
+        }
+        let text = String::from_utf8_lossy(&bytes);
+        findings.extend(scan_text(rel, &text, settings.entropy));
+    }
+    Ok(findings)
+}
+
+/// Run a git command listing NUL-separated paths and return its output.
+fn git_paths(repo_path: &str, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(repo_path)
+        .output()
+        .map_err(|e| format!("Failed to run git {}: {}", args[0], e))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("git {} failed: {}", args[0], stderr.trim()));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// File names that almost always hold credentials.
+fn sensitive_file_name(rel: &str) -> Option<&'static str> {
+    let name = rel.rsplit('/').next().unwrap_or(rel).to_lowercase();
+    let is_env = name == ".env"
+        || (name.starts_with(".env.")
+            && !matches!(
+                name.as_str(),
+                ".env.example" | ".env.sample" | ".env.template"
+            ));
+    if is_env {
+        return Some("environment file");
+    }
+    if matches!(
+        name.as_str(),
+        "id_rsa" | "id_dsa" | "id_ecdsa" | "id_ed25519" | ".npmrc" | ".netrc" | ".pypirc"
+    ) || name.starts_with("credentials")
+    {
+        return Some("credentials file");
+    }
+    let ext = name.rsplit_once('.').map(|(_, ext)| ext).unwrap_or("");
+    if matches!(ext, "pem" | "key" | "p12" | "pfx" | "keystore" | "jks") {
+        return Some("key or certificate file");
+    }
+    None
+}
+
+/// Look for known secret formats and random-looking tokens, line by line.
+fn scan_text(rel: &str, text: &str, entropy: bool) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    for (idx, line) in text.lines().enumerate() {
+        let mut push = |detail: String| {
+            findings.push(Finding {
+                path: rel.to_string(),
+                line: Some(idx + 1),
+                detail,
+            })
+        };
+
+        if let Some((name, _)) = SECRET_PATTERNS.iter().find(|(_, re)| re.is_match(line)) {
+            push(name.to_string());
+            continue;
+        }
+        if let Some(value) = ASSIGNMENT_RE.captures(line).and_then(|c| c.get(1)) {
+            if looks_random(value.as_str(), 3.5) {
+                push("hard-coded credential".to_string());
+                continue;
+            }
+        }
+        // Inline data URIs are legitimately long and random.
+        if entropy
+            && !line.contains(";base64,")
+            && TOKEN_RE
+                .find_iter(line)
+                .any(|m| looks_random(m.as_str(), ENTROPY_THRESHOLD))
+        {
+            push("high-entropy token".to_string());
+        }
+    }
+    findings
+}
+
+/// Mixed-case, digit-bearing strings whose entropy clears `threshold`.
+/// Plain words, kebab-case slugs and paths rarely satisfy all three.
+fn looks_random(s: &str, threshold: f64) -> bool {
+    s.chars().any(|c| c.is_ascii_digit())
+        && s.chars().any(|c| c.is_ascii_uppercase())
+        && s.chars().any(|c| c.is_ascii_lowercase())
+        && shannon_entropy(s) >= threshold
+}
+
+fn shannon_entropy(s: &str) -> f64 {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for c in s.chars() {
+        *counts.entry(c).or_default() += 1;
+    }
+    let len = s.chars().count() as f64;
+    counts
+        .values()
+        .map(|&n| {
+            let p = n as f64 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Human-readable summary; findings arrive grouped by file.
+fn report(findings: &[Finding]) -> String {
+    let mut out = format!("{BLOCKED}:\n");
+    let mut shown = 0;
+    for (i, f) in findings.iter().enumerate() {
+        shown = if i > 0 && findings[i - 1].path == f.path {
+            shown + 1
+        } else {
+            1
+        };
+        if shown <= MAX_FINDINGS_PER_FILE {
+            match f.line {
+                Some(line) => out.push_str(&format!("• {}:{} — {}\n", f.path, line, f.detail)),
+                None => out.push_str(&format!("• {} — {}\n", f.path, f.detail)),
+            }
+        }
+        let last_of_file = findings.get(i + 1).is_none_or(|next| next.path != f.path);
+        if last_of_file && shown > MAX_FINDINGS_PER_FILE {
+            out.push_str(&format!(
+                "• {} — {} more\n",
+                f.path,
+                shown - MAX_FINDINGS_PER_FILE
+            ));
+        }
+    }
+    out.push_str("Remove or ignore these files, or publish anyway to override.");
+    out
+}
@@ -0,0 +1,206 @@
+//! Watch scopes — matching repo files that affect the site build to the
+//! channel they are reported on, and the directories to watch for them.
+//!
+//! Patterns are globs over repo-relative, `/`-separated paths (see
+//! `WatchScope`). Each is watched from its literal leading directory:
+//! recursively if anything after it can span directories, otherwise just that
+//! directory, so top-level files don't put `node_modules` under watch.
+
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+use crate::collections;
+use crate::types::{WatchChannel, WatchScope, WatchSettings};
+
+/// Compiled scopes, in configured order.
+pub struct Scopes {
+    patterns: Vec<(WatchChannel, Regex)>,
+}
+
+impl Scopes {
+    pub fn new(settings: &WatchSettings) -> Self {
+        let patterns = settings
+            .scopes
+            .iter()
+            .flat_map(|scope| scope.paths.iter().map(|p| (scope.channel, glob_regex(p))))
+            .collect();
+        Self { patterns }
+    }
+
+    /// The channel of the first scope matching `rel_path`.
+    pub fn channel_of(&self, rel_path: &str) -> Option<WatchChannel> {
+        self.patterns
+            .iter()
+            .find(|(_, re)| re.is_match(rel_path))
+            .map(|(channel, _)| *channel)
+    }
+}
+
+/// Reject patterns that leave the repository or would watch it recursively from the root.
+pub fn validate(scope: &WatchScope) -> Result<(), String> {
+    for pattern in &scope.paths {
+        let first = pattern.split('/').next().unwrap_or_default();
+        let deep = has_wildcard(first) && (pattern.contains('/') || first.contains("**"));
+        if !collections::is_relative_inside(pattern) || deep {
+            return Err(format!(
+                "watch scope path \"{pattern}\" must be relative to the repo \
+                 and start with a literal directory"
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Directories to watch for `settings`, relative to the repo root, with
+/// whether each is watched recursively. A directory needed both ways is
+/// listed once, recursively.
+pub fn watch_dirs(settings: &WatchSettings) -> Vec<(PathBuf, bool)> {
+    let mut dirs: Vec<(PathBuf, bool)> = Vec::new();
+    for pattern in settings.scopes.iter().flat_map(|s| &s.paths) {
+        let segments: Vec<&str> = pattern.split('/').collect();
+        let literal = segments.iter().take_while(|s| !has_wildcard(s)).count();
+        // Everything literal: a single file, watched through its directory.
+        let (dir, recursive) = if literal == segments.len() {
+            (&segments[..literal - 1], false)
+        } else {
+            (
+                &segments[..literal],
+                literal + 1 < segments.len() || segments[literal] == "**",
+            )
+        };
+        let dir: PathBuf = dir.iter().collect();
+        match dirs.iter_mut().find(|(d, _)| *d == dir) {
+            Some((_, r)) => *r |= recursive,
+            None => dirs.push((dir, recursive)),
+        }
+    }
+    dirs
+}
+
+/// `path` relative to `base` in the `/`-separated form scopes match against.
+pub fn rel_path(base: &Path, path: &Path) -> Option<String> {
+    let rel = path.strip_prefix(base).ok()?;
+    let parts: Vec<String> = rel
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+    (!parts.is_empty()).then(|| parts.join("/"))
+}
+
+fn has_wildcard(segment: &str) -> bool {
+    segment.contains(['*', '?'])
+}
+
+fn glob_regex(pattern: &str) -> Regex {
+    let segments: Vec<&str> = pattern.split('/').collect();
+    let mut re = String::from("^");
+    for (i, segment) in segments.iter().enumerate() {
+        let last = i + 1 == segments.len();
+        if *segment == "**" {
+            re.push_str(if last { ".*" } else { "(?:[^/]+/)*" });
+            continue;
+        }
+        for c in segment.chars() {
+            match c {
+                '*' => re.push_str("[^/]*"),
+                '?' => re.push_str("[^/]"),
+                c => re.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+        if !last {
+            re.push('/');
+        }
+    }
+    re.push('$');
+    // Every metacharacter is escaped above, so the pattern always compiles.
+    Regex::new(&re).expect("glob pattern")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(paths: &[&str]) -> WatchSettings {
+        WatchSettings {
+            scopes: vec![WatchScope {
+                channel: WatchChannel::Components,
+                paths: paths.iter().map(|p| p.to_string()).collect(),
+            }],
+            respect_gitignore: true,
+        }
+    }
+
+    #[test]
+    fn single_star_stays_in_one_segment() {
+        let re = glob_regex("astro.config.*");
+        assert!(re.is_match("astro.config.mjs"));
+        assert!(!re.is_match("astro.config.d/x.mjs"));
+        assert!(!re.is_match("astroXconfig.mjs"));
+        assert!(!re.is_match("src/astro.config.mjs"));
+    }
+
+    #[test]
+    fn double_star_spans_directories() {
+        let re = glob_regex("src/components/**");
+        assert!(re.is_match("src/components/Card.astro"));
+        assert!(re.is_match("src/components/ui/deep/Button.tsx"));
+        assert!(!re.is_match("src/componentsX/Card.astro"));
+
+        let re = glob_regex("src/**/config.ts");
+        assert!(re.is_match("src/config.ts"));
+        assert!(re.is_match("src/a/b/config.ts"));
+        assert!(!re.is_match("src/a/b/config.tsx"));
+    }
+
+    #[test]
+    fn question_mark_matches_one_character() {
+        let re = glob_regex("bun.lock?");
+        assert!(re.is_match("bun.lockb"));
+        assert!(!re.is_match("bun.lock"));
+        assert!(!re.is_match("bun.lock/x"));
+    }
+
+    #[test]
+    fn regex_metacharacters_are_literal() {
+        let re = glob_regex("src/(group)/[id]+.astro");
+        assert!(re.is_match("src/(group)/[id]+.astro"));
+        assert!(!re.is_match("src/group/i.astro"));
+    }
+
+    #[test]
+    fn top_level_files_watch_the_root_non_recursively() {
+        let dirs = watch_dirs(&settings(&["package.json", "astro.config.*"]));
+        assert_eq!(dirs, [(PathBuf::new(), false)]);
+    }
+
+    #[test]
+    fn watched_dirs_are_merged_and_recursive_when_needed() {
+        let dirs = watch_dirs(&settings(&[
+            "src/content.config.*",
+            "src/components/**",
+            "src/layouts/*.astro",
+            "src/layouts/*/x.astro",
+        ]));
+        assert_eq!(
+            dirs,
+            [
+                (PathBuf::from("src"), false),
+                (PathBuf::from("src/components"), true),
+                (PathBuf::from("src/layouts"), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn scopes_must_start_with_a_literal_directory() {
+        let scope = |p: &str| WatchScope {
+            channel: WatchChannel::Components,
+            paths: vec![p.to_string()],
+        };
+        assert!(validate(&scope("src/**")).is_ok());
+        assert!(validate(&scope("*.json")).is_ok());
+        assert!(validate(&scope("**")).is_err());
+        assert!(validate(&scope("*/x.ts")).is_err());
+        assert!(validate(&scope("../outside/*")).is_err());
+    }
+}
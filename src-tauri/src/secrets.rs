@@ -0,0 +1,504 @@
+//! Encrypted secret storage — credentials kept out of `config.json`.
+//!
+//! Secrets are sealed with ChaCha20-Poly1305 under a 256-bit key that comes
+//! either from a locally generated key file (the default, owner-only
+//! permissions) or from a user passphrase via PBKDF2-HMAC-SHA256. A passphrase
+//! key is never written to disk; it lives in `SecretsState` once unlocked.
+//! Plaintext secrets never cross the IPC boundary.
+
+use base64::{engine::general_purpose::STANDARD as B64, Engine};
+use chacha20poly1305::aead::{Aead, KeyInit, Payload};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tauri::Manager;
+
+use crate::error::{AppError, ErrorCode};
+use crate::types::{KeySource, SecretsStatus};
+
+/// Secret name of the Cloudflare API token (one per profile).
+pub const CF_API_TOKEN: &str = "cf_api_token";
+/// Placeholder returned to the webview in place of a stored secret.
+pub const MASKED: &str = "••••••••";
+
+const PBKDF2_ROUNDS: u32 = 600_000;
+/// Known plaintext sealed under a passphrase key, used to verify the passphrase on unlock.
+const CHECK_ID: &str = "__check__";
+const CHECK_PLAINTEXT: &[u8] = b"fpl0-panel";
+
+type SecretKey = [u8; 32];
+
+/// In-memory key derived from the user's passphrase, if unlocked.
+pub struct SecretsState(Mutex<Option<SecretKey>>);
+
+impl SecretsState {
+    pub fn new() -> Self {
+        Self(Mutex::new(None))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SealedSecret {
+    nonce: String,
+    ciphertext: String,
+}
+
+/// The secrets file as stored on disk. Entries are keyed by `profile/name`.
+#[derive(Debug, Serialize, Deserialize)]
+struct SecretsFile {
+    key_source: KeySource,
+    salt: Option<String>,
+    check: Option<SealedSecret>,
+    entries: BTreeMap<String, SealedSecret>,
+}
+
+impl Default for SecretsFile {
+    fn default() -> Self {
+        Self {
+            key_source: KeySource::Keyfile,
+            salt: None,
+            check: None,
+            entries: BTreeMap::new(),
+        }
+    }
+}
+
+fn data_dir(app: &tauri::AppHandle) -> PathBuf {
+    app.path()
+        .app_data_dir()
+        .unwrap_or_else(|_| PathBuf::from("."))
+}
+
+fn secrets_path(app: &tauri::AppHandle) -> PathBuf {
+    data_dir(app).join("secrets.json")
+}
+
+fn keyfile_path(app: &tauri::AppHandle) -> PathBuf {
+    data_dir(app).join("secrets.key")
+}
+
+/// The key file `rekey` replaces, kept until the entries re-sealed under the
+/// new key are saved.
+fn old_keyfile_path(app: &tauri::AppHandle) -> PathBuf {
+    data_dir(app).join("secrets.key.old")
+}
+
+fn entry_id(profile: &str, name: &str) -> String {
+    format!("{profile}/{name}")
+}
+
+/// Write a file readable only by the current user, via temp file + rename.
+/// The temp file is named after the whole file name (as in
+/// `files::write_atomic`), so the key and the store never share one.
+fn write_private(path: &Path, data: &[u8]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create secrets directory: {e}"))?;
+    }
+    let name = path
+        .file_name()
+        .ok_or_else(|| format!("Invalid path: {}", path.display()))?;
+    let tmp = path.with_file_name(format!(".{}.tmp", name.to_string_lossy()));
+    // A leftover temp file would keep its permissions; start from a fresh one.
+    let _ = fs::remove_file(&tmp);
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let result = options.open(&tmp).and_then(|mut file| {
+        file.write_all(data)?;
+        file.sync_all()?;
+        fs::rename(&tmp, path)
+    });
+    result.map_err(|e| {
+        let _ = fs::remove_file(&tmp);
+        format!("Failed to write {}: {e}", path.display())
+    })
+}
+
+fn random_bytes<const N: usize>() -> Result<[u8; N], String> {
+    let mut buf = [0u8; N];
+    getrandom::getrandom(&mut buf).map_err(|e| format!("Failed to generate random bytes: {e}"))?;
+    Ok(buf)
+}
+
+fn derive_key(passphrase: &str, salt: &[u8]) -> SecretKey {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(passphrase.as_bytes(), salt, PBKDF2_ROUNDS, &mut key);
+    key
+}
+
+/// Encrypt `plaintext`, binding it to `id` as associated data so entries can't be swapped.
+fn seal(key: &SecretKey, id: &str, plaintext: &[u8]) -> Result<SealedSecret, String> {
+    let nonce = random_bytes::<12>()?;
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(key));
+    let ciphertext = cipher
+        .encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: plaintext,
+                aad: id.as_bytes(),
+            },
+        )
+        .map_err(|_| "Failed to encrypt secret".to_string())?;
+    Ok(SealedSecret {
+        nonce: B64.encode(nonce),
+        ciphertext: B64.encode(ciphertext),
+    })
+}
+
+fn open(key: &SecretKey, id: &str, sealed: &SealedSecret) -> Result<Vec<u8>, String> {
+    let nonce = B64
+        .decode(&sealed.nonce)
+        .map_err(|_| "Corrupt secret nonce".to_string())?;
+    let ciphertext = B64
+        .decode(&sealed.ciphertext)
+        .map_err(|_| "Corrupt secret ciphertext".to_string())?;
+    if nonce.len() != 12 {
+        return Err("Corrupt secret nonce".to_string());
+    }
+    let cipher = ChaCha20Poly1305::new(Key::from_slice(key));
+    cipher
+        .decrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: &ciphertext,
+                aad: id.as_bytes(),
+            },
+        )
+        .map_err(|_| "Failed to decrypt secret (wrong key or tampered data)".to_string())
+}
+
+fn load_file(app: &tauri::AppHandle) -> Result<SecretsFile, String> {
+    match fs::read_to_string(secrets_path(app)) {
+        Ok(data) => {
+            serde_json::from_str(&data).map_err(|e| format!("Secrets file is unreadable: {e}"))
+        }
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(SecretsFile::default()),
+        Err(e) => Err(format!("Failed to read secrets file: {e}")),
+    }
+}
+
+fn save_file(app: &tauri::AppHandle, file: &SecretsFile) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(file)
+        .map_err(|e| format!("Failed to serialize secrets: {e}"))?;
+    write_private(&secrets_path(app), data.as_bytes())
+}
+
+/// Settle a `rekey` that stopped between replacing the key file and saving the
+/// entries: keep whichever key file opens the stored entries.
+fn recover_keyfile(app: &tauri::AppHandle) -> Result<(), String> {
+    let old_path = old_keyfile_path(app);
+    let Ok(old) = fs::read(&old_path) else {
+        return Ok(());
+    };
+    let path = keyfile_path(app);
+    let file = load_file(app)?;
+    let opens = |key: &[u8]| {
+        SecretKey::try_from(key).is_ok_and(|key| {
+            file.entries
+                .iter()
+                .next()
+                .is_none_or(|(id, sealed)| open(&key, id, sealed).is_ok())
+        })
+    };
+    let current_opens = fs::read(&path).is_ok_and(|key| opens(&key));
+    if file.key_source == KeySource::Keyfile && !current_opens && opens(&old) {
+        fs::rename(&old_path, &path)
+            .map_err(|e| format!("Failed to restore {}: {e}", path.display()))
+    } else {
+        fs::remove_file(&old_path)
+            .map_err(|e| format!("Failed to remove {}: {e}", old_path.display()))
+    }
+}
+
+/// Read the key file, generating one on first use.
+fn keyfile_key(app: &tauri::AppHandle) -> Result<SecretKey, String> {
+    recover_keyfile(app)?;
+    let path = keyfile_path(app);
+    match fs::read(&path) {
+        Ok(bytes) => bytes
+            .try_into()
+            .map_err(|_| format!("Key file {} is corrupt", path.display())),
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            let key = random_bytes::<32>()?;
+            write_private(&path, &key)?;
+            Ok(key)
+        }
+        Err(e) => Err(format!("Failed to read key file: {e}")),
+    }
+}
+
+/// Resolve the key that currently seals `file`'s entries. Fails with
+/// `ErrorCode::SecretsLocked` while the passphrase hasn't been entered.
+fn current_key(app: &tauri::AppHandle, file: &SecretsFile) -> Result<SecretKey, AppError> {
+    match file.key_source {
+        KeySource::Keyfile => Ok(keyfile_key(app)?),
+        KeySource::Passphrase => app
+            .state::<SecretsState>()
+            .0
+            .lock()
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| {
+                AppError::new(
+                    ErrorCode::SecretsLocked,
+                    "Secrets are locked. Enter your passphrase to unlock them.",
+                )
+            }),
+    }
+}
+
+/// Read a secret. `Ok(None)` if it was never stored; a locked store fails with
+/// `ErrorCode::SecretsLocked` and a secret that won't decrypt with `Internal`.
+pub fn get(app: &tauri::AppHandle, profile: &str, name: &str) -> Result<Option<String>, AppError> {
+    let file = load_file(app)?;
+    let id = entry_id(profile, name);
+    let Some(sealed) = file.entries.get(&id) else {
+        return Ok(None);
+    };
+    let key = current_key(app, &file)?;
+    let plaintext = open(&key, &id, sealed)?;
+    String::from_utf8(plaintext)
+        .map(Some)
+        .map_err(|_| AppError::from("Secret is not valid UTF-8"))
+}
+
+/// Whether a secret is stored, without decrypting it.
+pub fn has(app: &tauri::AppHandle, profile: &str, name: &str) -> bool {
+    load_file(app)
+        .map(|file| file.entries.contains_key(&entry_id(profile, name)))
+        .unwrap_or(false)
+}
+
+/// Store (or replace) a secret.
+pub fn set(app: &tauri::AppHandle, profile: &str, name: &str, value: &str) -> Result<(), String> {
+    let mut file = load_file(app)?;
+    let key = current_key(app, &file).map_err(|e| e.message)?;
+    let id = entry_id(profile, name);
+    let sealed = seal(&key, &id, value.as_bytes())?;
+    file.entries.insert(id, sealed);
+    save_file(app, &file)
+}
+
+/// Remove a secret. Removing a missing secret is not an error.
+pub fn remove(app: &tauri::AppHandle, profile: &str, name: &str) -> Result<(), String> {
+    let mut file = load_file(app)?;
+    if file.entries.remove(&entry_id(profile, name)).is_some() {
+        save_file(app, &file)?;
+    }
+    Ok(())
+}
+
+/// Remove every secret belonging to a profile.
+pub fn remove_profile(app: &tauri::AppHandle, profile: &str) -> Result<(), String> {
+    let mut file = load_file(app)?;
+    let prefix = format!("{profile}/");
+    let before = file.entries.len();
+    file.entries.retain(|id, _| !id.starts_with(&prefix));
+    if file.entries.len() != before {
+        save_file(app, &file)?;
+    }
+    Ok(())
+}
+
+/// Unlock a passphrase-protected store for the rest of the session.
+pub fn unlock(app: &tauri::AppHandle, passphrase: &str) -> Result<(), String> {
+    let file = load_file(app)?;
+    if file.key_source != KeySource::Passphrase {
+        return Ok(());
+    }
+    let salt = file
+        .salt
+        .as_deref()
+        .and_then(|s| B64.decode(s).ok())
+        .ok_or("Secrets file is missing its salt")?;
+    let check = file
+        .check
+        .as_ref()
+        .ok_or("Secrets file is missing its check value")?;
+    let key = derive_key(passphrase, &salt);
+    if open(&key, CHECK_ID, check).ok().as_deref() != Some(CHECK_PLAINTEXT) {
+        return Err("Incorrect passphrase.".to_string());
+    }
+    *app.state::<SecretsState>()
+        .0
+        .lock()
+        .map_err(|e| e.to_string())? = Some(key);
+    Ok(())
+}
+
+/// Re-encrypt every secret under fresh key material: a new passphrase-derived key
+/// (new salt) when `passphrase` is given, otherwise a newly generated key file.
+/// The store must be unlocked if it is currently passphrase-protected.
+pub fn rekey(app: &tauri::AppHandle, passphrase: Option<&str>) -> Result<(), String> {
+    let mut file = load_file(app)?;
+
+    let mut plaintexts: Vec<(String, Vec<u8>)> = Vec::new();
+    let mut old_keyfile_key = None;
+    if !file.entries.is_empty() {
+        let old_key = current_key(app, &file).map_err(|e| e.message)?;
+        for (id, sealed) in &file.entries {
+            plaintexts.push((id.clone(), open(&old_key, id, sealed)?));
+        }
+        if file.key_source == KeySource::Keyfile {
+            old_keyfile_key = Some(old_key);
+        }
+    }
+
+    let new_key = match passphrase {
+        Some(passphrase) => {
+            if passphrase.is_empty() {
+                return Err("Passphrase cannot be empty.".to_string());
+            }
+            let salt = random_bytes::<16>()?;
+            let key = derive_key(passphrase, &salt);
+            file.key_source = KeySource::Passphrase;
+            file.salt = Some(B64.encode(salt));
+            file.check = Some(seal(&key, CHECK_ID, CHECK_PLAINTEXT)?);
+            key
+        }
+        None => {
+            file.key_source = KeySource::Keyfile;
+            file.salt = None;
+            file.check = None;
+            random_bytes::<32>()?
+        }
+    };
+
+    file.entries = plaintexts
+        .into_iter()
+        .map(|(id, plaintext)| seal(&new_key, &id, &plaintext).map(|sealed| (id, sealed)))
+        .collect::<Result<_, _>>()?;
+
+    // The stored entries stay sealed under the old key until `save_file`
+    // replaces them, so the old key file is kept until then. If that never
+    // happens, `recover_keyfile` puts it back.
+    if file.key_source == KeySource::Keyfile {
+        if let Some(old_key) = old_keyfile_key {
+            write_private(&old_keyfile_path(app), &old_key)?;
+        }
+        write_private(&keyfile_path(app), &new_key)?;
+    }
+    if let Err(e) = save_file(app, &file) {
+        let _ = recover_keyfile(app);
+        return Err(e);
+    }
+    match file.key_source {
+        KeySource::Keyfile => {
+            let _ = fs::remove_file(old_keyfile_path(app));
+        }
+        KeySource::Passphrase => {
+            let _ = fs::remove_file(keyfile_path(app));
+        }
+    }
+
+    *app.state::<SecretsState>()
+        .0
+        .lock()
+        .map_err(|e| e.to_string())? =
+        (file.key_source == KeySource::Passphrase).then_some(new_key);
+    Ok(())
+}
+
+/// Describe how the store is keyed and whether it can be read right now.
+pub fn status(app: &tauri::AppHandle) -> Result<SecretsStatus, String> {
+    let file = load_file(app)?;
+    let unlocked = match file.key_source {
+        KeySource::Keyfile => true,
+        KeySource::Passphrase => app
+            .state::<SecretsState>()
+            .0
+            .lock()
+            .map_err(|e| e.to_string())?
+            .is_some(),
+    };
+    Ok(SecretsStatus {
+        key_source: file.key_source,
+        unlocked,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("panel-secrets-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn sealed_secret_opens_with_its_key_and_id() {
+        let key = [7u8; 32];
+        let sealed = seal(&key, "default/cf_api_token", b"token").unwrap();
+        assert_eq!(
+            open(&key, "default/cf_api_token", &sealed).unwrap(),
+            b"token"
+        );
+    }
+
+    #[test]
+    fn wrong_key_or_id_fails_to_open() {
+        let key = [7u8; 32];
+        let sealed = seal(&key, "default/cf_api_token", b"token").unwrap();
+        assert!(open(&[8u8; 32], "default/cf_api_token", &sealed).is_err());
+        // Entries are bound to their id, so they can't be swapped between profiles.
+        assert!(open(&key, "other/cf_api_token", &sealed).is_err());
+    }
+
+    #[test]
+    fn corrupt_sealed_secret_fails_to_open() {
+        let key = [7u8; 32];
+        let mut sealed = seal(&key, "id", b"token").unwrap();
+        sealed.nonce = B64.encode([0u8; 4]);
+        assert!(open(&key, "id", &sealed).is_err());
+        sealed.nonce = "not base64!".to_string();
+        assert!(open(&key, "id", &sealed).is_err());
+    }
+
+    #[test]
+    fn passphrase_key_depends_on_salt() {
+        assert_eq!(derive_key("pass", b"salt"), derive_key("pass", b"salt"));
+        assert_ne!(derive_key("pass", b"salt"), derive_key("pass", b"pepper"));
+        assert_ne!(derive_key("pass", b"salt"), derive_key("word", b"salt"));
+    }
+
+    #[test]
+    fn private_files_do_not_share_a_temp_file() {
+        let dir = temp_dir("write");
+        // A stale temp file from an interrupted write is replaced.
+        fs::write(dir.join(".secrets.json.tmp"), "stale").unwrap();
+        write_private(&dir.join("secrets.json"), b"store").unwrap();
+        write_private(&dir.join("secrets.key"), b"key").unwrap();
+
+        assert_eq!(fs::read(dir.join("secrets.json")).unwrap(), b"store");
+        assert_eq!(fs::read(dir.join("secrets.key")).unwrap(), b"key");
+        let mut names: Vec<String> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["secrets.json", "secrets.key"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn private_files_are_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir = temp_dir("mode");
+        let path = dir.join("secrets.key");
+        write_private(&path, b"key").unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
@@ -1,23 +1,330 @@
-//! Security utilities — path traversal prevention and YAML string escaping.
+//! Security utilities — path traversal prevention, SSRF checks and YAML string escaping.
+//!
+//! Every IPC command that touches the filesystem goes through this module:
+//! `repo_root` pins the webview-supplied repo path to the configured repository,
+//! `validate_slug` gates slugs before they become path segments, and
+//! `ensure_within` checks concrete (possibly not yet existing) paths, and
+//! `ensure_writable` additionally keeps raw writes out of `.git/` and `.panel.toml`.
+//! Outbound requests to user-supplied URLs use `resolve_public`.
 
+use std::ffi::OsString;
+use std::io::ErrorKind;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, ToSocketAddrs};
 use std::path::{Path, PathBuf};
 
+use crate::config;
+use crate::error::AppError;
+use crate::repo_config;
+
+/// Longest slug accepted as a path segment.
+const MAX_SLUG_LEN: usize = 128;
+
+/// Return the configured repository path, or an error if none is set.
+pub fn configured_repo(app: &tauri::AppHandle) -> Result<String, AppError> {
+    config::load_config(app)?
+        .repo_path
+        .filter(|p| !p.is_empty())
+        .ok_or_else(|| AppError::not_configured("No repository configured."))
+}
+
+/// Check that a repo path supplied by the webview is the configured repository.
+/// Returns the configured path so callers never act on an arbitrary directory.
+pub fn repo_root(app: &tauri::AppHandle, repo_path: &str) -> Result<String, AppError> {
+    let configured = configured_repo(app)?;
+    let same = match (
+        Path::new(&configured).canonicalize(),
+        Path::new(repo_path).canonicalize(),
+    ) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    };
+    if same {
+        Ok(configured)
+    } else {
+        Err(AppError::invalid_input("Path is not the configured repository").with_path(repo_path))
+    }
+}
+
+/// Reject slugs that could act as anything other than a single, visible path segment.
+pub fn validate_slug(slug: &str) -> Result<(), AppError> {
+    let valid = !slug.is_empty()
+        && slug.len() <= MAX_SLUG_LEN
+        && !slug.starts_with('.')
+        && slug
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::invalid_input(format!("Invalid slug \"{}\"", slug)).with_slug(slug))
+    }
+}
+
 /// Ensure `path` resolves to a location inside `base_dir`.
-pub fn ensure_within(path: &Path, base_dir: &Path) -> Result<PathBuf, String> {
-    let canonical = path
-        .canonicalize()
-        .map_err(|e| format!("Invalid path: {}", e))?;
+/// The path need not exist yet: its nearest existing ancestor is resolved
+/// (following symlinks) and the remaining components are appended as-is.
+pub fn ensure_within(path: &Path, base_dir: &Path) -> Result<PathBuf, AppError> {
+    let invalid = |message: String| AppError::invalid_input(message).with_path(path);
+    if !path.is_absolute() {
+        return Err(invalid("Invalid path: must be absolute".to_string()));
+    }
+    let canonical = canonicalize_partial(path).map_err(invalid)?;
     let base = base_dir
         .canonicalize()
-        .map_err(|e| format!("Invalid base path: {}", e))?;
+        .map_err(|e| invalid(format!("Invalid base path: {}", e)))?;
     if canonical.starts_with(&base) {
         Ok(canonical)
     } else {
-        Err("Path escapes the repository directory".to_string())
+        Err(invalid("Path escapes the repository directory".to_string()))
+    }
+}
+
+/// `ensure_within` for paths the webview writes to directly: also rejects
+/// anything under `.git/` and the repo's `.panel.toml`, which are only changed
+/// through git and the settings commands. Compared case-insensitively, since
+/// the repository may live on a case-insensitive filesystem.
+pub fn ensure_writable(path: &Path, base_dir: &Path) -> Result<PathBuf, AppError> {
+    let canonical = ensure_within(path, base_dir)?;
+    let base = base_dir.canonicalize().map_err(|e| {
+        AppError::invalid_input(format!("Invalid base path: {}", e)).with_path(path)
+    })?;
+    let rel = canonical.strip_prefix(&base).unwrap_or(&canonical);
+    let is = |name: Option<std::path::Component>, target: &str| {
+        name.is_some_and(|c| c.as_os_str().eq_ignore_ascii_case(target))
+    };
+    let mut components = rel.components();
+    let first = components.next();
+    let protected =
+        is(first, ".git") || (is(first, repo_config::FILE_NAME) && components.next().is_none());
+    if protected {
+        return Err(
+            AppError::invalid_input("This file can't be edited from the panel").with_path(path),
+        );
+    }
+    Ok(canonical)
+}
+
+/// Canonicalize the longest existing prefix of `path` and re-append the rest.
+/// `..` or a dangling symlink in the missing part can't be resolved safely and is rejected.
+fn canonicalize_partial(path: &Path) -> Result<PathBuf, String> {
+    let mut existing = path.to_path_buf();
+    let mut missing: Vec<OsString> = Vec::new();
+    loop {
+        match existing.canonicalize() {
+            Ok(mut resolved) => {
+                resolved.extend(missing.iter().rev());
+                return Ok(resolved);
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                if existing.symlink_metadata().is_ok() {
+                    return Err("Invalid path: dangling symlink".to_string());
+                }
+                let name = existing
+                    .file_name()
+                    .ok_or_else(|| format!("Invalid path: {}", e))?;
+                missing.push(name.to_os_string());
+                existing.pop();
+            }
+            Err(e) => return Err(format!("Invalid path: {}", e)),
+        }
+    }
+}
+
+/// Whether a URL points at the panel's own dev server: plain HTTP on a loopback
+/// host at the configured dev port.
+pub fn is_dev_server_url(url: &reqwest::Url, dev_port: u16) -> bool {
+    let loopback_host = match url.host_str() {
+        Some("localhost") => true,
+        Some(host) => parse_ip_host(host).is_some_and(|ip| ip.is_loopback()),
+        None => false,
+    };
+    url.scheme() == "http" && loopback_host && url.port_or_known_default() == Some(dev_port)
+}
+
+/// Resolve a URL's host and require every address to be publicly routable.
+/// Returns the addresses so the caller can connect to exactly what was checked
+/// (a second lookup could be answered differently — DNS rebinding).
+///
+/// IP literals arrive already normalized: the URL parser turns decimal, octal
+/// and hex IPv4 forms (`http://2130706433/`, `http://0x7f.1/`) into dotted quads.
+pub async fn resolve_public(url: &reqwest::Url) -> Result<Vec<SocketAddr>, String> {
+    let host = url.host_str().ok_or("URL has no host")?.to_string();
+    let port = url.port_or_known_default().ok_or("URL has no port")?;
+
+    let addrs: Vec<SocketAddr> = match parse_ip_host(&host) {
+        Some(ip) => vec![SocketAddr::new(ip, port)],
+        None => {
+            let target = (host.clone(), port);
+            tauri::async_runtime::spawn_blocking(move || {
+                target.to_socket_addrs().map(|addrs| addrs.collect())
+            })
+            .await
+            .map_err(|e| e.to_string())?
+            .map_err(|e| format!("Could not resolve {}: {}", host, e))?
+        }
+    };
+
+    if addrs.is_empty() || addrs.iter().any(|a| !is_public_ip(a.ip())) {
+        return Err("Requests to local/private addresses are not allowed".to_string());
+    }
+    Ok(addrs)
+}
+
+/// Parse a URL host as an IP address (IPv6 hosts come bracketed).
+fn parse_ip_host(host: &str) -> Option<IpAddr> {
+    host.trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse()
+        .ok()
+}
+
+/// Whether an address is globally routable — not loopback, private, link-local,
+/// shared (CGNAT), multicast, documentation or otherwise reserved.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => is_public_v4(v4),
+        IpAddr::V6(v6) => is_public_v6(v6),
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private() // 10/8, 172.16/12, 192.168/16
+        || ip.is_link_local() // 169.254/16
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        || a == 0 // "this network"
+        || (a == 100 && (64..128).contains(&b)) // shared address space 100.64/10
+        || (a == 192 && b == 0 && c == 0) // IETF protocol assignments
+        || (a == 198 && (b == 18 || b == 19)) // benchmarking 198.18/15
+        || a >= 240) // reserved
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    // IPv4-mapped (::ffff:a.b.c.d) and NAT64 (64:ff9b::a.b.c.d) reach IPv4 hosts.
+    if let Some(v4) = ip.to_ipv4_mapped() {
+        return is_public_v4(v4);
     }
+    let seg = ip.segments();
+    if seg[0] == 0x64 && seg[1] == 0xff9b && seg[2..6] == [0, 0, 0, 0] {
+        let [hi, lo] = [seg[6].to_be_bytes(), seg[7].to_be_bytes()];
+        return is_public_v4(Ipv4Addr::new(hi[0], hi[1], lo[0], lo[1]));
+    }
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_multicast()
+        || (seg[0] & 0xfe00) == 0xfc00 // unique local fc00::/7
+        || (seg[0] & 0xffc0) == 0xfe80 // link-local fe80::/10
+        || (seg[0] & 0xffc0) == 0xfec0 // site-local fec0::/10 (deprecated)
+        || (seg[0] == 0x2001 && seg[1] == 0x0db8) // documentation
+        || seg[..6] == [0, 0, 0, 0, 0, 0]) // IPv4-compatible and other ::/96
 }
 
 /// Escape a string for safe interpolation inside double-quoted YAML values.
 pub fn escape_yaml_string(s: &str) -> String {
     s.replace('\\', "\\\\").replace('"', "\\\"")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    /// A fresh directory under the system temp dir, canonicalized.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("panel-security-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir.canonicalize().unwrap()
+    }
+
+    #[test]
+    fn private_and_shared_v4_ranges_are_not_public() {
+        for ip in [
+            "172.16.0.1",
+            "172.31.255.255",
+            "100.64.0.1",
+            "100.127.255.255",
+        ] {
+            assert!(!is_public_v4(ip.parse().unwrap()), "{ip}");
+        }
+        for ip in [
+            "172.15.255.255",
+            "172.32.0.0",
+            "100.63.255.255",
+            "100.128.0.0",
+            "1.1.1.1",
+        ] {
+            assert!(is_public_v4(ip.parse().unwrap()), "{ip}");
+        }
+    }
+
+    #[test]
+    fn mapped_and_link_local_v6_are_not_public() {
+        for ip in [
+            "::ffff:127.0.0.1",
+            "::ffff:10.0.0.1",
+            "fe80::1",
+            "febf::1",
+            "64:ff9b::7f00:1",
+        ] {
+            assert!(!is_public_v6(ip.parse().unwrap()), "{ip}");
+        }
+        for ip in ["::ffff:1.1.1.1", "fe7f::1", "2606:4700::1111"] {
+            assert!(is_public_v6(ip.parse().unwrap()), "{ip}");
+        }
+    }
+
+    #[test]
+    fn missing_components_are_appended() {
+        let base = temp_dir("missing");
+        let path = base.join("new/dir/file.md");
+        assert_eq!(ensure_within(&path, &base).unwrap(), path);
+    }
+
+    #[test]
+    fn parent_dir_through_missing_dir_is_rejected() {
+        let base = temp_dir("dotdot");
+        assert!(canonicalize_partial(&base.join("missing/../../escaped")).is_err());
+        assert!(ensure_within(&base.join("missing/../../escaped"), &base).is_err());
+    }
+
+    #[test]
+    fn git_dir_and_panel_config_are_not_writable() {
+        let base = temp_dir("writable");
+        for rel in [
+            ".git/config",
+            ".git/hooks/pre-commit",
+            ".GIT/HEAD",
+            ".panel.toml",
+        ] {
+            assert!(ensure_writable(&base.join(rel), &base).is_err(), "{rel}");
+        }
+        for rel in ["content/blog/post.md", ".gitignore", "docs/.panel.toml"] {
+            assert!(ensure_writable(&base.join(rel), &base).is_ok(), "{rel}");
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlink_into_git_dir_is_not_writable() {
+        let base = temp_dir("writable-symlink");
+        fs::create_dir_all(base.join(".git/hooks")).unwrap();
+        std::os::unix::fs::symlink(base.join(".git/hooks"), base.join("hooks")).unwrap();
+        assert!(ensure_writable(&base.join("hooks/pre-commit"), &base).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlink_out_of_repo_is_rejected() {
+        let base = temp_dir("symlink");
+        let outside = temp_dir("symlink-outside");
+        std::os::unix::fs::symlink(&outside, base.join("link")).unwrap();
+        let err = ensure_within(&base.join("link/new.md"), &base).unwrap_err();
+        assert_eq!(err.message, "Path escapes the repository directory");
+    }
+}
@@ -0,0 +1,206 @@
+//! Autosave snapshots — a rolling local history of every content write,
+//! kept in the app data directory independently of git.
+//!
+//! Each write stores the full file (frontmatter and body) *before* it touches
+//! disk, so a crash mid-save leaves a snapshot newer than the file. Layout:
+//! `snapshots/<repo key>/<file key>/<unix millis>.json`, where the keys are
+//! short hashes of the repository path and the repo-relative file path.
+
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+use crate::files;
+use crate::frontmatter::calculate_content_hash;
+use crate::types::SnapshotInfo;
+
+/// Snapshots kept per file, newest first.
+const MAX_PER_FILE: usize = 50;
+/// Snapshots older than this are pruned (the newest one is always kept).
+const MAX_AGE_DAYS: i64 = 30;
+
+#[derive(Serialize, Deserialize)]
+struct Snapshot {
+    /// Repo-relative path of the snapshotted file.
+    path: String,
+    content: String,
+}
+
+fn snapshots_root(app: &tauri::AppHandle) -> PathBuf {
+    app.path()
+        .app_data_dir()
+        .unwrap_or_else(|_| PathBuf::from("."))
+        .join("snapshots")
+}
+
+/// Short, filesystem-safe key for a path.
+fn key(path: &str) -> String {
+    calculate_content_hash(path)[..16].to_string()
+}
+
+fn repo_dir(app: &tauri::AppHandle, repo_path: &str) -> PathBuf {
+    let canonical = Path::new(repo_path)
+        .canonicalize()
+        .unwrap_or_else(|_| PathBuf::from(repo_path));
+    snapshots_root(app).join(key(&canonical.to_string_lossy()))
+}
+
+fn rel_path(repo_path: &str, file: &Path) -> String {
+    file.strip_prefix(repo_path)
+        .unwrap_or(file)
+        .to_string_lossy()
+        .to_string()
+}
+
+fn file_dir(app: &tauri::AppHandle, repo_path: &str, file: &Path) -> PathBuf {
+    repo_dir(app, repo_path).join(key(&rel_path(repo_path, file)))
+}
+
+/// Snapshot ids (unix millis) in a file's directory, newest first.
+fn ids(dir: &Path) -> Vec<i64> {
+    let mut ids: Vec<i64> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|e| {
+            e.file_name()
+                .to_string_lossy()
+                .strip_suffix(".json")?
+                .parse()
+                .ok()
+        })
+        .collect();
+    ids.sort_unstable_by(|a, b| b.cmp(a));
+    ids
+}
+
+fn read(dir: &Path, id: i64) -> Result<Snapshot, String> {
+    let data = fs::read_to_string(dir.join(format!("{id}.json")))
+        .map_err(|e| format!("Failed to read snapshot {id}: {e}"))?;
+    serde_json::from_str(&data).map_err(|e| format!("Invalid snapshot {id}: {e}"))
+}
+
+fn info(repo_path: &str, dir: &Path, id: i64) -> Option<SnapshotInfo> {
+    let snapshot = read(dir, id).ok()?;
+    Some(SnapshotInfo {
+        id: id.to_string(),
+        path: Path::new(repo_path)
+            .join(&snapshot.path)
+            .to_string_lossy()
+            .to_string(),
+        taken_at: Utc.timestamp_millis_opt(id).single()?.to_rfc3339(),
+        size: snapshot.content.len() as u64,
+    })
+}
+
+/// Store `content` as the newest snapshot of `file`, unless it matches the
+/// newest one already, then apply retention.
+pub fn record(
+    app: &tauri::AppHandle,
+    repo_path: &str,
+    file: &Path,
+    content: &str,
+) -> Result<(), String> {
+    let dir = file_dir(app, repo_path, file);
+    let existing = ids(&dir);
+    if let Some(&latest) = existing.first() {
+        if read(&dir, latest).is_ok_and(|s| s.content == content) {
+            return Ok(());
+        }
+    }
+
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create snapshot directory: {e}"))?;
+    // Two writes within a millisecond still get distinct, ordered ids.
+    let id = Utc::now()
+        .timestamp_millis()
+        .max(existing.first().map_or(0, |l| l + 1));
+    let data = serde_json::to_string(&Snapshot {
+        path: rel_path(repo_path, file),
+        content: content.to_string(),
+    })
+    .map_err(|e| format!("Failed to serialize snapshot: {e}"))?;
+    files::write_atomic(&dir.join(format!("{id}.json")), data.as_bytes())?;
+
+    prune(&dir);
+    Ok(())
+}
+
+/// Keep the newest `MAX_PER_FILE` snapshots, dropping any past `MAX_AGE_DAYS`
+/// except the newest.
+fn prune(dir: &Path) {
+    let cutoff = Utc::now().timestamp_millis() - MAX_AGE_DAYS * 24 * 60 * 60 * 1000;
+    for (i, id) in ids(dir).into_iter().enumerate() {
+        if i >= MAX_PER_FILE || (i > 0 && id < cutoff) {
+            let _ = fs::remove_file(dir.join(format!("{id}.json")));
+        }
+    }
+}
+
+/// Snapshots of a file, newest first.
+pub fn list(app: &tauri::AppHandle, repo_path: &str, file: &Path) -> Vec<SnapshotInfo> {
+    let dir = file_dir(app, repo_path, file);
+    ids(&dir)
+        .into_iter()
+        .filter_map(|id| info(repo_path, &dir, id))
+        .collect()
+}
+
+/// Write a snapshot back to its file. The current contents are snapshotted
+/// first, so a restore can itself be undone.
+pub fn restore(
+    app: &tauri::AppHandle,
+    repo_path: &str,
+    file: &Path,
+    id: &str,
+) -> Result<(), String> {
+    let id: i64 = id
+        .parse()
+        .map_err(|_| format!("Invalid snapshot id \"{id}\""))?;
+    let snapshot = read(&file_dir(app, repo_path, file), id)?;
+    if let Ok(current) = fs::read_to_string(file) {
+        record(app, repo_path, file, &current)?;
+    }
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {e}"))?;
+    }
+    files::write_text(file, &snapshot.content)
+}
+
+/// Files whose newest snapshot is newer than, and differs from, the file on
+/// disk — typically a save interrupted by a crash.
+pub fn recoverable(app: &tauri::AppHandle, repo_path: &str) -> Vec<SnapshotInfo> {
+    let mut found = Vec::new();
+    for dir in fs::read_dir(repo_dir(app, repo_path))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+    {
+        let Some(&latest) = ids(&dir).first() else {
+            continue;
+        };
+        let Ok(snapshot) = read(&dir, latest) else {
+            continue;
+        };
+        // Deleted files are not flagged: removal is deliberate (panel delete, git pull).
+        let file = Path::new(repo_path).join(&snapshot.path);
+        let Ok(modified) = fs::metadata(&file).and_then(|m| m.modified()) else {
+            continue;
+        };
+        let modified: DateTime<Utc> = modified.into();
+        let differs = fs::read_to_string(&file)
+            .is_ok_and(|disk| normalize(&disk) != normalize(&snapshot.content));
+        if latest > modified.timestamp_millis() && differs {
+            found.extend(info(repo_path, &dir, latest));
+        }
+    }
+    found.sort_by(|a, b| b.taken_at.cmp(&a.taken_at));
+    found
+}
+
+/// Compare contents regardless of the line endings and BOM `files::write_text` applies.
+fn normalize(content: &str) -> String {
+    files::strip_bom(content).replace("\r\n", "\n")
+}
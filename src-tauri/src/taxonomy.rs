@@ -0,0 +1,427 @@
+//! Tag taxonomy — tags aggregated across every collection, near-duplicate
+//! detection, renames and merges, and an optional metadata file describing
+//! tags for the site's `/tags` pages.
+//!
+//! Tags are free strings in each entry's frontmatter and are only rewritten
+//! through `bulk`. The metadata file (`TagSettings::metadata`) is a JSON object
+//! keyed by tag, `{"rust": {"description": "..."}}`; fields other than
+//! `description` belong to the site and are kept as they are.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use crate::bulk;
+use crate::content::{self, Rewrite};
+use crate::diff;
+use crate::error::AppError;
+use crate::files;
+use crate::git;
+use crate::index;
+use crate::operations::Operation;
+use crate::repo_config::{self, commit_message};
+use crate::security::ensure_within;
+use crate::types::{
+    BulkEdit, BulkEditChange, BulkEditResult, BulkOp, EntryFilter, MutationKind, RepoConfig,
+    TagMerge, TagUsage, Taxonomy,
+};
+
+#[derive(Serialize, Deserialize, Default, Clone, PartialEq)]
+struct TagMeta {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(flatten)]
+    extra: Map<String, Value>,
+}
+
+impl TagMeta {
+    fn is_empty(&self) -> bool {
+        self.description.is_none() && self.extra.is_empty()
+    }
+}
+
+type Metadata = BTreeMap<String, TagMeta>;
+
+/// The metadata file and its repo-relative path, if descriptions are on.
+fn metadata_file(
+    repo_path: &str,
+    repo: &RepoConfig,
+) -> Result<Option<(PathBuf, String)>, AppError> {
+    let Some(rel) = &repo.tags.metadata else {
+        return Ok(None);
+    };
+    let base = Path::new(repo_path);
+    let file = base.join(rel);
+    ensure_within(&file, base)?;
+    Ok(Some((file, rel.clone())))
+}
+
+/// The metadata file's text (empty if it doesn't exist yet) and its contents.
+fn read_metadata(file: &Path) -> Result<(String, Metadata), AppError> {
+    let text = match fs::read_to_string(file) {
+        Ok(text) => text,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok((String::new(), Metadata::new())),
+        Err(e) => {
+            return Err(AppError::io(format!("Failed to read tags metadata: {e}")).with_path(file))
+        }
+    };
+    let metadata = if files::strip_bom(&text).trim().is_empty() {
+        Metadata::new()
+    } else {
+        serde_json::from_str(files::strip_bom(&text)).map_err(|e| {
+            AppError::invalid_input(format!("Invalid tags metadata: {e}")).with_path(file)
+        })?
+    };
+    Ok((text, metadata))
+}
+
+fn render_metadata(metadata: &Metadata) -> Result<String, AppError> {
+    let json = serde_json::to_string_pretty(metadata).map_err(|e| e.to_string())?;
+    Ok(format!("{json}\n"))
+}
+
+/// The form near-duplicate tags share: lowercase letters and digits, without
+/// a separated `lang`/`language` suffix or a plural `s`.
+fn normalize(tag: &str) -> String {
+    let mut lower = tag.to_lowercase();
+    for suffix in ["language", "lang"] {
+        let stem = lower.strip_suffix(suffix).unwrap_or_default();
+        if stem.ends_with(|c: char| !c.is_alphanumeric()) && stem.chars().any(char::is_alphanumeric)
+        {
+            lower = stem.to_string();
+            break;
+        }
+    }
+    let mut key: String = lower.chars().filter(|c| c.is_alphanumeric()).collect();
+    if key.is_empty() {
+        return lower;
+    }
+    if key.len() > 3 && key.ends_with('s') && !key.ends_with("ss") {
+        key.pop();
+    }
+    key
+}
+
+/// Every tag in use in `repo_path`, with near-duplicates and descriptions.
+pub fn taxonomy(app: &tauri::AppHandle, repo_path: &str) -> Result<Taxonomy, AppError> {
+    let repo = repo_config::load(repo_path)?;
+    let metadata = match metadata_file(repo_path, &repo)? {
+        Some((file, _)) => read_metadata(&file)?.1,
+        None => Metadata::new(),
+    };
+
+    let mut usage: HashMap<String, TagUsage> = HashMap::new();
+    for entry in index::list(app, repo_path)? {
+        let created = (entry.created_date != "unknown").then_some(entry.created_date.as_str());
+        let tags: HashSet<&String> = entry.tags.iter().collect();
+        for tag in tags {
+            let u = usage.entry(tag.clone()).or_insert_with(|| TagUsage {
+                tag: tag.clone(),
+                count: 0,
+                published: 0,
+                first_used: None,
+                last_used: None,
+                description: None,
+            });
+            u.count += 1;
+            if !entry.is_draft {
+                u.published += 1;
+            }
+            if let Some(created) = created {
+                if u.first_used.as_deref().is_none_or(|f| created < f) {
+                    u.first_used = Some(created.to_string());
+                }
+                if u.last_used.as_deref().is_none_or(|l| created > l) {
+                    u.last_used = Some(created.to_string());
+                }
+            }
+        }
+    }
+
+    let mut tags: Vec<TagUsage> = usage.into_values().collect();
+    for u in &mut tags {
+        u.description = metadata.get(&u.tag).and_then(|m| m.description.clone());
+    }
+    tags.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.tag.cmp(&b.tag)));
+
+    // Tags are visited most used first, so groups come out ordered by their
+    // most used tag, which also leads its group.
+    let mut groups: Vec<Vec<String>> = Vec::new();
+    let mut group_of: HashMap<String, usize> = HashMap::new();
+    for u in &tags {
+        match group_of.get(&normalize(&u.tag)) {
+            Some(&i) => groups[i].push(u.tag.clone()),
+            None => {
+                group_of.insert(normalize(&u.tag), groups.len());
+                groups.push(vec![u.tag.clone()]);
+            }
+        }
+    }
+
+    let used: HashSet<&str> = tags.iter().map(|u| u.tag.as_str()).collect();
+    let unused = metadata
+        .keys()
+        .filter(|tag| !used.contains(tag.as_str()))
+        .cloned()
+        .collect();
+
+    Ok(Taxonomy {
+        near_duplicates: groups.into_iter().filter(|g| g.len() > 1).collect(),
+        tags,
+        unused,
+        metadata_path: repo.tags.metadata,
+    })
+}
+
+// ---------------------------------------------------------------------------
+// Renames and merges
+// ---------------------------------------------------------------------------
+
+fn merge_edit(merge: &TagMerge) -> BulkEdit {
+    BulkEdit {
+        filter: EntryFilter {
+            tags: merge.from.clone(),
+            ..EntryFilter::default()
+        },
+        ops: vec![BulkOp::RenameTags {
+            from: merge.from.clone(),
+            to: merge.to.clone(),
+        }],
+    }
+}
+
+/// Move metadata of the tags in `from` to `to`. Fields `to` already has win.
+fn merge_metadata(metadata: &mut Metadata, merge: &TagMerge) {
+    for from in merge.from.iter().filter(|f| **f != merge.to) {
+        let Some(old) = metadata.remove(from) else {
+            continue;
+        };
+        let target = metadata.entry(merge.to.clone()).or_default();
+        if target.description.is_none() {
+            target.description = old.description;
+        }
+        for (key, value) in old.extra {
+            target.extra.entry(key).or_insert(value);
+        }
+        if target.is_empty() {
+            metadata.remove(&merge.to);
+        }
+    }
+}
+
+fn plan_files(
+    app: &tauri::AppHandle,
+    repo_path: &str,
+    merge: &TagMerge,
+) -> Result<Vec<(BulkEditChange, Rewrite)>, AppError> {
+    let repo = repo_config::load(repo_path)?;
+    let mut planned = bulk::plan_files(app, repo_path, &merge_edit(merge))?;
+
+    if let Some((file, rel)) = metadata_file(repo_path, &repo)? {
+        let (original, mut metadata) = read_metadata(&file)?;
+        let before = metadata.clone();
+        merge_metadata(&mut metadata, merge);
+        if metadata != before {
+            let content = render_metadata(&metadata)?;
+            let uncommitted = git::uncommitted_paths(repo_path)?;
+            let change = BulkEditChange {
+                collection: String::new(),
+                slug: String::new(),
+                diff: diff::unified(&rel, &original, &content),
+                uncommitted: content::is_uncommitted(&uncommitted, &rel),
+                path: rel.clone(),
+            };
+            planned.push((
+                change,
+                Rewrite {
+                    file,
+                    rel_path: rel,
+                    content,
+                },
+            ));
+        }
+    }
+    Ok(planned)
+}
+
+/// Every file renaming `merge.from` to `merge.to` would change, the metadata
+/// file included. Writes nothing.
+pub fn plan_merge(
+    app: &tauri::AppHandle,
+    repo_path: &str,
+    merge: &TagMerge,
+) -> Result<Vec<BulkEditChange>, AppError> {
+    Ok(plan_files(app, repo_path, merge)?
+        .into_iter()
+        .map(|(change, _)| change)
+        .collect())
+}
+
+/// Rename `merge.from` to `merge.to` in every file without uncommitted edits,
+/// in one commit (see `content::commit_rewrites`).
+///
+/// Callers run this through the repository's operation queue (see `queue`).
+pub fn apply_merge(
+    app: &tauri::AppHandle,
+    repo_path: &str,
+    merge: &TagMerge,
+    op: &Operation,
+) -> Result<BulkEditResult, AppError> {
+    let repo = repo_config::load(repo_path)?;
+    let (changes, rewrites): (Vec<_>, Vec<_>) = plan_files(app, repo_path, merge)?
+        .into_iter()
+        .filter(|(change, _)| !change.uncommitted)
+        .unzip();
+
+    let subject = content::files_subject(
+        &format!("rename {} to {}", merge.from.join(", "), merge.to),
+        changes.len(),
+    );
+    let commit = content::commit_rewrites(
+        app,
+        repo_path,
+        &rewrites,
+        &commit_message(&repo.commit.tags, &subject),
+        MutationKind::Tags,
+        op,
+    )?;
+
+    Ok(BulkEditResult {
+        applied: changes,
+        commit,
+    })
+}
+
+/// Set `tag`'s description in the metadata file, or clear it when
+/// `description` is blank, and commit the file. Returns the commit, `None` if
+/// the description was already that.
+///
+/// Callers run this through the repository's operation queue (see `queue`).
+pub fn describe(
+    app: &tauri::AppHandle,
+    repo_path: &str,
+    tag: &str,
+    description: &str,
+    op: &Operation,
+) -> Result<Option<String>, AppError> {
+    let repo = repo_config::load(repo_path)?;
+    let Some((file, rel)) = metadata_file(repo_path, &repo)? else {
+        return Err(AppError::not_configured(format!(
+            "Set tags.metadata in {} to describe tags.",
+            repo_config::FILE_NAME
+        )));
+    };
+    bulk::check_tag(tag)?;
+    if content::is_uncommitted(&git::uncommitted_paths(repo_path)?, &rel) {
+        return Err(AppError::invalid_input(format!(
+            "{rel} has uncommitted changes. Commit or discard them first."
+        ))
+        .with_path(&file));
+    }
+
+    let (_, mut metadata) = read_metadata(&file)?;
+    let before = metadata.clone();
+    let description = description.trim();
+    if description.is_empty() {
+        if let Some(meta) = metadata.get_mut(tag) {
+            meta.description = None;
+            if meta.is_empty() {
+                metadata.remove(tag);
+            }
+        }
+    } else {
+        metadata.entry(tag.to_string()).or_default().description = Some(description.to_string());
+    }
+    if metadata == before {
+        return Ok(None);
+    }
+
+    let rewrite = Rewrite {
+        file,
+        rel_path: rel,
+        content: render_metadata(&metadata)?,
+    };
+    content::commit_rewrites(
+        app,
+        repo_path,
+        &[rewrite],
+        &commit_message(&repo.commit.tags, &format!("describe {tag}")),
+        MutationKind::Tags,
+        op,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn spellings_of_a_tag_normalize_together() {
+        for tag in ["rust", "Rust", "rust-lang", "Rust Language", "RUST_LANG"] {
+            assert_eq!(normalize(tag), "rust", "{tag}");
+        }
+        assert_eq!(normalize("golang"), "golang");
+        assert_ne!(normalize("css"), normalize("cs"));
+    }
+
+    #[test]
+    fn only_plain_plurals_are_singularized() {
+        assert_eq!(normalize("tags"), "tag");
+        assert_eq!(normalize("class"), "class");
+        assert_eq!(normalize("css"), "css");
+        assert_eq!(normalize("js"), "js");
+    }
+
+    fn meta(value: serde_json::Value) -> TagMeta {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn merge_keeps_fields_already_on_the_target() {
+        let mut metadata = Metadata::from([
+            (
+                "rust".to_string(),
+                meta(json!({"description": "Systems language", "color": "orange"})),
+            ),
+            (
+                "rust-lang".to_string(),
+                meta(json!({"description": "Old text", "color": "red", "icon": "crab"})),
+            ),
+            ("go".to_string(), meta(json!({"description": "Go"}))),
+        ]);
+        merge_metadata(
+            &mut metadata,
+            &TagMerge {
+                from: vec!["rust-lang".into(), "rust".into()],
+                to: "rust".into(),
+            },
+        );
+        assert_eq!(
+            serde_json::to_value(&metadata).unwrap(),
+            json!({
+                "go": {"description": "Go"},
+                "rust": {"description": "Systems language", "color": "orange", "icon": "crab"},
+            })
+        );
+    }
+
+    #[test]
+    fn merge_moves_metadata_to_a_new_tag() {
+        let mut metadata = Metadata::from([("js".to_string(), meta(json!({"description": "JS"})))]);
+        merge_metadata(
+            &mut metadata,
+            &TagMerge {
+                from: vec!["js".into()],
+                to: "javascript".into(),
+            },
+        );
+        assert_eq!(
+            serde_json::to_value(&metadata).unwrap(),
+            json!({"javascript": {"description": "JS"}})
+        );
+    }
+}
@@ -1,25 +1,336 @@
 //! Shared data types serialized across the Tauri IPC boundary.
 
 use serde::{Deserialize, Serialize};
+use specta::Type;
 
-/// Persisted application settings (repo path, theme preference, Cloudflare credentials).
-#[derive(Debug, Serialize, Deserialize, Clone)]
+use crate::error::AppError;
+
+/// Application settings for the active site profile (repo path, Cloudflare credentials)
+/// plus the global theme preference and the names of all profiles.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Type)]
+#[serde(default)]
 pub struct AppConfig {
     pub repo_path: Option<String>,
     pub theme: Option<String>,
     // Cloudflare credentials
     pub cf_account_id: Option<String>,
     pub cf_project_name: Option<String>,
+    /// Masked placeholder when a token is stored; never the token itself.
     pub cf_api_token: Option<String>,
     pub cf_domain: Option<String>,
     pub cf_zone_id: Option<String>,
+    pub cf_site_tag: Option<String>,
+    // Profiles
+    pub active_profile: Option<String>,
+    pub profiles: Vec<String>,
+}
+
+/// State of the encrypted secret store.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct SecretsStatus {
+    pub key_source: KeySource,
+    /// False while a passphrase-protected store is waiting for `unlock_secrets`.
+    pub unlocked: bool,
+}
+
+/// What the secret store's encryption key is derived from.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum KeySource {
+    /// A random key in a private file next to the store.
+    Keyfile,
+    Passphrase,
+}
+
+/// Per-repository settings read from `.panel.toml` at the site repo root.
+/// Every field has a default, so the file (and each table in it) is optional.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+#[serde(default)]
+pub struct RepoConfig {
+    /// Content collections. Entries here override the built-in `post` and `app`
+    /// collections with the same id; other ids are added.
+    pub collections: Vec<CollectionConfig>,
+    pub dev_server: DevServerSettings,
+    pub posts: PostDefaults,
+    pub commit: CommitPrefixes,
+    pub scan: ScanSettings,
+    pub watch: WatchSettings,
+    pub tags: TagSettings,
+}
+
+impl Default for RepoConfig {
+    fn default() -> Self {
+        Self {
+            collections: CollectionConfig::builtins(),
+            dev_server: DevServerSettings::default(),
+            posts: PostDefaults::default(),
+            commit: CommitPrefixes::default(),
+            scan: ScanSettings::default(),
+            watch: WatchSettings::default(),
+            tags: TagSettings::default(),
+        }
+    }
+}
+
+/// A content collection: where its entries live and how they are tracked.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct CollectionConfig {
+    /// Stable id, surfaced as `ContentEntry.content_type`.
+    pub id: String,
+    /// Display name (defaults to the id).
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Directory relative to the repo root.
+    pub dir: String,
+    /// Entry file patterns relative to `dir`, in priority order. Each starts with
+    /// `{slug}`: `"{slug}/index.md"` is one directory per entry, `"{slug}.md"` one file.
+    #[serde(default = "default_entry_files")]
+    pub entry_files: Vec<String>,
+    /// What the change-tracking hash covers.
+    #[serde(default)]
+    pub hash: HashMode,
+    /// Files written for a new entry. Empty means a frontmatter-only entry file.
+    #[serde(default)]
+    pub scaffold: Vec<ScaffoldFile>,
+    /// Tags every new entry starts with.
+    #[serde(default)]
+    pub default_tags: Vec<String>,
+    /// Site route for previews, e.g. `"/blog/{slug}"`.
+    #[serde(default)]
+    pub route: Option<String>,
+}
+
+fn default_entry_files() -> Vec<String> {
+    vec!["{slug}/index.md".into()]
+}
+
+impl CollectionConfig {
+    /// The blog and apps collections every site starts with.
+    pub fn builtins() -> Vec<Self> {
+        vec![
+            Self {
+                id: "post".into(),
+                label: Some("Posts".into()),
+                dir: "src/content/blog".into(),
+                entry_files: vec!["{slug}/index.mdx".into(), "{slug}/index.md".into()],
+                hash: HashMode::Body,
+                scaffold: Vec::new(),
+                default_tags: Vec::new(),
+                route: Some("/blog/{slug}".into()),
+            },
+            Self {
+                id: "app".into(),
+                label: Some("Apps".into()),
+                dir: "src/content/apps".into(),
+                entry_files: default_entry_files(),
+                hash: HashMode::Directory,
+                scaffold: vec![
+                    ScaffoldFile {
+                        path: "{slug}/index.md".into(),
+                        template: "builtin:frontmatter".into(),
+                    },
+                    ScaffoldFile {
+                        path: "{slug}/App.astro".into(),
+                        template: "builtin:app-astro".into(),
+                    },
+                ],
+                default_tags: vec!["app".into()],
+                route: Some("/apps/{slug}".into()),
+            },
+        ]
+    }
+}
+
+/// Change-tracking scope: the entry file's body, or every file in the entry directory.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum HashMode {
+    #[default]
+    Body,
+    Directory,
+}
+
+/// One file of a new-entry scaffold.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct ScaffoldFile {
+    /// Path relative to the collection dir, containing `{slug}`.
+    pub path: String,
+    /// Repo-relative template file, or `builtin:<name>`.
+    pub template: String,
+}
+
+/// Settings for the panel-managed Astro dev server.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+#[serde(default)]
+pub struct DevServerSettings {
+    /// Avoids conflicting with Astro's default 4321.
+    pub port: u16,
+}
+
+impl Default for DevServerSettings {
+    fn default() -> Self {
+        Self { port: 4322 }
+    }
+}
+
+/// Defaults applied to newly created posts.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+#[serde(default)]
+pub struct PostDefaults {
+    pub author: Option<String>,
+}
+
+impl Default for PostDefaults {
+    fn default() -> Self {
+        Self {
+            author: Some("Filipe Lima".into()),
+        }
+    }
+}
+
+/// Tag taxonomy settings.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Type)]
+#[serde(default)]
+pub struct TagSettings {
+    /// Repo-relative path of a JSON file describing tags, kept up to date by
+    /// the panel for the site's `/tags` pages. Unset: descriptions are off.
+    pub metadata: Option<String>,
+}
+
+/// Pre-commit inspection of everything the panel is about to commit.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+#[serde(default)]
+pub struct ScanSettings {
+    pub enabled: bool,
+    /// Files larger than this (in MB) block the commit.
+    pub max_file_mb: u64,
+    /// Flag random-looking tokens in addition to known secret formats.
+    pub entropy: bool,
+    /// Repo-relative path prefixes that are never scanned.
+    pub ignore: Vec<String>,
+}
+
+impl Default for ScanSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_file_mb: 10,
+            entropy: true,
+            ignore: Vec::new(),
+        }
+    }
+}
+
+/// Files outside the collections that the watcher follows because they affect
+/// the site build.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+#[serde(default)]
+pub struct WatchSettings {
+    /// Setting this replaces the default scopes rather than adding to them.
+    pub scopes: Vec<WatchScope>,
+    /// Skip changes to files `.gitignore` excludes (build output, caches).
+    pub respect_gitignore: bool,
+}
+
+impl Default for WatchSettings {
+    fn default() -> Self {
+        let scope = |channel, paths: &[&str]| WatchScope {
+            channel,
+            paths: paths.iter().map(|p| p.to_string()).collect(),
+        };
+        Self {
+            scopes: vec![
+                scope(
+                    WatchChannel::Components,
+                    &["src/components/**", "src/layouts/**"],
+                ),
+                scope(
+                    WatchChannel::Schema,
+                    &["src/content.config.*", "src/content/config.*"],
+                ),
+                scope(WatchChannel::AstroConfig, &["astro.config.*"]),
+                scope(
+                    WatchChannel::Dependencies,
+                    &[
+                        "package.json",
+                        "bun.lock",
+                        "bun.lockb",
+                        "package-lock.json",
+                        "pnpm-lock.yaml",
+                        "yarn.lock",
+                    ],
+                ),
+            ],
+            respect_gitignore: true,
+        }
+    }
+}
+
+/// Repo-relative file globs reported on one channel. `*` matches within a path
+/// segment and `**` across segments; the first segment must be literal unless
+/// it is the whole pattern, so the repository root is never watched recursively.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct WatchScope {
+    pub channel: WatchChannel,
+    pub paths: Vec<String>,
+}
+
+/// What a non-content change may call for; each has its own event.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum WatchChannel {
+    /// Site components and layouts: previews may need a reload.
+    Components,
+    /// The content collection schema: frontmatter validation may change.
+    Schema,
+    /// `astro.config.*`: the dev server needs a restart.
+    AstroConfig,
+    /// `package.json` and lockfiles: dependencies may need installing.
+    Dependencies,
+}
+
+impl WatchChannel {
+    /// Name of the event changes on this channel are emitted as.
+    pub fn event(self) -> &'static str {
+        match self {
+            Self::Components => "components-changed",
+            Self::Schema => "schema-changed",
+            Self::AstroConfig => "astro-config-changed",
+            Self::Dependencies => "dependencies-changed",
+        }
+    }
+}
+
+/// Prefixes for the commits the panel makes; the title or slug follows after a space.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+#[serde(default)]
+pub struct CommitPrefixes {
+    pub publish: String,
+    pub unpublish: String,
+    pub delete: String,
+    pub migrate: String,
+    pub bulk_edit: String,
+    pub tags: String,
+}
+
+impl Default for CommitPrefixes {
+    fn default() -> Self {
+        Self {
+            publish: "publish:".into(),
+            unpublish: "unpublish:".into(),
+            delete: "delete:".into(),
+            migrate: "migrate:".into(),
+            bulk_edit: "bulk edit:".into(),
+            tags: "tags:".into(),
+        }
+    }
 }
 
 /// A single content entry (blog post or app) as surfaced to the frontend.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
 pub struct ContentEntry {
     pub slug: String,
-    pub content_type: String, // "post" | "app"
+    pub content_type: String, // collection id, e.g. "post" | "app"
     pub title: String,
     pub summary: String,
     pub tags: Vec<String>,
@@ -33,28 +344,58 @@ pub struct ContentEntry {
     pub modified_date: Option<String>,
     pub published_hash: Option<String>,
     pub has_changed: bool,
+    /// Published (or unpublished) in a local commit that hasn't reached the remote yet.
+    pub pending_push: bool,
 }
 
-/// Arguments for creating a new blog post via the IPC `create_post` command.
-#[derive(Debug, Serialize, Deserialize)]
-pub struct CreatePostArgs {
+/// Arguments for creating an entry via `create_entry`, `create_post` or `create_app`.
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct CreateEntryArgs {
     pub title: String,
     pub slug: String,
     pub summary: String,
     pub tags: Vec<String>,
 }
 
-/// Arguments for creating a new app entry via the IPC `create_app` command.
-#[derive(Debug, Serialize, Deserialize)]
-pub struct CreateAppArgs {
-    pub title: String,
-    pub slug: String,
-    pub summary: String,
-    pub tags: Vec<String>,
+/// A file's text with the version it was read at.
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct FileContents {
+    pub content: String,
+    /// SHA-256 of the bytes on disk; pass back to `write_file` as `expected_version`.
+    pub version: String,
+}
+
+/// Outcome of a versioned write. A conflict means the file changed on disk
+/// since the caller read it; nothing was written.
+#[derive(Debug, Serialize, Deserialize, Type)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum WriteResult {
+    Written {
+        version: String,
+    },
+    Conflict {
+        expected: String,
+        /// Current version on disk, or `None` if the file was deleted.
+        actual: Option<String>,
+        /// Current contents on disk, for merging.
+        content: Option<String>,
+    },
+}
+
+/// A stored autosave snapshot of a content file.
+#[derive(Debug, Serialize, Deserialize, Type)]
+pub struct SnapshotInfo {
+    pub id: String,
+    /// Absolute path of the file the snapshot belongs to.
+    pub path: String,
+    /// RFC 3339 timestamp.
+    pub taken_at: String,
+    /// Size of the snapshotted contents in bytes.
+    pub size: u64,
 }
 
 /// Result of a URL health check (dev server or production site).
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Type)]
 pub struct HealthStatus {
     pub url: String,
     pub ok: bool,
@@ -62,7 +403,7 @@ pub struct HealthStatus {
 }
 
 /// Info about the last successful Cloudflare Pages deployment.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
 pub struct CfDeploymentInfo {
     pub deployed_at: String,
     pub commit_hash: Option<String>,
@@ -72,7 +413,7 @@ pub struct CfDeploymentInfo {
 }
 
 /// Aggregated Cloudflare traffic analytics for a given period.
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
 pub struct CfAnalytics {
     pub period: String,
     pub total_requests: u64,
@@ -81,9 +422,13 @@ pub struct CfAnalytics {
     pub top_countries: Vec<CfCountryCount>,
     pub status_codes: Vec<CfStatusCount>,
     pub browsers: Vec<CfBrowserCount>,
+    /// Deltas against the previous equal-length window, when requested.
+    pub comparison: Option<CfComparison>,
+    /// Slots that deviate strongly from their rolling baseline.
+    pub anomalies: Vec<CfAnomaly>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
 pub struct CfDailyCount {
     pub date: String,
     pub count: u64,
@@ -94,27 +439,436 @@ pub struct CfDailyCount {
     pub threats: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
 pub struct CfPathCount {
     pub path: String,
     pub count: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
 pub struct CfCountryCount {
     pub country: String,
     pub count: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
 pub struct CfStatusCount {
     pub status: u16,
     pub count: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
 pub struct CfBrowserCount {
     pub browser: String,
     pub page_views: u64,
 }
 
+/// Period-over-period deltas between the selected window and the one before it.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct CfComparison {
+    pub previous_period_start: String,
+    pub previous_period_end: String,
+    /// One delta per total: requests, uniques, bytes, cached bytes, cached
+    /// requests and threats, in that order.
+    pub totals: Vec<CfDelta>,
+    pub paths: Vec<CfDelta>,
+    pub countries: Vec<CfDelta>,
+}
+
+/// Change in a single count between two periods. `delta_pct` is `None` when
+/// the previous count was zero.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct CfDelta {
+    pub key: String,
+    pub current: u64,
+    pub previous: u64,
+    pub delta: i64,
+    pub delta_pct: Option<f64>,
+}
+
+/// A day (or hour) whose count deviates strongly from its rolling baseline.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct CfAnomaly {
+    pub date: String,
+    pub count: u64,
+    pub baseline: f64,
+    /// Deviation from the baseline in standard deviations (signed).
+    pub score: f64,
+    pub kind: AnomalyKind,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum AnomalyKind {
+    Spike,
+    Drop,
+}
+
+impl AnomalyKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Spike => "spike",
+            Self::Drop => "drop",
+        }
+    }
+}
+
+/// Output format for `export_analytics`.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Html,
+}
+
+/// What `export_analytics` should serialize: an already-fetched result, or a
+/// date range from the stored daily history.
+#[derive(Debug, Serialize, Deserialize, Type)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AnalyticsExportSource {
+    Analytics {
+        analytics: Box<CfAnalytics>,
+    },
+    History {
+        from: String,
+        to: String,
+        engagement: bool,
+    },
+}
+
+/// Browser-side (RUM) analytics from Cloudflare Web Analytics for a given period.
+/// Unlike `CfAnalytics`, these counts come from the beacon and reflect real readers.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct CfWebAnalytics {
+    pub period: String,
+    pub total_page_views: u64,
+    pub total_visits: u64,
+    pub daily: Vec<CfRumDailyCount>,
+    /// The most viewed paths only (50 at most), by page views descending.
+    pub paths: Vec<CfRumPathMetrics>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct CfRumDailyCount {
+    pub date: String,
+    pub page_views: u64,
+    pub visits: u64,
+}
+
+/// Page views, visits and performance percentiles for a single path.
+/// Timings are in milliseconds; CLS is unitless.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct CfRumPathMetrics {
+    pub path: String,
+    pub page_views: u64,
+    pub visits: u64,
+    pub lcp: Option<CfPercentiles>,
+    pub inp: Option<CfPercentiles>,
+    pub cls: Option<CfPercentiles>,
+    pub page_load: Option<CfPercentiles>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct CfPercentiles {
+    pub p50: f64,
+    pub p75: f64,
+    pub p90: f64,
+}
+
+/// A step of a long-running operation, reported via `operation-progress` events.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationStep {
+    Scanning,
+    Hashing,
+    Writing,
+    Deleting,
+    Committing,
+    Pushing,
+    /// The first push was rejected; pulling with rebase before pushing again.
+    Rebasing,
+    /// Running a Cloudflare query, `current` of `total`.
+    Querying,
+}
+
+/// Payload of an `operation-progress` event.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct OperationProgress {
+    /// The operation id the command was started with.
+    pub id: String,
+    pub step: OperationStep,
+    pub current: Option<u32>,
+    pub total: Option<u32>,
+}
+
+/// A repository mutation serialized through the operation queue.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum MutationKind {
+    Publish,
+    Unpublish,
+    SetPinned,
+    Rollback,
+    Delete,
+    /// Pushing queued commits; `slug` is empty.
+    Push,
+    /// Applying content migrations; `slug` is empty.
+    Migrate,
+    /// Applying a bulk frontmatter edit; `slug` is empty.
+    BulkEdit,
+    /// Renaming or merging tags, or editing a tag description; `slug` is empty.
+    Tags,
+}
+
+/// A mutation waiting for, or holding, its repository's turn.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct QueuedMutation {
+    pub kind: MutationKind,
+    pub slug: String,
+    /// False while an earlier mutation of the same repository still runs.
+    pub running: bool,
+    pub queued_at: String,
+}
+
+/// A repository's operation queue, oldest first. Also the payload of
+/// `operation-queue-changed` events.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct OperationQueue {
+    pub repo_path: String,
+    pub mutations: Vec<QueuedMutation>,
+}
+
+/// A local commit waiting to be pushed, kept across restarts.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct PendingPush {
+    pub repo_path: String,
+    pub commit: String,
+    pub slug: String,
+    pub kind: MutationKind,
+    pub committed_at: String,
+    /// Push attempts that failed so far.
+    pub attempts: u32,
+    pub last_error: Option<String>,
+}
+
+/// Payload of `pending-pushes-changed` events: the queue of `repo_path`
+/// changed, or a background retry failed with `error`. `repo_path` is `None`
+/// when the queue file itself couldn't be read.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct PushQueueChanged {
+    pub repo_path: Option<String>,
+    pub error: Option<AppError>,
+}
+
+/// How a watched file changed.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Created,
+    Modified,
+    Deleted,
+}
+
+/// A change to one file of a content entry, made outside the panel.
+/// `content-changed` events carry a list of these.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct ContentChange {
+    pub slug: String,
+    /// Collection id, as in `ContentEntry.content_type`.
+    pub collection: String,
+    pub kind: ChangeKind,
+    /// Path relative to the entry root, `/`-separated. Empty when the change is
+    /// the root itself: a one-file entry, or a whole entry directory.
+    pub subfile: String,
+    /// The changed file is the entry's content file (frontmatter and body).
+    pub entry_file: bool,
+    /// Absolute path of the changed file.
+    pub path: String,
+}
+
+/// Payload of `git-head-moved` events: HEAD now points elsewhere because of a
+/// pull, checkout or commit made outside the panel.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct HeadMoved {
+    pub repo_path: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    /// Checked-out branch, `None` with a detached HEAD.
+    pub branch: Option<String>,
+}
+
+/// Payload of the per-channel events (`schema-changed`, ...): files in a watch
+/// scope changed outside the panel.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct ScopeChange {
+    pub repo_path: String,
+    pub channel: WatchChannel,
+    /// Repo-relative, `/`-separated.
+    pub paths: Vec<String>,
+}
+
+/// A content file a migration would rewrite.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct MigrationChange {
+    pub collection: String,
+    pub slug: String,
+    /// Repo-relative path of the file.
+    pub path: String,
+    /// Ids of the migrations that change the file, in the order they run.
+    pub migrations: Vec<String>,
+    /// Unified diff of the rewrite.
+    pub diff: String,
+    /// The file has uncommitted edits. Applying skips it, so those edits are
+    /// not committed along with the migration.
+    pub uncommitted: bool,
+}
+
+/// What `apply_migrations` would do: each migration in the registry and the
+/// files it would change.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct MigrationPlan {
+    pub migrations: Vec<MigrationInfo>,
+    pub changes: Vec<MigrationChange>,
+}
+
+/// A registered migration.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct MigrationInfo {
+    pub id: String,
+    pub description: String,
+}
+
+/// Result of `apply_migrations`.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct MigrationResult {
+    /// The changes written and committed.
+    pub applied: Vec<MigrationChange>,
+    /// `None` when there was nothing to apply.
+    pub commit: Option<String>,
+}
+
+/// A frontmatter edit applied to every entry matching `filter`.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct BulkEdit {
+    #[serde(default)]
+    pub filter: EntryFilter,
+    /// Applied in order to each entry's frontmatter.
+    pub ops: Vec<BulkOp>,
+}
+
+/// Which entries a `BulkEdit` touches. Every set condition must hold; an
+/// empty filter matches every entry.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Type)]
+#[serde(default)]
+pub struct EntryFilter {
+    /// Collection ids; empty matches all.
+    pub collections: Vec<String>,
+    /// Slugs; empty matches all.
+    pub slugs: Vec<String>,
+    pub is_draft: Option<bool>,
+    pub is_pinned: Option<bool>,
+    /// Matches entries with any of these tags; empty matches all.
+    pub tags: Vec<String>,
+    /// Inclusive bounds on `createdDate`, compared as ISO-8601 strings, so a
+    /// bare date like `2024-01-01` works.
+    pub created_from: Option<String>,
+    pub created_to: Option<String>,
+}
+
+/// One step of a `BulkEdit`. Keys are top-level frontmatter keys; an op that
+/// doesn't apply to an entry (a missing key, a tag it lacks) leaves it alone.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum BulkOp {
+    /// Rename `from` to `to`, keeping the value. Skipped where `to` already exists.
+    RenameKey {
+        from: String,
+        to: String,
+    },
+    /// Set `key` to `value`, a single-line YAML value written as is (quote strings).
+    SetKey {
+        key: String,
+        value: String,
+    },
+    RemoveKey {
+        key: String,
+    },
+    /// Replace matches of the regex `pattern` in `key`'s value, inside any
+    /// quotes. `replacement` may use `$1`-style groups.
+    MapValues {
+        key: String,
+        pattern: String,
+        replacement: String,
+    },
+    /// Tag ops only edit inline `tags: [...]` lists, adding one if missing.
+    AddTags {
+        tags: Vec<String>,
+    },
+    RemoveTags {
+        tags: Vec<String>,
+    },
+    /// Replace each of `from` with `to` where it stands, dropping duplicates.
+    RenameTags {
+        from: Vec<String>,
+        to: String,
+    },
+}
+
+/// A content file a bulk edit would rewrite.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct BulkEditChange {
+    /// Empty, like `slug`, for a file that isn't an entry (the tags metadata file).
+    pub collection: String,
+    pub slug: String,
+    /// Repo-relative path of the file.
+    pub path: String,
+    /// Unified diff of the rewrite.
+    pub diff: String,
+    /// The file has uncommitted edits. Applying skips it.
+    pub uncommitted: bool,
+}
+
+/// Result of `apply_bulk_edit`.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct BulkEditResult {
+    /// The changes written and committed.
+    pub applied: Vec<BulkEditChange>,
+    /// `None` when nothing matched or nothing changed.
+    pub commit: Option<String>,
+}
+
+/// Every tag in use, how it is used, and tags that look like the same one.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct Taxonomy {
+    /// Most used first.
+    pub tags: Vec<TagUsage>,
+    /// Groups of tags that differ only in case, separators, a plural `s` or a
+    /// suffix like `-lang`. Each group and the list are ordered most used first.
+    pub near_duplicates: Vec<Vec<String>>,
+    /// Tags described in the metadata file that no entry uses.
+    pub unused: Vec<String>,
+    /// `TagSettings::metadata`, when descriptions are on.
+    pub metadata_path: Option<String>,
+}
+
+/// How one tag is used across all collections.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct TagUsage {
+    pub tag: String,
+    pub count: u32,
+    /// Entries with the tag that are published.
+    pub published: u32,
+    /// Earliest and latest `createdDate` among entries with the tag.
+    pub first_used: Option<String>,
+    pub last_used: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Rename tags: every tag in `from` becomes `to`. Merging is renaming several
+/// tags to one.
+#[derive(Debug, Serialize, Deserialize, Clone, Type)]
+pub struct TagMerge {
+    pub from: Vec<String>,
+    pub to: String,
+}
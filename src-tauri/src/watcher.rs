@@ -1,10 +1,41 @@
-use notify_debouncer_mini::{new_debouncer, DebouncedEventKind};
+//! File-system watcher — reports changes made outside the panel.
+//!
+//! Collection directories are watched recursively. Each changed file is
+//! resolved to its entry and reported on `content-changed` as a
+//! `ContentChange`; writes, deletions and checkouts made by the panel itself
+//! are recorded as they happen and left out. Files matching the repo's watch
+//! scopes (components, the content schema, `astro.config.*`, dependencies) are
+//! reported on one event per channel, as a `ScopeChange`. `.git` is watched
+//! too, so a pull, checkout or commit from a terminal fires `git-head-moved`.
+
 use notify::RecursiveMode;
-use std::path::PathBuf;
-use std::sync::Mutex;
-use std::time::Duration;
+use notify_debouncer_mini::{new_debouncer, DebouncedEventKind};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex, MutexGuard};
+use std::time::{Duration, Instant, SystemTime};
 use tauri::{AppHandle, Emitter, Manager};
 
+use crate::collections;
+use crate::git;
+use crate::scopes::{self, Scopes};
+use crate::types::{
+    ChangeKind, CollectionConfig, ContentChange, HeadMoved, ScopeChange, WatchChannel,
+};
+
+/// Event carrying a `Vec<ContentChange>`.
+const CHANGE_EVENT: &str = "content-changed";
+/// Event carrying a `HeadMoved`.
+const HEAD_EVENT: &str = "git-head-moved";
+/// How long a write by the panel is recognized. Its event arrives after the
+/// 500ms debounce; the file's stamp must still match, so a later external edit
+/// of the same file within the window is reported.
+const OWN_WRITE_TTL: Duration = Duration::from_secs(10);
+/// How long everything under a tree the panel removed or checked out is
+/// ignored. Git writes files one by one, so there is no stamp to compare.
+const OWN_TREE_TTL: Duration = Duration::from_secs(2);
+
 struct WatcherInner {
     _debouncer: notify_debouncer_mini::Debouncer<notify::RecommendedWatcher>,
     watched_path: String,
@@ -18,6 +49,252 @@ impl WatcherState {
     }
 }
 
+// ---------------------------------------------------------------------------
+// The panel's own changes
+// ---------------------------------------------------------------------------
+
+// Writes happen deep inside helpers (`files::write_atomic`, git checkouts)
+// that have no app handle, so these live in statics rather than managed state.
+
+#[derive(Default)]
+struct OwnChanges {
+    /// Files the panel wrote, with their stamp right after the write.
+    writes: HashMap<PathBuf, (Stamp, Instant)>,
+    /// Trees the panel removed or checked out, with when to stop ignoring them.
+    trees: Vec<(PathBuf, Instant)>,
+}
+
+type Stamp = (Option<SystemTime>, u64);
+
+static OWN: LazyLock<Mutex<OwnChanges>> = LazyLock::new(Default::default);
+
+/// Last HEAD commit seen per repository, updated by the panel's own commits.
+static HEADS: LazyLock<Mutex<HashMap<String, Option<String>>>> = LazyLock::new(Default::default);
+
+fn stamp(path: &Path) -> Option<Stamp> {
+    let meta = fs::metadata(path).ok()?;
+    Some((meta.modified().ok(), meta.len()))
+}
+
+fn own_changes() -> MutexGuard<'static, OwnChanges> {
+    let mut own = OWN.lock().unwrap_or_else(|e| e.into_inner());
+    let now = Instant::now();
+    own.writes
+        .retain(|_, (_, at)| now.duration_since(*at) < OWN_WRITE_TTL);
+    own.trees.retain(|(_, until)| *until > now);
+    own
+}
+
+/// Record that the panel just wrote `path`.
+pub fn note_own_write(path: &Path) {
+    if let Some(s) = stamp(path) {
+        own_changes()
+            .writes
+            .insert(path.to_path_buf(), (s, Instant::now()));
+    }
+}
+
+/// Record that the panel just removed or restored everything under `root`.
+pub fn note_own_tree(root: &Path) {
+    own_changes()
+        .trees
+        .push((root.to_path_buf(), Instant::now() + OWN_TREE_TTL));
+}
+
+/// Record HEAD of `repo_path` after the panel committed or rebased, so the
+/// move isn't reported as external.
+pub fn note_own_head(repo_path: &str) {
+    let head = git::head_commit(repo_path);
+    HEADS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(repo_path.to_string(), head);
+}
+
+fn is_own(path: &Path) -> bool {
+    let own = own_changes();
+    own.trees.iter().any(|(root, _)| path.starts_with(root))
+        || own
+            .writes
+            .get(path)
+            .is_some_and(|(s, _)| stamp(path).as_ref() == Some(s))
+}
+
+// ---------------------------------------------------------------------------
+// Classifying changes
+// ---------------------------------------------------------------------------
+
+/// Files under the watched collection directories, to tell creations from
+/// modifications and to expand a removed directory into its files.
+struct Tracker {
+    base: PathBuf,
+    collections: Vec<CollectionConfig>,
+    known: HashSet<PathBuf>,
+}
+
+impl Tracker {
+    fn new(base: &Path, collections: Vec<CollectionConfig>) -> Self {
+        let mut known = HashSet::new();
+        for collection in &collections {
+            walk_files(&base.join(&collection.dir), &mut known);
+        }
+        Self {
+            base: base.to_path_buf(),
+            collections,
+            known,
+        }
+    }
+
+    /// The changes behind a batch of reported paths. Directories expand to the
+    /// files created or deleted under them.
+    fn classify(&mut self, paths: &[PathBuf]) -> Vec<(PathBuf, ChangeKind)> {
+        let mut changes = Vec::new();
+        for path in paths {
+            if path.is_file() {
+                let kind = if self.known.insert(path.clone()) {
+                    ChangeKind::Created
+                } else {
+                    ChangeKind::Modified
+                };
+                changes.push((path.clone(), kind));
+            } else if path.is_dir() {
+                let mut files = HashSet::new();
+                walk_files(path, &mut files);
+                for file in files {
+                    if self.known.insert(file.clone()) {
+                        changes.push((file, ChangeKind::Created));
+                    }
+                }
+            } else if self.known.remove(path) {
+                changes.push((path.clone(), ChangeKind::Deleted));
+            } else {
+                let gone: Vec<PathBuf> = self
+                    .known
+                    .iter()
+                    .filter(|p| p.starts_with(path))
+                    .cloned()
+                    .collect();
+                for file in gone {
+                    self.known.remove(&file);
+                    changes.push((file, ChangeKind::Deleted));
+                }
+            }
+        }
+        changes.sort_by(|a, b| a.0.cmp(&b.0));
+        changes.dedup_by(|a, b| a.0 == b.0);
+        changes
+    }
+
+    /// Resolve a changed file to its entry; files outside any entry give `None`.
+    fn describe(&self, path: &Path, kind: ChangeKind) -> Option<ContentChange> {
+        self.collections.iter().find_map(|collection| {
+            let located = collections::locate(&self.base, collection, path)?;
+            Some(ContentChange {
+                slug: located.slug,
+                collection: collection.id.clone(),
+                kind,
+                subfile: located.subfile,
+                entry_file: located.entry_file,
+                path: path.to_string_lossy().to_string(),
+            })
+        })
+    }
+}
+
+/// Every non-hidden file under `dir`.
+fn walk_files(dir: &Path, out: &mut HashSet<PathBuf>) {
+    let Ok(readdir) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in readdir.flatten() {
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        if path.is_dir() {
+            walk_files(&path, out);
+        } else {
+            out.insert(path);
+        }
+    }
+}
+
+/// Whether a path under `.git` can mean HEAD moved.
+fn touches_head(git_dir: &Path, path: &Path) -> bool {
+    let Ok(rel) = path.strip_prefix(git_dir) else {
+        return false;
+    };
+    rel == Path::new("HEAD") || rel == Path::new("packed-refs") || rel.starts_with("refs/heads")
+}
+
+/// Emit `git-head-moved` if HEAD of `repo_path` differs from what was last seen.
+fn check_head(app: &AppHandle, repo_path: &str) {
+    let head = git::head_commit(repo_path);
+    let previous = HEADS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(repo_path.to_string(), head.clone());
+    match previous {
+        Some(from) if from != head => {
+            let _ = app.emit(
+                HEAD_EVENT,
+                HeadMoved {
+                    repo_path: repo_path.to_string(),
+                    from,
+                    to: head,
+                    branch: git::current_branch(repo_path),
+                },
+            );
+        }
+        _ => {}
+    }
+}
+
+/// Emit one event per channel for changed files in the watch scopes, leaving
+/// out the panel's own writes and, if configured, git-ignored files.
+fn report_scopes(
+    app: &AppHandle,
+    repo_path: &str,
+    scopes: &Scopes,
+    respect_gitignore: bool,
+    paths: &[PathBuf],
+) {
+    let base = Path::new(repo_path);
+    let mut matched: Vec<(String, WatchChannel)> = paths
+        .iter()
+        .filter(|p| !is_own(p))
+        .filter_map(|p| {
+            let rel = scopes::rel_path(base, p)?;
+            let channel = scopes.channel_of(&rel)?;
+            Some((rel, channel))
+        })
+        .collect();
+    if respect_gitignore {
+        let rels: Vec<String> = matched.iter().map(|(rel, _)| rel.clone()).collect();
+        let ignored = git::ignored(repo_path, &rels);
+        matched.retain(|(rel, _)| !ignored.contains(rel));
+    }
+
+    let mut by_channel: Vec<ScopeChange> = Vec::new();
+    for (rel, channel) in matched {
+        match by_channel.iter_mut().find(|c| c.channel == channel) {
+            Some(change) => change.paths.push(rel),
+            None => by_channel.push(ScopeChange {
+                repo_path: repo_path.to_string(),
+                channel,
+                paths: vec![rel],
+            }),
+        }
+    }
+    for change in by_channel {
+        let _ = app.emit(change.channel.event(), change);
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Lifecycle
+// ---------------------------------------------------------------------------
+
 pub fn start_watching(app: &AppHandle, repo_path: &str) -> Result<(), String> {
     let state = app.state::<WatcherState>();
     let mut guard = state.0.lock().map_err(|e| e.to_string())?;
@@ -30,24 +307,74 @@ pub fn start_watching(app: &AppHandle, repo_path: &str) -> Result<(), String> {
     }
 
     // Drop old watcher if any (different path)
-    *guard = None;
+    if let Some(inner) = guard.take() {
+        crate::index::set_watched(app, &inner.watched_path, false);
+    }
 
-    let blog_dir = PathBuf::from(repo_path).join("src/content/blog");
-    let apps_dir = PathBuf::from(repo_path).join("src/content/apps");
+    let repo = crate::repo_config::load(repo_path)?;
+    let base = PathBuf::from(repo_path);
+    let collection_dirs: Vec<(String, PathBuf)> = repo
+        .collections
+        .iter()
+        .map(|c| (c.id.clone(), base.join(&c.dir)))
+        .collect();
+    let git_dir = base.join(".git");
+    let scope_dirs = scopes::watch_dirs(&repo.watch);
+    let scopes = Scopes::new(&repo.watch);
+    let respect_gitignore = repo.watch.respect_gitignore;
+    note_own_head(repo_path);
 
     let app_handle = app.clone();
+    let watched_repo = repo_path.to_string();
+    let mut tracker = Tracker::new(&base, repo.collections);
+    let head_dir = git_dir.clone();
+    let content_dirs: Vec<PathBuf> = collection_dirs.iter().map(|(_, d)| d.clone()).collect();
     let mut debouncer = new_debouncer(
         Duration::from_millis(500),
         move |result: Result<Vec<notify_debouncer_mini::DebouncedEvent>, notify::Error>| {
-            if let Ok(events) = result {
-                let paths: Vec<String> = events
-                    .iter()
-                    .filter(|e| e.kind == DebouncedEventKind::Any)
-                    .map(|e| e.path.to_string_lossy().to_string())
-                    .collect();
-                if !paths.is_empty() {
-                    let _ = app_handle.emit("content-changed", paths);
+            let events = match result {
+                Ok(events) => events,
+                Err(_) => {
+                    // Changes may have been missed; have the index check everything.
+                    crate::index::revalidate(&app_handle, &watched_repo);
+                    return;
                 }
+            };
+            let (git_paths, paths): (Vec<PathBuf>, Vec<PathBuf>) = events
+                .iter()
+                .filter(|e| e.kind == DebouncedEventKind::Any)
+                .map(|e| e.path.clone())
+                .partition(|p| p.starts_with(&head_dir));
+            let (paths, scope_paths): (Vec<PathBuf>, Vec<PathBuf>) = paths
+                .into_iter()
+                .partition(|p| content_dirs.iter().any(|d| p.starts_with(d)));
+
+            if git_paths.iter().any(|p| touches_head(&head_dir, p)) {
+                check_head(&app_handle, &watched_repo);
+            }
+            if !scope_paths.is_empty() {
+                report_scopes(
+                    &app_handle,
+                    &watched_repo,
+                    &scopes,
+                    respect_gitignore,
+                    &scope_paths,
+                );
+            }
+            if paths.is_empty() {
+                return;
+            }
+            // Update the index first, so a listing triggered by the event sees
+            // the change. The panel's own changes count here too.
+            crate::index::mark_dirty(&app_handle, &watched_repo, &paths);
+            let changes: Vec<ContentChange> = tracker
+                .classify(&paths)
+                .into_iter()
+                .filter(|(path, _)| !is_own(path))
+                .filter_map(|(path, kind)| tracker.describe(&path, kind))
+                .collect();
+            if !changes.is_empty() {
+                let _ = app_handle.emit(CHANGE_EVENT, changes);
             }
         },
     )
@@ -55,22 +382,45 @@ pub fn start_watching(app: &AppHandle, repo_path: &str) -> Result<(), String> {
 
     let watcher = debouncer.watcher();
 
-    if blog_dir.is_dir() {
-        watcher
-            .watch(&blog_dir, RecursiveMode::Recursive)
-            .map_err(|e| format!("Failed to watch blog dir: {}", e))?;
+    for (id, dir) in &collection_dirs {
+        if dir.is_dir() {
+            watcher
+                .watch(dir, RecursiveMode::Recursive)
+                .map_err(|e| format!("Failed to watch {} dir: {}", id, e))?;
+        }
     }
-
-    if apps_dir.is_dir() {
+    for (dir, recursive) in &scope_dirs {
+        let dir = base.join(dir);
+        let mode = if *recursive {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        if dir.is_dir() {
+            watcher
+                .watch(&dir, mode)
+                .map_err(|e| format!("Failed to watch {}: {}", dir.display(), e))?;
+        }
+    }
+    // HEAD and packed-refs sit directly in .git; branch refs under refs/heads.
+    // Worktrees and submodules have a .git file instead and go without.
+    if git_dir.is_dir() {
         watcher
-            .watch(&apps_dir, RecursiveMode::Recursive)
-            .map_err(|e| format!("Failed to watch apps dir: {}", e))?;
+            .watch(&git_dir, RecursiveMode::NonRecursive)
+            .map_err(|e| format!("Failed to watch .git: {}", e))?;
+        let heads = git_dir.join("refs/heads");
+        if heads.is_dir() {
+            watcher
+                .watch(&heads, RecursiveMode::Recursive)
+                .map_err(|e| format!("Failed to watch .git/refs/heads: {}", e))?;
+        }
     }
 
     *guard = Some(WatcherInner {
         _debouncer: debouncer,
         watched_path: repo_path.to_string(),
     });
+    crate::index::set_watched(app, repo_path, true);
 
     Ok(())
 }
@@ -78,6 +428,8 @@ pub fn start_watching(app: &AppHandle, repo_path: &str) -> Result<(), String> {
 pub fn stop_watching(app: &AppHandle) -> Result<(), String> {
     let state = app.state::<WatcherState>();
     let mut guard = state.0.lock().map_err(|e| e.to_string())?;
-    *guard = None;
+    if let Some(inner) = guard.take() {
+        crate::index::set_watched(app, &inner.watched_path, false);
+    }
     Ok(())
 }
@@ -100,7 +100,7 @@ export function App() {
 
     const valid = await validateRepoPath(path);
     if (!valid) {
-      addToast("Invalid repo: missing src/content/blog/, src/content/apps/, or package.json", "error");
+      addToast("Invalid repo: missing content directories or package.json", "error");
       return;
     }
 
@@ -0,0 +1,263 @@
+import { createSignal, For, Index, Show } from "solid-js";
+import { createStore } from "solid-js/store";
+import type { BulkEdit, BulkEditChange, BulkOp, EntryFilter } from "../lib/commands";
+import { applyBulkEdit, isCommandError, planBulkEdit } from "../lib/commands";
+import {
+  state,
+  collections,
+  collectionLabel,
+  addToast,
+  updateToast,
+  trackOperation,
+  toastProgress,
+  refreshEntries,
+} from "../lib/store";
+import { DiffList } from "./DiffList";
+
+type OpKind = BulkOp["op"];
+
+/** An operation row as edited; only the fields its kind uses are sent. */
+interface OpDraft {
+  op: OpKind;
+  key: string;
+  to: string;
+  value: string;
+  pattern: string;
+  replacement: string;
+  tags: string;
+}
+
+const OP_LABELS: Record<OpKind, string> = {
+  rename_key: "Rename key",
+  set_key: "Set key",
+  remove_key: "Remove key",
+  map_values: "Replace in value",
+  add_tags: "Add tags",
+  remove_tags: "Remove tags",
+};
+
+const emptyOp = (): OpDraft => ({ op: "add_tags", key: "", to: "", value: "", pattern: "", replacement: "", tags: "" });
+
+const splitList = (text: string) =>
+  text
+    .split(",")
+    .map((t) => t.trim())
+    .filter(Boolean);
+
+function toOp(d: OpDraft): BulkOp {
+  switch (d.op) {
+    case "rename_key":
+      return { op: d.op, from: d.key, to: d.to };
+    case "set_key":
+      return { op: d.op, key: d.key, value: d.value };
+    case "remove_key":
+      return { op: d.op, key: d.key };
+    case "map_values":
+      return { op: d.op, key: d.key, pattern: d.pattern, replacement: d.replacement };
+    case "add_tags":
+    case "remove_tags":
+      return { op: d.op, tags: splitList(d.tags) };
+  }
+}
+
+/**
+ * Bulk frontmatter edits — pick entries with a filter, describe the edit as a
+ * list of operations, preview every affected file and apply them in one commit.
+ */
+export function BulkEditPanel() {
+  const [filter, setFilter] = createStore({ collection: "", status: "", tags: "", from: "", to: "" });
+  const [ops, setOps] = createStore<OpDraft[]>([emptyOp()]);
+  const [preview, setPreview] = createSignal<{ edit: BulkEdit; changes: BulkEditChange[] } | null>(null);
+  const [previewing, setPreviewing] = createSignal(false);
+  const [applying, setApplying] = createSignal(false);
+
+  const applicable = () => preview()?.changes.filter((c) => !c.uncommitted) ?? [];
+
+  // Any edit invalidates the preview, so what is applied is what was shown.
+  function editFilter(field: keyof typeof filter, value: string) {
+    setFilter(field, value);
+    setPreview(null);
+  }
+  function editOp(i: number, field: keyof OpDraft, value: string) {
+    setOps(i, field, value as never);
+    setPreview(null);
+  }
+
+  function buildEdit(): BulkEdit {
+    const entryFilter: EntryFilter = {
+      collections: filter.collection ? [filter.collection] : [],
+      slugs: [],
+      is_draft: filter.status === "" ? null : filter.status === "draft",
+      is_pinned: null,
+      tags: splitList(filter.tags),
+      created_from: filter.from || null,
+      created_to: filter.to || null,
+    };
+    return { filter: entryFilter, ops: ops.map(toOp) };
+  }
+
+  async function runPreview() {
+    const repoPath = state.config.repo_path;
+    if (!repoPath) return;
+    const edit = buildEdit();
+    setPreviewing(true);
+    try {
+      setPreview({ edit, changes: await planBulkEdit(repoPath, edit) });
+    } catch (e) {
+      addToast(`Failed to preview edit: ${e}`, "error");
+    } finally {
+      setPreviewing(false);
+    }
+  }
+
+  async function apply() {
+    const repoPath = state.config.repo_path;
+    const edit = preview()?.edit;
+    if (!repoPath || !edit) return;
+    setApplying(true);
+    const tid = addToast("Applying bulk edit...", "warn");
+    try {
+      const result = await trackOperation(
+        (id) => applyBulkEdit(repoPath, edit, id),
+        toastProgress(tid, "Editing"),
+      );
+      const count = result.applied.length;
+      updateToast(tid, count ? `Edited ${count} file${count === 1 ? "" : "s"}` : "Nothing to edit");
+      setPreview(null);
+      await refreshEntries();
+    } catch (e) {
+      if (isCommandError(e, "cancelled")) {
+        updateToast(tid, e.message, "warn");
+        return;
+      }
+      updateToast(tid, `Bulk edit failed: ${e}`, "error");
+    } finally {
+      setApplying(false);
+    }
+  }
+
+  const field = (i: number, name: keyof OpDraft, placeholder: string) => (
+    <input
+      class="settings-input"
+      type="text"
+      value={ops[i][name]}
+      onInput={(e) => editOp(i, name, e.currentTarget.value)}
+      placeholder={placeholder}
+    />
+  );
+
+  return (
+    <div class="settings-group">
+      <div class="settings-row">
+        <div class="settings-info">
+          <label class="settings-label">Bulk Edit</label>
+          <p class="settings-hint">
+            Edit the frontmatter of every matching entry. isDraft and publishedHash are left to
+            publishing.
+          </p>
+        </div>
+      </div>
+
+      <div class="settings-bulk-filter">
+        <select
+          class="settings-input"
+          value={filter.collection}
+          onChange={(e) => editFilter("collection", e.currentTarget.value)}
+        >
+          <option value="">All collections</option>
+          <For each={collections()}>{(c) => <option value={c.id}>{collectionLabel(c.id)}</option>}</For>
+        </select>
+        <select
+          class="settings-input"
+          value={filter.status}
+          onChange={(e) => editFilter("status", e.currentTarget.value)}
+        >
+          <option value="">Drafts and published</option>
+          <option value="draft">Drafts</option>
+          <option value="published">Published</option>
+        </select>
+        <input
+          class="settings-input"
+          type="text"
+          value={filter.tags}
+          onInput={(e) => editFilter("tags", e.currentTarget.value)}
+          placeholder="Any of these tags"
+        />
+        <input
+          class="settings-input"
+          type="date"
+          value={filter.from}
+          onInput={(e) => editFilter("from", e.currentTarget.value)}
+          title="Created on or after"
+        />
+        <input
+          class="settings-input"
+          type="date"
+          value={filter.to}
+          onInput={(e) => editFilter("to", e.currentTarget.value)}
+          title="Created on or before"
+        />
+      </div>
+
+      <Index each={ops}>
+        {(op, i) => (
+          <div class="settings-bulk-op">
+            <select
+              class="settings-input"
+              value={op().op}
+              onChange={(e) => editOp(i, "op", e.currentTarget.value)}
+            >
+              <For each={Object.entries(OP_LABELS)}>{([kind, label]) => <option value={kind}>{label}</option>}</For>
+            </select>
+            <Show
+              when={op().op !== "add_tags" && op().op !== "remove_tags"}
+              fallback={field(i, "tags", "Tags, comma separated")}
+            >
+              {field(i, "key", "Key")}
+            </Show>
+            <Show when={op().op === "rename_key"}>{field(i, "to", "New key")}</Show>
+            <Show when={op().op === "set_key"}>{field(i, "value", "YAML value, e.g. \"text\"")}</Show>
+            <Show when={op().op === "map_values"}>
+              {field(i, "pattern", "Regex")}
+              {field(i, "replacement", "Replacement")}
+            </Show>
+            <button
+              class="btn"
+              disabled={ops.length === 1}
+              onClick={() => {
+                setOps((list) => list.filter((_, j) => j !== i));
+                setPreview(null);
+              }}
+            >
+              Remove
+            </button>
+          </div>
+        )}
+      </Index>
+
+      <div class="settings-value-with-action">
+        <button
+          class="btn"
+          onClick={() => {
+            setOps(ops.length, emptyOp());
+            setPreview(null);
+          }}
+        >
+          Add Operation
+        </button>
+        <button class="btn" onClick={runPreview} disabled={previewing() || applying()}>
+          {previewing() ? "Previewing..." : "Preview"}
+        </button>
+        <Show when={applicable().length > 0}>
+          <button class="btn btn-primary" onClick={apply} disabled={applying()}>
+            {applying() ? "Applying..." : `Apply to ${applicable().length} file${applicable().length === 1 ? "" : "s"}`}
+          </button>
+        </Show>
+      </div>
+
+      <Show when={preview()}>
+        {(p) => <DiffList changes={p().changes} empty="No matching entry would change." />}
+      </Show>
+    </div>
+  );
+}
@@ -0,0 +1,32 @@
+import { For, Show } from "solid-js";
+
+/** A previewed rewrite of one file. */
+export interface FileDiff {
+  path: string;
+  diff: string;
+  uncommitted: boolean;
+}
+
+/**
+ * Diffs of the files a content-wide change would rewrite. Files with
+ * uncommitted edits are flagged; applying skips them.
+ */
+export function DiffList(props: { changes: FileDiff[]; empty: string }) {
+  return (
+    <Show when={props.changes.length > 0} fallback={<p class="settings-hint">{props.empty}</p>}>
+      <For each={props.changes}>
+        {(change) => (
+          <div class="settings-diff">
+            <div class="settings-diff-header">
+              <span class="settings-path-value">{change.path}</span>
+              <Show when={change.uncommitted}>
+                <span class="settings-value-tag">Uncommitted edits — skipped</span>
+              </Show>
+            </div>
+            <pre class="settings-diff-body">{change.diff}</pre>
+          </div>
+        )}
+      </For>
+    </Show>
+  );
+}
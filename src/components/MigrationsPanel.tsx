@@ -0,0 +1,96 @@
+import { createSignal, For, Show } from "solid-js";
+import type { MigrationPlan } from "../lib/commands";
+import { applyMigrations, isCommandError, planMigrations } from "../lib/commands";
+import { state, addToast, updateToast, trackOperation, toastProgress, refreshEntries } from "../lib/store";
+import { DiffList } from "./DiffList";
+
+/**
+ * Content migrations — review what the registered migrations would rewrite,
+ * as diffs, then apply them in one commit.
+ */
+export function MigrationsPanel() {
+  const [plan, setPlan] = createSignal<MigrationPlan | null>(null);
+  const [checking, setChecking] = createSignal(false);
+  const [applying, setApplying] = createSignal(false);
+
+  const applicable = () => plan()?.changes.filter((c) => !c.uncommitted) ?? [];
+
+  async function check() {
+    const repoPath = state.config.repo_path;
+    if (!repoPath) return;
+    setChecking(true);
+    try {
+      setPlan(await planMigrations(repoPath));
+    } catch (e) {
+      addToast(`Failed to check migrations: ${e}`, "error");
+    } finally {
+      setChecking(false);
+    }
+  }
+
+  async function apply() {
+    const repoPath = state.config.repo_path;
+    if (!repoPath) return;
+    setApplying(true);
+    const tid = addToast("Applying migrations...", "warn");
+    try {
+      const result = await trackOperation(
+        (id) => applyMigrations(repoPath, id),
+        toastProgress(tid, "Migrating"),
+      );
+      const count = result.applied.length;
+      updateToast(tid, count ? `Migrated ${count} file${count === 1 ? "" : "s"}` : "Nothing to migrate");
+      await refreshEntries();
+      await check();
+    } catch (e) {
+      if (isCommandError(e, "cancelled")) {
+        updateToast(tid, e.message, "warn");
+        return;
+      }
+      updateToast(tid, `Migration failed: ${e}`, "error");
+    } finally {
+      setApplying(false);
+    }
+  }
+
+  return (
+    <div class="settings-group">
+      <div class="settings-row">
+        <div class="settings-info">
+          <label class="settings-label">Content Migrations</label>
+          <p class="settings-hint">
+            One-time fixes to stored content. Nothing is written until you apply them, and
+            applying commits every changed file at once.
+          </p>
+          <Show when={plan()}>
+            {(p) => (
+              <ul class="settings-migration-list">
+                <For each={p().migrations}>
+                  {(m) => (
+                    <li>
+                      <code>{m.id}</code> — {m.description}
+                    </li>
+                  )}
+                </For>
+              </ul>
+            )}
+          </Show>
+        </div>
+        <div class="settings-value-with-action">
+          <button class="btn" onClick={check} disabled={checking() || applying()}>
+            {checking() ? "Checking..." : "Check"}
+          </button>
+          <Show when={applicable().length > 0}>
+            <button class="btn btn-primary" onClick={apply} disabled={applying()}>
+              {applying() ? "Applying..." : `Apply to ${applicable().length} file${applicable().length === 1 ? "" : "s"}`}
+            </button>
+          </Show>
+        </div>
+      </div>
+
+      <Show when={plan()}>
+        {(p) => <DiffList changes={p().changes} empty="Content is up to date." />}
+      </Show>
+    </div>
+  );
+}
@@ -0,0 +1,220 @@
+import { createSignal, For, Show } from "solid-js";
+import type { BulkEditChange, TagMerge, TagUsage, Taxonomy } from "../lib/commands";
+import { applyTagMerge, getTaxonomy, isCommandError, planTagMerge, setTagDescription } from "../lib/commands";
+import { state, addToast, updateToast, trackOperation, toastProgress, refreshEntries } from "../lib/store";
+import { DiffList } from "./DiffList";
+
+const splitList = (text: string) =>
+  text
+    .split(",")
+    .map((t) => t.trim())
+    .filter(Boolean);
+
+/** `2024-03-01T10:00` → `2024-03-01`. */
+const day = (date: string | null) => date?.slice(0, 10) ?? "—";
+
+/**
+ * Tag taxonomy — every tag with its usage, likely duplicates, renames and
+ * merges across all entries in one commit, and descriptions kept in the
+ * site's tags metadata file when one is configured.
+ */
+export function TagsPanel() {
+  const [taxonomy, setTaxonomy] = createSignal<Taxonomy | null>(null);
+  const [loading, setLoading] = createSignal(false);
+  const [from, setFrom] = createSignal("");
+  const [to, setTo] = createSignal("");
+  const [preview, setPreview] = createSignal<{ merge: TagMerge; changes: BulkEditChange[] } | null>(null);
+  const [busy, setBusy] = createSignal(false);
+
+  const applicable = () => preview()?.changes.filter((c) => !c.uncommitted) ?? [];
+
+  async function load() {
+    const repoPath = state.config.repo_path;
+    if (!repoPath) return;
+    setLoading(true);
+    try {
+      setTaxonomy(await getTaxonomy(repoPath));
+    } catch (e) {
+      addToast(`Failed to load tags: ${e}`, "error");
+    } finally {
+      setLoading(false);
+    }
+  }
+
+  async function runPreview(merge: TagMerge) {
+    const repoPath = state.config.repo_path;
+    if (!repoPath) return;
+    setFrom(merge.from.join(", "));
+    setTo(merge.to);
+    setBusy(true);
+    try {
+      setPreview({ merge, changes: await planTagMerge(repoPath, merge) });
+    } catch (e) {
+      addToast(`Failed to preview rename: ${e}`, "error");
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  /** Merge a near-duplicate group into its most used tag. */
+  function previewGroup(group: string[]) {
+    const [target, ...rest] = group;
+    runPreview({ from: rest, to: target });
+  }
+
+  async function apply() {
+    const repoPath = state.config.repo_path;
+    const merge = preview()?.merge;
+    if (!repoPath || !merge) return;
+    setBusy(true);
+    const tid = addToast(`Renaming to "${merge.to}"...`, "warn");
+    try {
+      const result = await trackOperation(
+        (id) => applyTagMerge(repoPath, merge, id),
+        toastProgress(tid, "Renaming tags"),
+      );
+      const count = result.applied.length;
+      updateToast(tid, count ? `Updated ${count} file${count === 1 ? "" : "s"}` : "Nothing to rename");
+      setPreview(null);
+      await refreshEntries();
+      await load();
+    } catch (e) {
+      if (isCommandError(e, "cancelled")) {
+        updateToast(tid, e.message, "warn");
+        return;
+      }
+      updateToast(tid, `Rename failed: ${e}`, "error");
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  async function describe(usage: TagUsage, description: string) {
+    const repoPath = state.config.repo_path;
+    if (!repoPath || description.trim() === (usage.description ?? "")) return;
+    const tid = addToast(`Saving description of "${usage.tag}"...`, "warn");
+    try {
+      const commit = await trackOperation(
+        (id) => setTagDescription(repoPath, usage.tag, description, id),
+        toastProgress(tid, "Saving"),
+      );
+      updateToast(tid, commit ? `Described "${usage.tag}"` : "Description unchanged");
+      await load();
+    } catch (e) {
+      if (isCommandError(e, "cancelled")) {
+        updateToast(tid, e.message, "warn");
+        return;
+      }
+      updateToast(tid, `Failed to save description: ${e}`, "error");
+    }
+  }
+
+  return (
+    <div class="settings-group">
+      <div class="settings-row">
+        <div class="settings-info">
+          <label class="settings-label">Tags</label>
+          <p class="settings-hint">
+            Every tag in use, with tags that look like the same one. Renames and merges rewrite
+            every entry in one commit.
+            <Show when={taxonomy() && !taxonomy()?.metadata_path}>
+              {" "}Set <code>tags.metadata</code> in .panel.toml to describe tags for the site.
+            </Show>
+          </p>
+        </div>
+        <div class="settings-value-with-action">
+          <button class="btn" onClick={load} disabled={loading()}>
+            {loading() ? "Loading..." : taxonomy() ? "Refresh" : "Audit Tags"}
+          </button>
+        </div>
+      </div>
+
+      <Show when={taxonomy()}>
+        {(t) => (
+          <>
+            <Show when={t().near_duplicates.length > 0}>
+              <label class="settings-label">Possible Duplicates</label>
+              <For each={t().near_duplicates}>
+                {(group) => (
+                  <div class="settings-tag-group">
+                    <span class="settings-path-value">{group.join(" · ")}</span>
+                    <button class="btn" onClick={() => previewGroup(group)} disabled={busy()}>
+                      Merge into "{group[0]}"
+                    </button>
+                  </div>
+                )}
+              </For>
+            </Show>
+
+            <div class="settings-bulk-op">
+              <input
+                class="settings-input"
+                type="text"
+                value={from()}
+                onInput={(e) => {
+                  setFrom(e.currentTarget.value);
+                  setPreview(null);
+                }}
+                placeholder="Tags to rename, comma separated"
+              />
+              <input
+                class="settings-input"
+                type="text"
+                value={to()}
+                onInput={(e) => {
+                  setTo(e.currentTarget.value);
+                  setPreview(null);
+                }}
+                placeholder="New tag"
+              />
+              <button
+                class="btn"
+                onClick={() => runPreview({ from: splitList(from()), to: to().trim() })}
+                disabled={busy()}
+              >
+                Preview
+              </button>
+              <Show when={applicable().length > 0}>
+                <button class="btn btn-primary" onClick={apply} disabled={busy()}>
+                  {`Apply to ${applicable().length} file${applicable().length === 1 ? "" : "s"}`}
+                </button>
+              </Show>
+            </div>
+
+            <Show when={preview()}>
+              {(p) => <DiffList changes={p().changes} empty="No entry has these tags." />}
+            </Show>
+
+            <div class="settings-tag-list">
+              <For each={t().tags}>
+                {(usage) => (
+                  <div class="settings-tag-row">
+                    <span class="settings-tag-name">{usage.tag}</span>
+                    <span class="settings-hint">
+                      {usage.count} ({usage.published} published) · {day(usage.first_used)} – {day(usage.last_used)}
+                    </span>
+                    <Show when={t().metadata_path}>
+                      <input
+                        class="settings-input"
+                        type="text"
+                        value={usage.description ?? ""}
+                        onChange={(e) => describe(usage, e.currentTarget.value)}
+                        placeholder="Description"
+                      />
+                    </Show>
+                  </div>
+                )}
+              </For>
+            </div>
+
+            <Show when={t().unused.length > 0}>
+              <p class="settings-hint">
+                Described but unused: {t().unused.join(", ")}
+              </p>
+            </Show>
+          </>
+        )}
+      </Show>
+    </div>
+  );
+}
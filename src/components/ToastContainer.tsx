@@ -1,4 +1,4 @@
-import { For } from "solid-js";
+import { For, Show } from "solid-js";
 import { toasts, dismissToast } from "../lib/store";
 
 export function ToastContainer() {
@@ -8,6 +8,13 @@ export function ToastContainer() {
         {(toast) => (
           <div class={`toast toast-${toast.type}`}>
             <span class="toast-message">{toast.message}</span>
+            <Show when={toast.action}>
+              {(action) => (
+                <button class="toast-action" onClick={() => action().run()}>
+                  {action().label}
+                </button>
+              )}
+            </Show>
             <button class="toast-dismiss" onClick={() => dismissToast(toast.id)} aria-label="Dismiss notification">&times;</button>
           </div>
         )}
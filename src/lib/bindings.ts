@@ -0,0 +1,954 @@
+// Generated from src-tauri by `UPDATE_BINDINGS=1 cargo test bindings`. Do not edit.
+
+import { invoke } from "./invoke";
+
+// Types
+
+/**
+ * What `export_analytics` should serialize: an already-fetched result, or a
+ * date range from the stored daily history.
+ */
+export type AnalyticsExportSource = { kind: "analytics"; analytics: CfAnalytics } | { kind: "history"; from: string; to: string; engagement: boolean }
+
+export type AnomalyKind = "spike" | "drop"
+
+/**
+ * Application settings for the active site profile (repo path, Cloudflare credentials)
+ * plus the global theme preference and the names of all profiles.
+ */
+export type AppConfig = { repo_path: string | null; theme: string | null; cf_account_id: string | null; cf_project_name: string | null; 
+/**
+ * Masked placeholder when a token is stored; never the token itself.
+ */
+cf_api_token: string | null; cf_domain: string | null; cf_zone_id: string | null; cf_site_tag: string | null; active_profile: string | null; profiles: string[] }
+
+export type AppError = { code: ErrorCode; message: string; 
+/**
+ * Whether retrying the same operation may succeed.
+ */
+retryable: boolean; context: ErrorContext }
+
+/**
+ * A frontmatter edit applied to every entry matching `filter`.
+ */
+export type BulkEdit = { filter?: EntryFilter; 
+/**
+ * Applied in order to each entry's frontmatter.
+ */
+ops: BulkOp[] }
+
+/**
+ * A content file a bulk edit would rewrite.
+ */
+export type BulkEditChange = { 
+/**
+ * Empty, like `slug`, for a file that isn't an entry (the tags metadata file).
+ */
+collection: string; slug: string; 
+/**
+ * Repo-relative path of the file.
+ */
+path: string; 
+/**
+ * Unified diff of the rewrite.
+ */
+diff: string; 
+/**
+ * The file has uncommitted edits. Applying skips it.
+ */
+uncommitted: boolean }
+
+/**
+ * Result of `apply_bulk_edit`.
+ */
+export type BulkEditResult = { 
+/**
+ * The changes written and committed.
+ */
+applied: BulkEditChange[]; 
+/**
+ * `None` when nothing matched or nothing changed.
+ */
+commit: string | null }
+
+/**
+ * One step of a `BulkEdit`. Keys are top-level frontmatter keys; an op that
+ * doesn't apply to an entry (a missing key, a tag it lacks) leaves it alone.
+ */
+export type BulkOp = 
+/**
+ * Rename `from` to `to`, keeping the value. Skipped where `to` already exists.
+ */
+{ op: "rename_key"; from: string; to: string } | 
+/**
+ * Set `key` to `value`, a single-line YAML value written as is (quote strings).
+ */
+{ op: "set_key"; key: string; value: string } | { op: "remove_key"; key: string } | 
+/**
+ * Replace matches of the regex `pattern` in `key`'s value, inside any
+ * quotes. `replacement` may use `$1`-style groups.
+ */
+{ op: "map_values"; key: string; pattern: string; replacement: string } | 
+/**
+ * Tag ops only edit inline `tags: [...]` lists, adding one if missing.
+ */
+{ op: "add_tags"; tags: string[] } | { op: "remove_tags"; tags: string[] } | 
+/**
+ * Replace each of `from` with `to` where it stands, dropping duplicates.
+ */
+{ op: "rename_tags"; from: string[]; to: string }
+
+/**
+ * Aggregated Cloudflare traffic analytics for a given period.
+ */
+export type CfAnalytics = { period: string; total_requests: number; daily_requests: CfDailyCount[]; top_paths: CfPathCount[]; top_countries: CfCountryCount[]; status_codes: CfStatusCount[]; browsers: CfBrowserCount[]; 
+/**
+ * Deltas against the previous equal-length window, when requested.
+ */
+comparison: CfComparison | null; 
+/**
+ * Slots that deviate strongly from their rolling baseline.
+ */
+anomalies: CfAnomaly[] }
+
+/**
+ * A day (or hour) whose count deviates strongly from its rolling baseline.
+ */
+export type CfAnomaly = { date: string; count: number; baseline: number; 
+/**
+ * Deviation from the baseline in standard deviations (signed).
+ */
+score: number; kind: AnomalyKind }
+
+export type CfBrowserCount = { browser: string; page_views: number }
+
+/**
+ * Period-over-period deltas between the selected window and the one before it.
+ */
+export type CfComparison = { previous_period_start: string; previous_period_end: string; 
+/**
+ * One delta per total: requests, uniques, bytes, cached bytes, cached
+ * requests and threats, in that order.
+ */
+totals: CfDelta[]; paths: CfDelta[]; countries: CfDelta[] }
+
+export type CfCountryCount = { country: string; count: number }
+
+export type CfDailyCount = { date: string; count: number; uniques: number; bytes: number; cached_bytes: number; cached_requests: number; threats: number }
+
+/**
+ * Change in a single count between two periods. `delta_pct` is `None` when
+ * the previous count was zero.
+ */
+export type CfDelta = { key: string; current: number; previous: number; delta: number; delta_pct: number | null }
+
+/**
+ * Info about the last successful Cloudflare Pages deployment.
+ */
+export type CfDeploymentInfo = { deployed_at: string; commit_hash: string | null; commit_message: string | null; status: string; url: string | null }
+
+export type CfPathCount = { path: string; count: number }
+
+export type CfPercentiles = { p50: number; p75: number; p90: number }
+
+export type CfRumDailyCount = { date: string; page_views: number; visits: number }
+
+/**
+ * Page views, visits and performance percentiles for a single path.
+ * Timings are in milliseconds; CLS is unitless.
+ */
+export type CfRumPathMetrics = { path: string; page_views: number; visits: number; lcp: CfPercentiles | null; inp: CfPercentiles | null; cls: CfPercentiles | null; page_load: CfPercentiles | null }
+
+export type CfStatusCount = { status: number; count: number }
+
+/**
+ * Browser-side (RUM) analytics from Cloudflare Web Analytics for a given period.
+ * Unlike `CfAnalytics`, these counts come from the beacon and reflect real readers.
+ */
+export type CfWebAnalytics = { period: string; total_page_views: number; total_visits: number; daily: CfRumDailyCount[]; 
+/**
+ * The most viewed paths only (50 at most), by page views descending.
+ */
+paths: CfRumPathMetrics[] }
+
+/**
+ * How a watched file changed.
+ */
+export type ChangeKind = "created" | "modified" | "deleted"
+
+/**
+ * A content collection: where its entries live and how they are tracked.
+ */
+export type CollectionConfig = { 
+/**
+ * Stable id, surfaced as `ContentEntry.content_type`.
+ */
+id: string; 
+/**
+ * Display name (defaults to the id).
+ */
+label?: string | null; 
+/**
+ * Directory relative to the repo root.
+ */
+dir: string; 
+/**
+ * Entry file patterns relative to `dir`, in priority order. Each starts with
+ * `{slug}`: `"{slug}/index.md"` is one directory per entry, `"{slug}.md"` one file.
+ */
+entry_files?: string[]; 
+/**
+ * What the change-tracking hash covers.
+ */
+hash?: HashMode; 
+/**
+ * Files written for a new entry. Empty means a frontmatter-only entry file.
+ */
+scaffold?: ScaffoldFile[]; 
+/**
+ * Tags every new entry starts with.
+ */
+default_tags?: string[]; 
+/**
+ * Site route for previews, e.g. `"/blog/{slug}"`.
+ */
+route?: string | null }
+
+/**
+ * Prefixes for the commits the panel makes; the title or slug follows after a space.
+ */
+export type CommitPrefixes = { publish: string; unpublish: string; delete: string; migrate: string; bulk_edit: string; tags: string }
+
+/**
+ * A change to one file of a content entry, made outside the panel.
+ * `content-changed` events carry a list of these.
+ */
+export type ContentChange = { slug: string; 
+/**
+ * Collection id, as in `ContentEntry.content_type`.
+ */
+collection: string; kind: ChangeKind; 
+/**
+ * Path relative to the entry root, `/`-separated. Empty when the change is
+ * the root itself: a one-file entry, or a whole entry directory.
+ */
+subfile: string; 
+/**
+ * The changed file is the entry's content file (frontmatter and body).
+ */
+entry_file: boolean; 
+/**
+ * Absolute path of the changed file.
+ */
+path: string }
+
+/**
+ * A single content entry (blog post or app) as surfaced to the frontend.
+ */
+export type ContentEntry = { slug: string; content_type: string; title: string; summary: string; tags: string[]; is_draft: boolean; is_pinned: boolean; created_date: string; publication_date: string | null; author: string | null; image: string | null; file_path: string; modified_date: string | null; published_hash: string | null; has_changed: boolean; 
+/**
+ * Published (or unpublished) in a local commit that hasn't reached the remote yet.
+ */
+pending_push: boolean }
+
+/**
+ * Arguments for creating an entry via `create_entry`, `create_post` or `create_app`.
+ */
+export type CreateEntryArgs = { title: string; slug: string; summary: string; tags: string[] }
+
+/**
+ * Settings for the panel-managed Astro dev server.
+ */
+export type DevServerSettings = { 
+/**
+ * Avoids conflicting with Astro's default 4321.
+ */
+port: number }
+
+/**
+ * Which entries a `BulkEdit` touches. Every set condition must hold; an
+ * empty filter matches every entry.
+ */
+export type EntryFilter = { 
+/**
+ * Collection ids; empty matches all.
+ */
+collections: string[]; 
+/**
+ * Slugs; empty matches all.
+ */
+slugs: string[]; is_draft: boolean | null; is_pinned: boolean | null; 
+/**
+ * Matches entries with any of these tags; empty matches all.
+ */
+tags: string[]; 
+/**
+ * Inclusive bounds on `createdDate`, compared as ISO-8601 strings, so a
+ * bare date like `2024-01-01` works.
+ */
+created_from: string | null; created_to: string | null }
+
+/**
+ * Machine-readable failure category.
+ */
+export type ErrorCode = 
+/**
+ * Malformed or unsafe input (slug, path, URL).
+ */
+"invalid_input" | 
+/**
+ * A setting the operation needs (repo, token, account) is missing.
+ */
+"not_configured" | "not_found" | "already_exists" | 
+/**
+ * A filesystem operation failed.
+ */
+"io" | 
+/**
+ * A git command failed.
+ */
+"git" | 
+/**
+ * The remote rejected the push, even after a rebase.
+ */
+"git_push_rejected" | 
+/**
+ * The pre-commit scan found secrets or oversized files.
+ */
+"scan_blocked" | 
+/**
+ * Cloudflare rejected the API token or its permissions.
+ */
+"cloudflare_auth" | "cloudflare_rate_limited" | 
+/**
+ * Any other Cloudflare API error.
+ */
+"cloudflare" | 
+/**
+ * The request could not reach the server.
+ */
+"network" | 
+/**
+ * The operation was cancelled via `cancel_operation`.
+ */
+"cancelled" | 
+/**
+ * The secret store is passphrase-protected and hasn't been unlocked.
+ */
+"secrets_locked" | 
+/**
+ * The app config file is unreadable; the context path is its backup.
+ */
+"config_corrupt" | "internal"
+
+/**
+ * What the failing operation was acting on.
+ */
+export type ErrorContext = { slug?: string | null; path?: string | null; 
+/**
+ * External command that failed, e.g. `git push`.
+ */
+command?: string | null }
+
+/**
+ * Output format for `export_analytics`.
+ */
+export type ExportFormat = "csv" | "json" | "html"
+
+/**
+ * A file's text with the version it was read at.
+ */
+export type FileContents = { content: string; 
+/**
+ * SHA-256 of the bytes on disk; pass back to `write_file` as `expected_version`.
+ */
+version: string }
+
+/**
+ * Change-tracking scope: the entry file's body, or every file in the entry directory.
+ */
+export type HashMode = "body" | "directory"
+
+/**
+ * Payload of `git-head-moved` events: HEAD now points elsewhere because of a
+ * pull, checkout or commit made outside the panel.
+ */
+export type HeadMoved = { repo_path: string; from: string | null; to: string | null; 
+/**
+ * Checked-out branch, `None` with a detached HEAD.
+ */
+branch: string | null }
+
+/**
+ * Result of a URL health check (dev server or production site).
+ */
+export type HealthStatus = { url: string; ok: boolean; status_code: number | null }
+
+/**
+ * What the secret store's encryption key is derived from.
+ */
+export type KeySource = 
+/**
+ * A random key in a private file next to the store.
+ */
+"keyfile" | "passphrase"
+
+/**
+ * A content file a migration would rewrite.
+ */
+export type MigrationChange = { collection: string; slug: string; 
+/**
+ * Repo-relative path of the file.
+ */
+path: string; 
+/**
+ * Ids of the migrations that change the file, in the order they run.
+ */
+migrations: string[]; 
+/**
+ * Unified diff of the rewrite.
+ */
+diff: string; 
+/**
+ * The file has uncommitted edits. Applying skips it, so those edits are
+ * not committed along with the migration.
+ */
+uncommitted: boolean }
+
+/**
+ * A registered migration.
+ */
+export type MigrationInfo = { id: string; description: string }
+
+/**
+ * What `apply_migrations` would do: each migration in the registry and the
+ * files it would change.
+ */
+export type MigrationPlan = { migrations: MigrationInfo[]; changes: MigrationChange[] }
+
+/**
+ * Result of `apply_migrations`.
+ */
+export type MigrationResult = { 
+/**
+ * The changes written and committed.
+ */
+applied: MigrationChange[]; 
+/**
+ * `None` when there was nothing to apply.
+ */
+commit: string | null }
+
+/**
+ * A repository mutation serialized through the operation queue.
+ */
+export type MutationKind = "publish" | "unpublish" | "set_pinned" | "rollback" | "delete" | 
+/**
+ * Pushing queued commits; `slug` is empty.
+ */
+"push" | 
+/**
+ * Applying content migrations; `slug` is empty.
+ */
+"migrate" | 
+/**
+ * Applying a bulk frontmatter edit; `slug` is empty.
+ */
+"bulk_edit" | 
+/**
+ * Renaming or merging tags, or editing a tag description; `slug` is empty.
+ */
+"tags"
+
+/**
+ * Payload of an `operation-progress` event.
+ */
+export type OperationProgress = { 
+/**
+ * The operation id the command was started with.
+ */
+id: string; step: OperationStep; current: number | null; total: number | null }
+
+/**
+ * A repository's operation queue, oldest first. Also the payload of
+ * `operation-queue-changed` events.
+ */
+export type OperationQueue = { repo_path: string; mutations: QueuedMutation[] }
+
+/**
+ * A step of a long-running operation, reported via `operation-progress` events.
+ */
+export type OperationStep = "scanning" | "hashing" | "writing" | "deleting" | "committing" | "pushing" | 
+/**
+ * The first push was rejected; pulling with rebase before pushing again.
+ */
+"rebasing" | 
+/**
+ * Running a Cloudflare query, `current` of `total`.
+ */
+"querying"
+
+/**
+ * A local commit waiting to be pushed, kept across restarts.
+ */
+export type PendingPush = { repo_path: string; commit: string; slug: string; kind: MutationKind; committed_at: string; 
+/**
+ * Push attempts that failed so far.
+ */
+attempts: number; last_error: string | null }
+
+/**
+ * Defaults applied to newly created posts.
+ */
+export type PostDefaults = { author: string | null }
+
+/**
+ * Payload of `pending-pushes-changed` events: the queue of `repo_path`
+ * changed, or a background retry failed with `error`. `repo_path` is `None`
+ * when the queue file itself couldn't be read.
+ */
+export type PushQueueChanged = { repo_path: string | null; error: AppError | null }
+
+/**
+ * A mutation waiting for, or holding, its repository's turn.
+ */
+export type QueuedMutation = { kind: MutationKind; slug: string; 
+/**
+ * False while an earlier mutation of the same repository still runs.
+ */
+running: boolean; queued_at: string }
+
+/**
+ * Per-repository settings read from `.panel.toml` at the site repo root.
+ * Every field has a default, so the file (and each table in it) is optional.
+ */
+export type RepoConfig = { 
+/**
+ * Content collections. Entries here override the built-in `post` and `app`
+ * collections with the same id; other ids are added.
+ */
+collections: CollectionConfig[]; dev_server: DevServerSettings; posts: PostDefaults; commit: CommitPrefixes; scan: ScanSettings; watch: WatchSettings; tags: TagSettings }
+
+/**
+ * One file of a new-entry scaffold.
+ */
+export type ScaffoldFile = { 
+/**
+ * Path relative to the collection dir, containing `{slug}`.
+ */
+path: string; 
+/**
+ * Repo-relative template file, or `builtin:<name>`.
+ */
+template: string }
+
+/**
+ * Pre-commit inspection of everything the panel is about to commit.
+ */
+export type ScanSettings = { enabled: boolean; 
+/**
+ * Files larger than this (in MB) block the commit.
+ */
+max_file_mb: number; 
+/**
+ * Flag random-looking tokens in addition to known secret formats.
+ */
+entropy: boolean; 
+/**
+ * Repo-relative path prefixes that are never scanned.
+ */
+ignore: string[] }
+
+/**
+ * Payload of the per-channel events (`schema-changed`, ...): files in a watch
+ * scope changed outside the panel.
+ */
+export type ScopeChange = { repo_path: string; channel: WatchChannel; 
+/**
+ * Repo-relative, `/`-separated.
+ */
+paths: string[] }
+
+/**
+ * State of the encrypted secret store.
+ */
+export type SecretsStatus = { key_source: KeySource; 
+/**
+ * False while a passphrase-protected store is waiting for `unlock_secrets`.
+ */
+unlocked: boolean }
+
+/**
+ * A stored autosave snapshot of a content file.
+ */
+export type SnapshotInfo = { id: string; 
+/**
+ * Absolute path of the file the snapshot belongs to.
+ */
+path: string; 
+/**
+ * RFC 3339 timestamp.
+ */
+taken_at: string; 
+/**
+ * Size of the snapshotted contents in bytes.
+ */
+size: number }
+
+/**
+ * Rename tags: every tag in `from` becomes `to`. Merging is renaming several
+ * tags to one.
+ */
+export type TagMerge = { from: string[]; to: string }
+
+/**
+ * Tag taxonomy settings.
+ */
+export type TagSettings = { 
+/**
+ * Repo-relative path of a JSON file describing tags, kept up to date by
+ * the panel for the site's `/tags` pages. Unset: descriptions are off.
+ */
+metadata: string | null }
+
+/**
+ * How one tag is used across all collections.
+ */
+export type TagUsage = { tag: string; count: number; 
+/**
+ * Entries with the tag that are published.
+ */
+published: number; 
+/**
+ * Earliest and latest `createdDate` among entries with the tag.
+ */
+first_used: string | null; last_used: string | null; description: string | null }
+
+/**
+ * Every tag in use, how it is used, and tags that look like the same one.
+ */
+export type Taxonomy = { 
+/**
+ * Most used first.
+ */
+tags: TagUsage[]; 
+/**
+ * Groups of tags that differ only in case, separators, a plural `s` or a
+ * suffix like `-lang`. Each group and the list are ordered most used first.
+ */
+near_duplicates: string[][]; 
+/**
+ * Tags described in the metadata file that no entry uses.
+ */
+unused: string[]; 
+/**
+ * `TagSettings::metadata`, when descriptions are on.
+ */
+metadata_path: string | null }
+
+/**
+ * What a non-content change may call for; each has its own event.
+ */
+export type WatchChannel = 
+/**
+ * Site components and layouts: previews may need a reload.
+ */
+"components" | 
+/**
+ * The content collection schema: frontmatter validation may change.
+ */
+"schema" | 
+/**
+ * `astro.config.*`: the dev server needs a restart.
+ */
+"astro_config" | 
+/**
+ * `package.json` and lockfiles: dependencies may need installing.
+ */
+"dependencies"
+
+/**
+ * Repo-relative file globs reported on one channel. `*` matches within a path
+ * segment and `**` across segments; the first segment must be literal unless
+ * it is the whole pattern, so the repository root is never watched recursively.
+ */
+export type WatchScope = { channel: WatchChannel; paths: string[] }
+
+/**
+ * Files outside the collections that the watcher follows because they affect
+ * the site build.
+ */
+export type WatchSettings = { 
+/**
+ * Setting this replaces the default scopes rather than adding to them.
+ */
+scopes: WatchScope[]; 
+/**
+ * Skip changes to files `.gitignore` excludes (build output, caches).
+ */
+respect_gitignore: boolean }
+
+/**
+ * Outcome of a versioned write. A conflict means the file changed on disk
+ * since the caller read it; nothing was written.
+ */
+export type WriteResult = { status: "written"; version: string } | { status: "conflict"; expected: string; actual: string | null; content: string | null }
+
+// Commands
+
+export function getConfig(): Promise<AppConfig> {
+  return invoke("get_config");
+}
+
+export function setConfig(config: AppConfig): Promise<void> {
+  return invoke("set_config", { config });
+}
+
+export function resetConfig(): Promise<AppConfig> {
+  return invoke("reset_config");
+}
+
+export function switchProfile(name: string): Promise<AppConfig> {
+  return invoke("switch_profile", { name });
+}
+
+export function createProfile(name: string): Promise<AppConfig> {
+  return invoke("create_profile", { name });
+}
+
+export function deleteProfile(name: string): Promise<AppConfig> {
+  return invoke("delete_profile", { name });
+}
+
+/** Store or rotate the Cloudflare API token for the active profile. */
+export function setCfApiToken(token: string): Promise<AppConfig> {
+  return invoke("set_cf_api_token", { token });
+}
+
+export function clearCfApiToken(): Promise<AppConfig> {
+  return invoke("clear_cf_api_token");
+}
+
+export function getSecretsStatus(): Promise<SecretsStatus> {
+  return invoke("get_secrets_status");
+}
+
+export function unlockSecrets(passphrase: string): Promise<SecretsStatus> {
+  return invoke("unlock_secrets", { passphrase });
+}
+
+/** Re-encrypt all secrets under a passphrase, or under a key file when `None`. */
+export function rekeySecrets(passphrase?: string | null): Promise<SecretsStatus> {
+  return invoke("rekey_secrets", { passphrase: passphrase ?? null });
+}
+
+export function validateRepoPath(path: string): Promise<boolean> {
+  return invoke("validate_repo_path", { path });
+}
+
+/** Settings from the repo's `.panel.toml` (defaults if the file is absent). */
+export function getRepoConfig(repoPath: string): Promise<RepoConfig> {
+  return invoke("get_repo_config", { repoPath });
+}
+
+export function listContent(repoPath: string): Promise<ContentEntry[]> {
+  return invoke("list_content", { repoPath });
+}
+
+export function readFile(path: string): Promise<FileContents> {
+  return invoke("read_file", { path });
+}
+
+/**
+ * Write a file, refusing if it no longer matches `expected_version` (as returned
+ * by `read_file`). Without an expected version the write is unconditional.
+ */
+export function writeFile(path: string, content: string, expectedVersion?: string | null): Promise<WriteResult> {
+  return invoke("write_file", { path, content, expectedVersion: expectedVersion ?? null });
+}
+
+export function listSnapshots(path: string): Promise<SnapshotInfo[]> {
+  return invoke("list_snapshots", { path });
+}
+
+/** Restore a snapshot over its file, returning the restored contents. */
+export function restoreSnapshot(path: string, id: string): Promise<FileContents> {
+  return invoke("restore_snapshot", { path, id });
+}
+
+/** Snapshots newer than their files on disk — unsaved work from an interrupted session. */
+export function getRecoverableSnapshots(): Promise<SnapshotInfo[]> {
+  return invoke("get_recoverable_snapshots");
+}
+
+export function createPost(repoPath: string, args: CreateEntryArgs): Promise<ContentEntry> {
+  return invoke("create_post", { repoPath, args });
+}
+
+export function createApp(repoPath: string, args: CreateEntryArgs): Promise<ContentEntry> {
+  return invoke("create_app", { repoPath, args });
+}
+
+/** Create an entry in any configured collection. */
+export function createEntry(repoPath: string, collection: string, args: CreateEntryArgs): Promise<ContentEntry> {
+  return invoke("create_entry", { repoPath, collection, args });
+}
+
+/** Delete an entry, reporting progress under `operation_id`. */
+export function deleteContent(repoPath: string, slug: string, operationId?: string | null): Promise<void> {
+  return invoke("delete_content", { repoPath, slug, operationId: operationId ?? null });
+}
+
+/**
+ * Publish an entry, reporting progress under `operation_id`. Cancelling stops
+ * it before the entry file is written, or after the commit and before the push.
+ * A push that fails leaves the commit queued and the entry `pending_push`.
+ */
+export function publish(repoPath: string, slug: string, allowFindings?: boolean | null, operationId?: string | null): Promise<ContentEntry> {
+  return invoke("publish", { repoPath, slug, allowFindings: allowFindings ?? null, operationId: operationId ?? null });
+}
+
+/**
+ * Unpublish an entry, reporting progress under `operation_id`. Like `publish`,
+ * the scan runs before the entry is written and `allow_findings` overrides it.
+ */
+export function unpublish(repoPath: string, slug: string, allowFindings?: boolean | null, operationId?: string | null): Promise<ContentEntry> {
+  return invoke("unpublish", { repoPath, slug, allowFindings: allowFindings ?? null, operationId: operationId ?? null });
+}
+
+export function rollback(repoPath: string, slug: string): Promise<ContentEntry> {
+  return invoke("rollback", { repoPath, slug });
+}
+
+/**
+ * Ask a running operation to stop at its next safe point. Returns false if
+ * it already finished.
+ */
+export function cancelOperation(id: string): Promise<boolean> {
+  return invoke("cancel_operation", { id });
+}
+
+/**
+ * Mutations queued or running for `repo_path`, oldest first. Changes are also
+ * pushed as `operation-queue-changed` events.
+ */
+export function getOperationQueue(repoPath: string): Promise<OperationQueue> {
+  return invoke("get_operation_queue", { repoPath });
+}
+
+/** Commits waiting to be pushed in `repo_path`, oldest first. */
+export function listPendingPushes(repoPath: string): Promise<PendingPush[]> {
+  return invoke("list_pending_pushes", { repoPath });
+}
+
+/**
+ * Push queued commits now instead of waiting for the next automatic retry,
+ * e.g. when the network comes back. Returns what is still queued.
+ */
+export function retryPendingPushes(repoPath: string, operationId?: string | null): Promise<PendingPush[]> {
+  return invoke("retry_pending_pushes", { repoPath, operationId: operationId ?? null });
+}
+
+/**
+ * Every registered migration and the files it would change, with diffs.
+ * Writes nothing.
+ */
+export function planMigrations(repoPath: string): Promise<MigrationPlan> {
+  return invoke("plan_migrations", { repoPath });
+}
+
+/**
+ * Apply the current plan in one commit, reporting progress under
+ * `operation_id`. Files with uncommitted edits are skipped.
+ */
+export function applyMigrations(repoPath: string, operationId?: string | null): Promise<MigrationResult> {
+  return invoke("apply_migrations", { repoPath, operationId: operationId ?? null });
+}
+
+/** Every file `edit` would change, with its diff. Writes nothing. */
+export function planBulkEdit(repoPath: string, edit: BulkEdit): Promise<BulkEditChange[]> {
+  return invoke("plan_bulk_edit", { repoPath, edit });
+}
+
+/**
+ * Apply `edit` in one commit, reporting progress under `operation_id`.
+ * Files with uncommitted edits are skipped.
+ */
+export function applyBulkEdit(repoPath: string, edit: BulkEdit, operationId?: string | null): Promise<BulkEditResult> {
+  return invoke("apply_bulk_edit", { repoPath, edit, operationId: operationId ?? null });
+}
+
+export function getTaxonomy(repoPath: string): Promise<Taxonomy> {
+  return invoke("get_taxonomy", { repoPath });
+}
+
+/** Every file a tag rename or merge would change, with its diff. Writes nothing. */
+export function planTagMerge(repoPath: string, merge: TagMerge): Promise<BulkEditChange[]> {
+  return invoke("plan_tag_merge", { repoPath, merge });
+}
+
+/**
+ * Rename or merge tags in one commit, reporting progress under `operation_id`.
+ * Files with uncommitted edits are skipped.
+ */
+export function applyTagMerge(repoPath: string, merge: TagMerge, operationId?: string | null): Promise<BulkEditResult> {
+  return invoke("apply_tag_merge", { repoPath, merge, operationId: operationId ?? null });
+}
+
+/** Set or clear a tag's description in the tags metadata file and commit it. */
+export function setTagDescription(repoPath: string, tag: string, description: string, operationId?: string | null): Promise<string | null> {
+  return invoke("set_tag_description", { repoPath, tag, description, operationId: operationId ?? null });
+}
+
+export function setPinned(repoPath: string, slug: string, pinned: boolean): Promise<ContentEntry> {
+  return invoke("set_pinned", { repoPath, slug, pinned });
+}
+
+export function gitStatus(repoPath: string): Promise<string> {
+  return invoke("git_status", { repoPath });
+}
+
+export function openInVscode(path: string): Promise<void> {
+  return invoke("open_in_vscode", { path });
+}
+
+export function startWatcher(repoPath: string): Promise<void> {
+  return invoke("start_watcher", { repoPath });
+}
+
+export function stopWatcher(): Promise<void> {
+  return invoke("stop_watcher");
+}
+
+export function startDevServer(repoPath: string): Promise<void> {
+  return invoke("start_dev_server", { repoPath });
+}
+
+export function stopDevServer(): Promise<void> {
+  return invoke("stop_dev_server");
+}
+
+export function checkUrlHealth(url: string): Promise<HealthStatus> {
+  return invoke("check_url_health", { url });
+}
+
+export function fetchLastDeployment(): Promise<CfDeploymentInfo> {
+  return invoke("fetch_last_deployment");
+}
+
+/** Fetch traffic analytics, reporting each GraphQL query under `operation_id`. */
+export function fetchAnalytics(days: number, engagement: boolean, compare: boolean, operationId?: string | null): Promise<CfAnalytics> {
+  return invoke("fetch_analytics", { days, engagement, compare, operationId: operationId ?? null });
+}
+
+export function fetchWebAnalytics(days: number): Promise<CfWebAnalytics> {
+  return invoke("fetch_web_analytics", { days });
+}
+
+export function exportAnalytics(source: AnalyticsExportSource, format: ExportFormat, dir: string): Promise<string[]> {
+  return invoke("export_analytics", { source, format, dir });
+}
+
+export function testCfConnection(): Promise<string> {
+  return invoke("test_cf_connection");
+}
@@ -0,0 +1,41 @@
+/**
+ * Tauri `invoke` with rejections turned into typed `CommandError`s.
+ * The generated wrappers in `bindings.ts` call through this.
+ */
+import { invoke as tauriInvoke, type InvokeArgs } from "@tauri-apps/api/core";
+import type { AppError, ErrorCode, ErrorContext } from "./bindings";
+
+/** A rejected command. Stringifies to its message, so `${e}` in toasts reads as before. */
+export class CommandError extends Error {
+  constructor(
+    readonly code: ErrorCode,
+    message: string,
+    /** Whether retrying the same operation may succeed. */
+    readonly retryable: boolean,
+    readonly context: ErrorContext,
+  ) {
+    super(message);
+    this.name = "CommandError";
+  }
+
+  toString(): string {
+    return this.message;
+  }
+}
+
+/** Whether `e` is a command error, optionally with the given code. */
+export function isCommandError(e: unknown, code?: ErrorCode): e is CommandError {
+  return e instanceof CommandError && (code === undefined || e.code === code);
+}
+
+export async function invoke<T>(cmd: string, args?: InvokeArgs): Promise<T> {
+  try {
+    return await tauriInvoke<T>(cmd, args);
+  } catch (e) {
+    if (e && typeof e === "object" && "code" in e && "message" in e) {
+      const err = e as AppError;
+      throw new CommandError(err.code, err.message, err.retryable, err.context);
+    }
+    throw new CommandError("internal", String(e), false, {});
+  }
+}
@@ -76,6 +76,7 @@ export async function getCachedAnalytics(
   days: number,
   engagement: boolean,
   onUpdate: (a: CfAnalytics) => void,
+  operationId?: string,
 ): Promise<CfAnalytics | null> {
   const key = analyticsKey(days, engagement);
   const cached = analyticsCache.get(key);
@@ -88,7 +89,7 @@ export async function getCachedAnalytics(
   // Refresh if stale
   if (isStale(cached, ANALYTICS_TTL)) {
     try {
-      const data = await fetchAnalytics(days, engagement);
+      const data = await fetchAnalytics(days, engagement, false, operationId);
       analyticsCache.set(key, { data, fetchedAt: Date.now() });
       onUpdate(data);
       return data;
@@ -109,8 +110,9 @@ export async function getCachedAnalytics(
 export async function forceAnalytics(
   days: number,
   engagement: boolean,
+  operationId?: string,
 ): Promise<CfAnalytics> {
-  const data = await fetchAnalytics(days, engagement);
+  const data = await fetchAnalytics(days, engagement, false, operationId);
   analyticsCache.set(analyticsKey(days, engagement), { data, fetchedAt: Date.now() });
   return data;
 }
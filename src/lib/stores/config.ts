@@ -1,42 +1,106 @@
 import { reconcile } from "solid-js/store";
+import { ask } from "@tauri-apps/plugin-dialog";
 import type { AppConfig } from "../commands";
 import {
+  clearCfApiToken,
+  createProfile as createProfileCmd,
+  deleteProfile as deleteProfileCmd,
   getConfig,
+  getRecoverableSnapshots,
+  getRepoConfig,
+  isCommandError,
   listContent,
+  resetConfig,
+  setCfApiToken,
   setConfig as saveConfig,
   startDevServer,
+  switchProfile as switchProfileCmd,
 } from "../commands";
 import { state, setState } from "./state";
 import { refreshEntries } from "./content";
 import { setupWatcher } from "./watcher";
+import { setupOperationQueue } from "./queue";
+import { setupPendingPushes } from "./pushes";
 import { initTheme } from "./theme";
 import { addToast } from "./notifications";
 import { startHealthPolling, recheckHealth } from "./health";
 
+/**
+ * Load the config. If the backend reports it corrupt (it has already backed the
+ * file up), offer an explicit reset instead of silently using defaults. Any
+ * other failure, such as a file that can't be read right now, is only reported:
+ * nothing was backed up, so resetting could lose settings.
+ */
+async function loadConfig(): Promise<AppConfig | null> {
+  try {
+    return await getConfig();
+  } catch (err) {
+    const message = err instanceof Error ? err.message : String(err);
+    addToast(`Config error: ${message}`, "error");
+    if (!isCommandError(err, "config_corrupt")) return null;
+    const reset = await ask(`${message}\n\nReset all settings to defaults?`, {
+      title: "Settings could not be loaded",
+      kind: "warning",
+    });
+    if (!reset) return null;
+    const cfg = await resetConfig();
+    addToast("Settings reset to defaults");
+    return cfg;
+  }
+}
+
+/** Load the repo's `.panel.toml`; a malformed file is reported and defaults apply. */
+async function loadRepoConfig(repoPath: string | null) {
+  if (!repoPath) {
+    setState("repoConfig", null);
+    return;
+  }
+  try {
+    setState("repoConfig", await getRepoConfig(repoPath));
+  } catch (err) {
+    setState("repoConfig", null);
+    addToast(err instanceof Error ? err.message : String(err), "error");
+  }
+}
+
+/** Surface autosaves left newer than their files by an interrupted session. */
+async function detectRecoverable() {
+  try {
+    const found = await getRecoverableSnapshots();
+    setState("recoverable", found);
+    if (found.length > 0) {
+      const n = found.length;
+      addToast(`${n} ${n === 1 ? "entry has" : "entries have"} unsaved changes from a previous session. Open ${n === 1 ? "it" : "them"} to restore.`, "warn");
+    }
+  } catch { /* snapshots are best-effort */ }
+}
+
 export async function initApp() {
+  const cfg = await loadConfig();
+  if (!cfg) return;
   try {
-    const cfg = await getConfig();
     setState("config", cfg);
     initTheme(cfg.theme);
 
+    await loadRepoConfig(cfg.repo_path);
     if (cfg.repo_path) {
       const entries = await listContent(cfg.repo_path);
       setState("entries", reconcile(entries));
       setupWatcher(cfg.repo_path, refreshEntries);
+      setupOperationQueue(cfg.repo_path).catch(() => {});
+      setupPendingPushes(cfg.repo_path).catch(() => {});
       startDevServer(cfg.repo_path)
         .then(() => setTimeout(recheckHealth, 5_000))
         .catch((err) => {
           addToast(`Dev server failed: ${err}`, "error");
         });
+      await detectRecoverable();
     }
 
     // Start health polling immediately on app open, then every 5 minutes
     startHealthPolling();
   } catch (err) {
-    // First launch or corrupt config — use defaults
-    if (err instanceof Error && !err.message.includes("No such file")) {
-      addToast(`Config error: ${err.message}`, "error");
-    }
+    addToast(`Failed to load content: ${err instanceof Error ? err.message : String(err)}`, "error");
   }
 }
 
@@ -47,11 +111,54 @@ export async function updateConfig(updates: Partial<AppConfig>) {
 
   // Refresh entries and restart watcher if repo path changed
   if (updates.repo_path) {
+    await loadRepoConfig(updates.repo_path);
     const entries = await listContent(updates.repo_path);
     setState("entries", reconcile(entries));
     setupWatcher(updates.repo_path, refreshEntries);
+    setupOperationQueue(updates.repo_path).catch(() => {});
+    setupPendingPushes(updates.repo_path).catch(() => {});
     startDevServer(updates.repo_path)
       .then(() => setTimeout(recheckHealth, 5_000))
       .catch(() => {});
   }
 }
+
+/**
+ * Store, rotate or (with an empty string) clear the active profile's API token.
+ * The backend keeps it encrypted; state only ever holds the masked placeholder.
+ */
+export async function updateApiToken(token: string) {
+  const cfg = token.trim() ? await setCfApiToken(token) : await clearCfApiToken();
+  setState("config", "cf_api_token", cfg.cf_api_token);
+}
+
+/**
+ * Apply a config returned by a profile switch. The backend has already
+ * restarted the watcher and dev server; reload entries and re-listen.
+ */
+async function applyProfile(cfg: AppConfig) {
+  setState("config", cfg);
+  await loadRepoConfig(cfg.repo_path);
+  if (cfg.repo_path) {
+    const entries = await listContent(cfg.repo_path);
+    setState("entries", reconcile(entries));
+    await setupWatcher(cfg.repo_path, refreshEntries);
+    setupOperationQueue(cfg.repo_path).catch(() => {});
+    setupPendingPushes(cfg.repo_path).catch(() => {});
+    setTimeout(recheckHealth, 5_000);
+  } else {
+    setState("entries", reconcile([]));
+  }
+}
+
+export async function switchProfile(name: string) {
+  await applyProfile(await switchProfileCmd(name));
+}
+
+export async function createProfile(name: string) {
+  await applyProfile(await createProfileCmd(name));
+}
+
+export async function deleteProfile(name: string) {
+  await applyProfile(await deleteProfileCmd(name));
+}
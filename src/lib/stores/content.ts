@@ -13,7 +13,6 @@ import {
   rollback,
 } from "../commands";
 import { state, setState } from "./state";
-import { suppressFsChange } from "./watcher";
 
 export async function refreshEntries() {
   const repoPath = state.config.repo_path;
@@ -22,28 +21,37 @@ export async function refreshEntries() {
   setState("entries", reconcile(entries));
 }
 
-export async function publishEntry(slug: string): Promise<ContentEntry> {
+/**
+ * Publish an entry. `allowFindings` overrides a block from the pre-commit scan;
+ * `operationId` ties its progress events to a `trackOperation` call.
+ */
+export async function publishEntry(
+  slug: string,
+  allowFindings = false,
+  operationId?: string,
+): Promise<ContentEntry> {
   const repoPath = state.config.repo_path;
   if (!repoPath) throw new Error("No repo configured");
-  suppressFsChange();
   try {
-    const updated = await publish(repoPath, slug);
+    const updated = await publish(repoPath, slug, allowFindings, operationId);
     return updated;
   } finally {
-    suppressFsChange();
     await refreshEntries();
   }
 }
 
-export async function unpublishEntry(slug: string): Promise<ContentEntry> {
+/** Unpublish an entry; `allowFindings` and `operationId` as for `publishEntry`. */
+export async function unpublishEntry(
+  slug: string,
+  allowFindings = false,
+  operationId?: string,
+): Promise<ContentEntry> {
   const repoPath = state.config.repo_path;
   if (!repoPath) throw new Error("No repo configured");
-  suppressFsChange();
   try {
-    const updated = await unpublish(repoPath, slug);
+    const updated = await unpublish(repoPath, slug, allowFindings, operationId);
     return updated;
   } finally {
-    suppressFsChange();
     await refreshEntries();
   }
 }
@@ -51,12 +59,10 @@ export async function unpublishEntry(slug: string): Promise<ContentEntry> {
 export async function setPinnedEntry(slug: string, pinned: boolean): Promise<ContentEntry> {
   const repoPath = state.config.repo_path;
   if (!repoPath) throw new Error("No repo configured");
-  suppressFsChange();
   try {
     const updated = await setPinned(repoPath, slug, pinned);
     return updated;
   } finally {
-    suppressFsChange();
     await refreshEntries();
   }
 }
@@ -64,24 +70,20 @@ export async function setPinnedEntry(slug: string, pinned: boolean): Promise<Con
 export async function rollbackEntry(slug: string): Promise<ContentEntry> {
   const repoPath = state.config.repo_path;
   if (!repoPath) throw new Error("No repo configured");
-  suppressFsChange();
   try {
     const updated = await rollback(repoPath, slug);
     return updated;
   } finally {
-    suppressFsChange();
     await refreshEntries();
   }
 }
 
-export async function deleteEntry(slug: string): Promise<void> {
+export async function deleteEntry(slug: string, operationId?: string): Promise<void> {
   const repoPath = state.config.repo_path;
   if (!repoPath) throw new Error("No repo configured");
-  suppressFsChange();
   try {
-    await deleteContent(repoPath, slug);
+    await deleteContent(repoPath, slug, operationId);
   } finally {
-    suppressFsChange();
     await refreshEntries();
     setState("view", { kind: "library" });
   }
@@ -1,6 +1,7 @@
 import { createSignal } from "solid-js";
-import { checkUrlHealth, DEV_SERVER_ORIGIN } from "../commands";
+import { checkUrlHealth } from "../commands";
 import type { HealthStatus } from "../commands";
+import { devServerOrigin } from "./state";
 
 const HEALTH_TTL = 300_000; // 5 minutes
 
@@ -10,7 +11,7 @@ const [prodHealth, setProdHealth] = createSignal<HealthStatus | null>(null);
 let intervalId: ReturnType<typeof setInterval> | null = null;
 
 async function pollHealth() {
-  checkUrlHealth(DEV_SERVER_ORIGIN)
+  checkUrlHealth(devServerOrigin())
     .then((h) => setDevHealth(h))
     .catch(() => {});
   checkUrlHealth("https://fpl0.io")
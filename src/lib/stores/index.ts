@@ -6,14 +6,17 @@
  * not by value — the entries array is the single source of truth.
  */
 
-export { state, setState, activeEntry } from "./state";
+export { state, setState, activeEntry, devServerOrigin, collections, collectionLabel, previewUrlFor } from "./state";
 export type { View, AppState } from "./state";
 export { navigate, openEntry, openEntryBySlug, confirmNavigation, cancelNavigation, setNavigationGuard } from "./navigation";
 export { refreshEntries, publishEntry, unpublishEntry, rollbackEntry, setPinnedEntry, deleteEntry, patchEntry } from "./content";
-export { lastExternalChange, suppressFsChange, clearExternalChange, setupWatcher } from "./watcher";
-export { initApp, updateConfig } from "./config";
+export { lastExternalChange, clearExternalChange, lastComponentChange, setupWatcher } from "./watcher";
+export { initApp, updateConfig, updateApiToken, switchProfile, createProfile, deleteProfile } from "./config";
 export { toggleTheme, initTheme } from "./theme";
 export { toasts, addToast, updateToast, dismissToast } from "./notifications";
-export type { Toast } from "./notifications";
+export type { Toast, ToastAction } from "./notifications";
+export { operationQueue, setupOperationQueue } from "./queue";
+export { pendingPushes, setupPendingPushes, retryPushes } from "./pushes";
+export { trackOperation, toastProgress, describeStep, cancelTrackedOperation } from "./operations";
 export { toggleSearch, closeSearch } from "./search";
 export { devHealth, prodHealth, startHealthPolling, recheckHealth } from "./health";
@@ -23,7 +23,7 @@ export function openEntry(entry: ContentEntry) {
   }
 }
 
-export function openEntryBySlug(slug: string, contentType: "post" | "app") {
+export function openEntryBySlug(slug: string, contentType: string) {
   if (contentType === "app") {
     navigate({ kind: "app-detail", slug });
   } else {
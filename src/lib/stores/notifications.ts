@@ -1,6 +1,7 @@
 /**
  * Toast notification system — ephemeral status messages.
  * Toasts auto-dismiss after 3 seconds and can be updated in-place or dismissed early.
+ * A toast with an action (e.g. Cancel) stays up until it is updated without one.
  */
 import { createSignal } from "solid-js";
 
@@ -8,6 +9,12 @@ export interface Toast {
   id: number;
   message: string;
   type: "success" | "error" | "warn";
+  action?: ToastAction;
+}
+
+export interface ToastAction {
+  label: string;
+  run: () => void;
 }
 
 let toastId = 0;
@@ -15,9 +22,11 @@ const toastTimers = new Map<number, ReturnType<typeof setTimeout>>();
 const [toasts, setToasts] = createSignal<Toast[]>([]);
 export { toasts };
 
-function scheduleToastDismiss(id: number) {
+function scheduleToastDismiss(id: number, action?: ToastAction) {
   const prev = toastTimers.get(id);
   if (prev) clearTimeout(prev);
+  toastTimers.delete(id);
+  if (action) return;
   toastTimers.set(
     id,
     setTimeout(() => {
@@ -27,17 +36,17 @@ function scheduleToastDismiss(id: number) {
   );
 }
 
-export function addToast(message: string, type: Toast["type"] = "success") {
+export function addToast(message: string, type: Toast["type"] = "success", action?: ToastAction) {
   const id = ++toastId;
-  setToasts((prev) => [...prev, { id, message, type }]);
-  scheduleToastDismiss(id);
+  setToasts((prev) => [...prev, { id, message, type, action }]);
+  scheduleToastDismiss(id, action);
   return id;
 }
 
-/** Update an existing toast in-place (message and/or type), then auto-dismiss. */
-export function updateToast(id: number, message: string, type: Toast["type"] = "success") {
-  setToasts((prev) => prev.map((t) => (t.id === id ? { ...t, message, type } : t)));
-  scheduleToastDismiss(id);
+/** Update an existing toast in-place (message, type and action), then auto-dismiss unless it has an action. */
+export function updateToast(id: number, message: string, type: Toast["type"] = "success", action?: ToastAction) {
+  setToasts((prev) => prev.map((t) => (t.id === id ? { ...t, message, type, action } : t)));
+  scheduleToastDismiss(id, action);
 }
 
 /** Immediately dismiss a toast and clean up its auto-dismiss timer. */
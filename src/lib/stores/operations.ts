@@ -0,0 +1,60 @@
+/**
+ * Long-running operations — progress events and cancellation.
+ *
+ * Commands like publish, delete and analytics take an operation id and emit
+ * `operation-progress` events carrying it. `trackOperation` runs a command
+ * under a fresh id and forwards its progress until the command settles.
+ */
+import { listen } from "@tauri-apps/api/event";
+import type { OperationProgress, OperationStep } from "../commands";
+import { cancelOperation } from "../commands";
+import { updateToast } from "./notifications";
+
+const STEP_LABELS: Record<OperationStep, string> = {
+  scanning: "Scanning",
+  hashing: "Hashing",
+  writing: "Writing",
+  deleting: "Deleting",
+  committing: "Committing",
+  pushing: "Pushing",
+  rebasing: "Rebasing",
+  querying: "Querying",
+};
+
+/** Human-readable step, e.g. "Pushing" or "Querying 3 of 7". */
+export function describeStep(progress: OperationProgress): string {
+  const label = STEP_LABELS[progress.step];
+  if (progress.current == null || progress.total == null) return label;
+  return `${label} ${progress.current} of ${progress.total}`;
+}
+
+/** Run `command` under a new operation id, calling `onProgress` for each step it reports. */
+export async function trackOperation<T>(
+  command: (operationId: string) => Promise<T>,
+  onProgress: (progress: OperationProgress) => void,
+): Promise<T> {
+  const id = crypto.randomUUID();
+  const unlisten = await listen<OperationProgress>("operation-progress", (event) => {
+    if (event.payload.id === id) onProgress(event.payload);
+  });
+  try {
+    return await command(id);
+  } finally {
+    unlisten();
+  }
+}
+
+/** Progress handler that shows each step in toast `tid`, with a Cancel action. */
+export function toastProgress(tid: number, label: string) {
+  return (progress: OperationProgress) => {
+    updateToast(tid, `${label}: ${describeStep(progress)}...`, "warn", {
+      label: "Cancel",
+      run: () => cancelTrackedOperation(progress.id),
+    });
+  };
+}
+
+/** Ask an operation to stop; a no-op if it already finished. */
+export function cancelTrackedOperation(id: string) {
+  cancelOperation(id).catch(() => {});
+}
@@ -0,0 +1,54 @@
+/**
+ * Pending pushes — local commits the backend hasn't pushed yet (e.g. published
+ * while offline). The backend retries on its own; this store mirrors its queue,
+ * refreshes entries when it changes, retries right away when the network comes
+ * back, and reports background retries that fail.
+ */
+import type { UnlistenFn } from "@tauri-apps/api/event";
+import { listen } from "@tauri-apps/api/event";
+import { createSignal } from "solid-js";
+import type { PendingPush, PushQueueChanged } from "../commands";
+import { listPendingPushes, retryPendingPushes } from "../commands";
+import { refreshEntries } from "./content";
+import { addToast } from "./notifications";
+
+const [pendingPushes, setPendingPushes] = createSignal<PendingPush[]>([]);
+export { pendingPushes };
+
+let unlistenFn: UnlistenFn | null = null;
+let onlineHandler: (() => void) | null = null;
+/** Last background failure shown, so a retry failing the same way every minute toasts once. */
+let lastRetryError: string | null = null;
+
+/** Push queued commits of `repoPath` now; the remaining queue replaces the store. */
+export async function retryPushes(repoPath: string) {
+  setPendingPushes(await retryPendingPushes(repoPath));
+}
+
+/** Load the push queue of `repoPath` and follow its changes, replacing any previous repository. */
+export async function setupPendingPushes(repoPath: string) {
+  try {
+    unlistenFn?.();
+  } finally {
+    unlistenFn = null;
+  }
+  if (onlineHandler) window.removeEventListener("online", onlineHandler);
+
+  setPendingPushes(await listPendingPushes(repoPath));
+
+  unlistenFn = await listen<PushQueueChanged>("pending-pushes-changed", async ({ payload }) => {
+    if (payload.repo_path !== null && payload.repo_path !== repoPath) return;
+    if (payload.error) {
+      if (payload.error.message !== lastRetryError) addToast(`Push failed: ${payload.error.message}`, "error");
+      lastRetryError = payload.error.message;
+      return;
+    }
+    setPendingPushes(await listPendingPushes(repoPath).catch(() => []));
+    if (pendingPushes().length === 0) lastRetryError = null;
+    refreshEntries().catch(() => {});
+  });
+  onlineHandler = () => {
+    if (pendingPushes().length > 0) retryPushes(repoPath).catch(() => {});
+  };
+  window.addEventListener("online", onlineHandler);
+}
@@ -0,0 +1,31 @@
+/**
+ * Operation queue — mutations (publish, unpublish, pin, rollback, delete, migrate,
+ * bulk edit, tags) waiting for or holding the repository's turn on the backend.
+ * Follows `operation-queue-changed` events for the active repository.
+ */
+import type { UnlistenFn } from "@tauri-apps/api/event";
+import { listen } from "@tauri-apps/api/event";
+import { createSignal } from "solid-js";
+import type { QueuedMutation, OperationQueue } from "../commands";
+import { getOperationQueue } from "../commands";
+
+const [operationQueue, setOperationQueue] = createSignal<QueuedMutation[]>([]);
+export { operationQueue };
+
+let unlistenFn: UnlistenFn | null = null;
+
+/** Load the queue of `repoPath` and follow its changes, replacing any previous repository. */
+export async function setupOperationQueue(repoPath: string) {
+  try {
+    unlistenFn?.();
+  } finally {
+    unlistenFn = null;
+  }
+
+  const initial = await getOperationQueue(repoPath);
+  setOperationQueue(initial.mutations);
+
+  unlistenFn = await listen<OperationQueue>("operation-queue-changed", (event) => {
+    if (event.payload.repo_path === initial.repo_path) setOperationQueue(event.payload.mutations);
+  });
+}
@@ -1,6 +1,7 @@
 import { createMemo } from "solid-js";
 import { createStore } from "solid-js/store";
-import type { AppConfig, ContentEntry } from "../commands";
+import { DEFAULT_DEV_SERVER_PORT } from "../commands";
+import type { AppConfig, CollectionConfig, ContentEntry, RepoConfig, SnapshotInfo } from "../commands";
 
 export type View =
   | { kind: "list" }
@@ -13,7 +14,11 @@ export type View =
 
 export interface AppState {
   config: AppConfig;
+  /** `.panel.toml` settings of the open repo (null until loaded). */
+  repoConfig: RepoConfig | null;
   entries: ContentEntry[];
+  /** Autosave snapshots newer than their files on disk, found at startup. */
+  recoverable: SnapshotInfo[];
   view: View;
   theme: "light" | "dark";
   searchOpen: boolean;
@@ -22,8 +27,10 @@ export interface AppState {
 }
 
 export const [state, setState] = createStore<AppState>({
-  config: { repo_path: null, theme: null, cf_account_id: null, cf_project_name: null, cf_api_token: null, cf_domain: null, cf_zone_id: null },
+  config: { repo_path: null, theme: null, cf_account_id: null, cf_project_name: null, cf_api_token: null, cf_domain: null, cf_zone_id: null, cf_site_tag: null, active_profile: null, profiles: [] },
+  repoConfig: null,
   entries: [],
+  recoverable: [],
   view: { kind: "list" },
   theme: "light",
   searchOpen: false,
@@ -31,6 +38,26 @@ export const [state, setState] = createStore<AppState>({
   navigationGuardActive: false,
 });
 
+/** Origin of the managed dev server, following the repo's configured port. */
+export const devServerOrigin = createMemo(
+  () => `http://localhost:${state.repoConfig?.dev_server.port ?? DEFAULT_DEV_SERVER_PORT}`,
+);
+
+/** Content collections of the open repo, in configuration order. */
+export const collections = createMemo<CollectionConfig[]>(() => state.repoConfig?.collections ?? []);
+
+/** Display name for a collection id. */
+export function collectionLabel(id: string): string {
+  const c = collections().find((c) => c.id === id);
+  return c?.label ?? id;
+}
+
+/** Dev-server preview URL for an entry, if its collection declares a route. */
+export function previewUrlFor(entry: { slug: string; content_type: string }): string | null {
+  const route = collections().find((c) => c.id === entry.content_type)?.route;
+  return route ? `${devServerOrigin()}${route.replace("{slug}", entry.slug)}` : null;
+}
+
 export const activeEntry = createMemo(() => {
   const v = state.view;
   if (v.kind === "editor" || v.kind === "app-detail") {
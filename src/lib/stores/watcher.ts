@@ -1,45 +1,87 @@
 /**
- * File-system watcher — listens for external content changes via Tauri events.
- * Provides a 1-second suppression window so our own writes don't trigger a
- * false "external change" banner in the editor.
+ * File-system watcher — follows content changes made outside the panel via
+ * Tauri events. The backend resolves each change to its entry and leaves out
+ * the panel's own writes, so nothing here needs to tell them apart.
+ * Changes to site files in the repo's watch scopes arrive per channel and are
+ * answered here: reload previews, re-read entries, or offer a dev server restart.
  */
 import type { UnlistenFn } from "@tauri-apps/api/event";
 import { listen } from "@tauri-apps/api/event";
 import { createSignal } from "solid-js";
-import { startWatcher, stopWatcher } from "../commands";
+import type { ContentChange, HeadMoved, ScopeChange } from "../commands";
+import { startDevServer, startWatcher, stopWatcher } from "../commands";
+import { addToast } from "./notifications";
 
-/** 5s suppression covers the file-watcher debounce plus git operations. */
-const FS_SUPPRESS_MS = 5000;
-let suppressUntil = 0;
-let unlistenFn: UnlistenFn | null = null;
+let unlistenFns: UnlistenFn[] = [];
 
-/** Signal with paths from the most recent external change (or null). */
-const [lastExternalChange, setLastExternalChange] = createSignal<string[] | null>(null);
+/** Signal with the changes from the most recent external change event (or null). */
+const [lastExternalChange, setLastExternalChange] = createSignal<ContentChange[] | null>(null);
 export { lastExternalChange };
 
-/** Suppress FS change events for 1s (covers 500ms debounce + margin). */
-export function suppressFsChange() {
-  suppressUntil = Date.now() + FS_SUPPRESS_MS;
-}
-
 export function clearExternalChange() {
   setLastExternalChange(null);
 }
 
+/** Signal with the most recent change to site components or layouts (or null). */
+const [lastComponentChange, setLastComponentChange] = createSignal<ScopeChange | null>(null);
+export { lastComponentChange };
+
+/** Toast action restarting the panel-managed dev server. */
+function restartAction(repoPath: string) {
+  return {
+    label: "Restart dev server",
+    run: () => {
+      startDevServer(repoPath).catch((e) => addToast(`Failed to restart dev server: ${e}`, "error"));
+    },
+  };
+}
+
+/** Describe a HEAD move for a toast, e.g. "main is now at 1a2b3c4". */
+function describeHeadMove(move: HeadMoved): string {
+  const where = move.branch ?? "Detached HEAD";
+  return move.to ? `${where} is now at ${move.to.slice(0, 7)}` : `${where} has no commits`;
+}
+
 export async function setupWatcher(repoPath: string, onContentChanged: () => void) {
-  // Tear down previous listener
+  // Tear down previous listeners
   try {
-    unlistenFn?.();
+    for (const unlisten of unlistenFns) unlisten();
   } finally {
-    unlistenFn = null;
+    unlistenFns = [];
   }
   await stopWatcher().catch(() => {});
 
   await startWatcher(repoPath);
 
-  unlistenFn = await listen<string[]>("content-changed", (event) => {
-    if (Date.now() < suppressUntil) return;
-    setLastExternalChange(event.payload);
-    onContentChanged();
-  });
+  unlistenFns.push(
+    await listen<ContentChange[]>("content-changed", (event) => {
+      setLastExternalChange(event.payload);
+      onContentChanged();
+    }),
+    await listen<HeadMoved>("git-head-moved", (event) => {
+      if (event.payload.repo_path !== repoPath) return;
+      addToast(`Repository changed outside the panel: ${describeHeadMove(event.payload)}`, "warn");
+      onContentChanged();
+    }),
+    await listen<ScopeChange>("components-changed", (event) => {
+      if (event.payload.repo_path === repoPath) setLastComponentChange(event.payload);
+    }),
+    await listen<ScopeChange>("schema-changed", (event) => {
+      if (event.payload.repo_path !== repoPath) return;
+      addToast("Content schema changed — entries reloaded", "warn");
+      onContentChanged();
+    }),
+    await listen<ScopeChange>("astro-config-changed", (event) => {
+      if (event.payload.repo_path !== repoPath) return;
+      addToast(`${event.payload.paths.join(", ")} changed`, "warn", restartAction(repoPath));
+    }),
+    await listen<ScopeChange>("dependencies-changed", (event) => {
+      if (event.payload.repo_path !== repoPath) return;
+      addToast(
+        `${event.payload.paths.join(", ")} changed — run bun install, then restart the dev server`,
+        "warn",
+        restartAction(repoPath),
+      );
+    }),
+  );
 }
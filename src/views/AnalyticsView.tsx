@@ -5,8 +5,11 @@
  * Toggleable between full traffic metrics and engagement-only (page views).
  */
 import { createSignal, For, Show, onMount } from "solid-js";
-import type { CfAnalytics } from "../lib/commands";
+import { open } from "@tauri-apps/plugin-dialog";
+import type { CfAnalytics, ExportFormat, OperationProgress } from "../lib/commands";
+import { exportAnalytics, isCommandError } from "../lib/commands";
 import { getCachedAnalytics, forceAnalytics } from "../lib/stores/cfcache";
+import { addToast, trackOperation, describeStep, cancelTrackedOperation, navigate } from "../lib/store";
 
 type Period = 1 | 7 | 30;
 type MetricMode = "engagement" | "full";
@@ -140,30 +143,59 @@ export function AnalyticsView() {
   const [analytics, setAnalytics] = createSignal<CfAnalytics | null>(null);
   const [loading, setLoading] = createSignal(true);
   const [error, setError] = createSignal<string | null>(null);
+  // The API token is in a passphrase-protected store that hasn't been unlocked.
+  const [locked, setLocked] = createSignal(false);
   const [period, setPeriod] = createSignal<Period>(1);
   const [metric, setMetric] = createSignal<MetricMode>("engagement");
+  const [progress, setProgress] = createSignal<OperationProgress | null>(null);
 
   // Guards against stale responses when filters are switched rapidly
   let requestGen = 0;
+  // Operation id of the in-flight fetch, cancelled when a newer one starts
+  let inFlight: string | null = null;
+
+  /** Run an analytics fetch as operation `gen`, cancelling the one it supersedes. */
+  function tracked<T>(gen: number, fetch: (operationId: string) => Promise<T>): Promise<T> {
+    if (inFlight) cancelTrackedOperation(inFlight);
+    setProgress(null);
+    return trackOperation(
+      (id) => {
+        inFlight = id;
+        return fetch(id).finally(() => {
+          if (inFlight === id) inFlight = null;
+        });
+      },
+      (p) => {
+        if (gen === requestGen) setProgress(p);
+      },
+    );
+  }
 
   // Initial load: serve cached data instantly, refresh if stale
   onMount(async () => {
     const gen = ++requestGen;
     const eng = metric() === "engagement";
     try {
-      await getCachedAnalytics(period(), eng, (data) => {
-        if (gen === requestGen) {
-          setAnalytics(data);
-          setLoading(false);
-        }
-      });
+      await tracked(gen, (id) =>
+        getCachedAnalytics(period(), eng, (data) => {
+          if (gen === requestGen) {
+            setAnalytics(data);
+            setLoading(false);
+          }
+        }, id),
+      );
     } catch (e) {
-      if (gen === requestGen) setError(e instanceof Error ? e.message : String(e));
+      if (gen === requestGen) fail(e);
     } finally {
       if (gen === requestGen) setLoading(false);
     }
   });
 
+  function fail(e: unknown) {
+    setLocked(isCommandError(e, "secrets_locked"));
+    setError(e instanceof Error ? e.message : String(e));
+  }
+
   // User-triggered filter changes: always hit the network
   async function switchPeriod(p: Period) {
     const gen = ++requestGen;
@@ -171,10 +203,10 @@ export function AnalyticsView() {
     setLoading(true);
     setError(null);
     try {
-      const data = await forceAnalytics(p, metric() === "engagement");
+      const data = await tracked(gen, (id) => forceAnalytics(p, metric() === "engagement", id));
       if (gen === requestGen) setAnalytics(data);
     } catch (e) {
-      if (gen === requestGen) setError(e instanceof Error ? e.message : String(e));
+      if (gen === requestGen) fail(e);
     } finally {
       if (gen === requestGen) setLoading(false);
     }
@@ -186,15 +218,28 @@ export function AnalyticsView() {
     setLoading(true);
     setError(null);
     try {
-      const data = await forceAnalytics(period(), m === "engagement");
+      const data = await tracked(gen, (id) => forceAnalytics(period(), m === "engagement", id));
       if (gen === requestGen) setAnalytics(data);
     } catch (e) {
-      if (gen === requestGen) setError(e instanceof Error ? e.message : String(e));
+      if (gen === requestGen) fail(e);
     } finally {
       if (gen === requestGen) setLoading(false);
     }
   }
 
+  async function exportTo(format: ExportFormat) {
+    const a = analytics();
+    if (!a) return;
+    const dir = await open({ directory: true, multiple: false });
+    if (!dir) return;
+    try {
+      const files = await exportAnalytics({ kind: "analytics", analytics: a }, format, dir as string);
+      addToast(`Exported ${files.length} file${files.length === 1 ? "" : "s"}`);
+    } catch (e) {
+      addToast(e instanceof Error ? e.message : String(e), "error");
+    }
+  }
+
   const metricLabel = () => (metric() === "engagement" ? "page views" : "requests");
   const periodLabel = () => period() === 1 ? "24h" : `${period()}d`;
 
@@ -215,12 +260,25 @@ export function AnalyticsView() {
       <Show when={error()}>
         <div class="analytics-error">
           <p>{error()}</p>
-          <button class="btn" onClick={() => switchPeriod(period())}>
-            Retry
-          </button>
+          <Show
+            when={locked()}
+            fallback={
+              <button class="btn" onClick={() => switchPeriod(period())}>
+                Retry
+              </button>
+            }
+          >
+            <button class="btn" onClick={() => navigate({ kind: "settings" })}>
+              Unlock in Settings
+            </button>
+          </Show>
         </div>
       </Show>
 
+      <Show when={loading() && progress()}>
+        {(p) => <p class="analytics-progress">{describeStep(p())}...</p>}
+      </Show>
+
       <Show when={loading() && !analytics()}>
         <div class="analytics-skeleton">
           <div class="analytics-skeleton-bar" />
@@ -272,6 +330,17 @@ export function AnalyticsView() {
                     30d
                   </button>
                 </div>
+                <div class="filter-group">
+                  <button class="filter-chip" onClick={() => exportTo("csv")}>
+                    CSV
+                  </button>
+                  <button class="filter-chip" onClick={() => exportTo("json")}>
+                    JSON
+                  </button>
+                  <button class="filter-chip" onClick={() => exportTo("html")}>
+                    Report
+                  </button>
+                </div>
               </div>
               <div class="analytics-kpi-strip">
                 <div class="analytics-kpi">
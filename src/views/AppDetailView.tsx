@@ -1,10 +1,13 @@
-import { createSignal, createEffect, onMount, onCleanup, Show } from "solid-js";
+import { createSignal, createEffect, on, onMount, onCleanup, Show } from "solid-js";
 import type { ContentEntry } from "../lib/commands";
-import { openInVscode, readFile, writeFile, DEV_SERVER_ORIGIN } from "../lib/commands";
+import { openInVscode, readFile, writeFile, isCommandError } from "../lib/commands";
 import { setYamlField, splitFrontmatterFromContent } from "../lib/yaml";
 import {
   state,
   activeEntry,
+  devServerOrigin,
+  collections,
+  previewUrlFor,
   navigate,
   publishEntry,
   unpublishEntry,
@@ -14,7 +17,10 @@ import {
   refreshEntries,
   addToast,
   updateToast,
+  trackOperation,
+  toastProgress,
   lastExternalChange,
+  lastComponentChange,
   clearExternalChange,
 } from "../lib/store";
 import { DetailBar } from "../components/Sidebar";
@@ -29,6 +35,7 @@ export function AppDetailView(props: Props) {
   const [showDeleteConfirm, setShowDeleteConfirm] = createSignal(false);
   const [showUnpubConfirm, setShowUnpubConfirm] = createSignal(false);
   const [showRollbackConfirm, setShowRollbackConfirm] = createSignal(false);
+  const [scanReport, setScanReport] = createSignal<{ action: "publish" | "unpublish"; message: string } | null>(null);
   const [publishing, setPublishing] = createSignal(false);
   const [iframeError, setIframeError] = createSignal(false);
   const [previewReady, setPreviewReady] = createSignal(false);
@@ -38,6 +45,8 @@ export function AppDetailView(props: Props) {
 
   // Snapshot file_path at mount so async operations don't crash if entry disappears
   let filePath = "";
+  // Collection of the entry, to match watcher changes by slug
+  let collection = "";
 
   async function checkPreviewAvailable() {
     try {
@@ -52,6 +61,7 @@ export function AppDetailView(props: Props) {
     const e = activeEntry();
     if (!e) { navigate({ kind: "list" }); return; }
     filePath = e.file_path;
+    collection = e.content_type;
     checkPreviewAvailable();
   });
 
@@ -67,13 +77,11 @@ export function AppDetailView(props: Props) {
     }
   });
 
-  // Auto-reload when any file in the app directory changes externally
+  // Auto-reload when any file of this app changes externally
   createEffect(() => {
-    const paths = lastExternalChange();
-    if (!paths || !filePath) return;
-    // filePath is .../apps/{slug}/index.md — match any file in the parent directory
-    const appDir = filePath.replace(/\/index\.md$/, "");
-    if (paths.some((p) => p.startsWith(appDir))) {
+    const changes = lastExternalChange();
+    if (!changes || !collection) return;
+    if (changes.some((c) => c.slug === props.slug && c.collection === collection)) {
       checkPreviewAvailable();
       reloadIframe();
       refreshEntries().catch(() => {});
@@ -81,29 +89,68 @@ export function AppDetailView(props: Props) {
     }
   });
 
-  async function handlePublish() {
+  // Site components and layouts feed the preview too
+  createEffect(on(lastComponentChange, (change) => {
+    if (change && previewReady()) reloadIframe();
+  }, { defer: true }));
+
+  async function handlePublish(allowFindings = false) {
     if (!state.config.repo_path) return;
+    setScanReport(null);
     setPublishing(true);
     const tid = addToast("Publishing...", "warn");
     try {
-      const updated = await publishEntry(props.slug);
-      updateToast(tid, `Published: ${updated.title}`, "success");
+      const updated = await trackOperation(
+        (id) => publishEntry(props.slug, allowFindings, id),
+        toastProgress(tid, "Publishing"),
+      );
+      if (updated.pending_push) {
+        updateToast(tid, `Published locally: ${updated.title}. It will be pushed once the remote is reachable.`, "warn");
+      } else {
+        updateToast(tid, `Published: ${updated.title}`, "success");
+      }
     } catch (e) {
+      if (isCommandError(e, "scan_blocked")) {
+        updateToast(tid, "Publish blocked by the pre-commit scan", "warn");
+        setScanReport({ action: "publish", message: e.message });
+        return;
+      }
+      if (isCommandError(e, "cancelled")) {
+        updateToast(tid, e.message, "warn");
+        return;
+      }
       updateToast(tid, `Publish failed: ${e}`, "error");
     } finally {
       setPublishing(false);
     }
   }
 
-  async function handleUnpublish() {
+  async function handleUnpublish(allowFindings = false) {
     if (!state.config.repo_path) return;
     setShowUnpubConfirm(false);
+    setScanReport(null);
     setPublishing(true);
     const tid = addToast("Unpublishing...", "warn");
     try {
-      const updated = await unpublishEntry(props.slug);
-      updateToast(tid, `Unpublished: ${updated.title}`, "success");
+      const updated = await trackOperation(
+        (id) => unpublishEntry(props.slug, allowFindings, id),
+        toastProgress(tid, "Unpublishing"),
+      );
+      if (updated.pending_push) {
+        updateToast(tid, `Unpublished locally: ${updated.title}. It will be pushed once the remote is reachable.`, "warn");
+      } else {
+        updateToast(tid, `Unpublished: ${updated.title}`, "success");
+      }
     } catch (e) {
+      if (isCommandError(e, "scan_blocked")) {
+        updateToast(tid, "Unpublish blocked by the pre-commit scan", "warn");
+        setScanReport({ action: "unpublish", message: e.message });
+        return;
+      }
+      if (isCommandError(e, "cancelled")) {
+        updateToast(tid, e.message, "warn");
+        return;
+      }
       updateToast(tid, `Unpublish failed: ${e}`, "error");
     } finally {
       setPublishing(false);
@@ -142,9 +189,13 @@ export function AppDetailView(props: Props) {
     setPublishing(true);
     const tid = addToast("Deleting...", "warn");
     try {
-      await deleteEntry(props.slug);
+      await trackOperation((id) => deleteEntry(props.slug, id), toastProgress(tid, "Deleting"));
       updateToast(tid, `Deleted: ${props.slug}`, "success");
     } catch (e) {
+      if (isCommandError(e, "cancelled")) {
+        updateToast(tid, "Delete cancelled", "warn");
+        return;
+      }
       updateToast(tid, `Delete failed: ${e}`, "error");
     } finally {
       setPublishing(false);
@@ -154,7 +205,8 @@ export function AppDetailView(props: Props) {
   async function handleOpenVscode() {
     const repoPath = state.config.repo_path;
     if (!repoPath) return;
-    const appDir = `${repoPath}/src/content/apps/${props.slug}`;
+    const appsDir = collections().find((c) => c.id === "app")?.dir ?? "src/content/apps";
+    const appDir = `${repoPath}/${appsDir}/${props.slug}`;
     try {
       await openInVscode(appDir);
     } catch (e) {
@@ -166,7 +218,7 @@ export function AppDetailView(props: Props) {
     if (!filePath) return;
     setSaveState("saving");
     try {
-      const content = await readFile(filePath);
+      const { content, version } = await readFile(filePath);
       const parts = splitFrontmatterFromContent(content);
       if (!parts) { setSaveState("unsaved"); return; }
 
@@ -174,7 +226,12 @@ export function AppDetailView(props: Props) {
       const newYaml = setYamlField(parts.yaml, field, yamlValue);
 
       const newContent = `${parts.prefix}${newYaml}${parts.suffix}${parts.rest}`;
-      await writeFile(filePath, newContent);
+      const result = await writeFile(filePath, newContent, version);
+      if (result.status === "conflict") {
+        setSaveState("unsaved");
+        addToast("Not saved: the file changed on disk while updating. Try again.", "warn");
+        return;
+      }
       await refreshEntries();
       setSaveState("saved");
     } catch {
@@ -183,14 +240,15 @@ export function AppDetailView(props: Props) {
   }
 
   function appPreviewUrl() {
-    return `${DEV_SERVER_ORIGIN}/apps/${props.slug}?theme=${state.theme}`;
+    const url = previewUrlFor({ slug: props.slug, content_type: "app" }) ?? `${devServerOrigin()}/apps/${props.slug}`;
+    return `${url}?theme=${state.theme}`;
   }
 
   function syncIframeTheme() {
     try {
       iframeRef?.contentWindow?.postMessage(
         { type: "setTheme", theme: state.theme },
-        DEV_SERVER_ORIGIN,
+        devServerOrigin(),
       );
     } catch { /* cross-origin — ignored */ }
   }
@@ -223,7 +281,7 @@ export function AppDetailView(props: Props) {
             </button>
 
             {entry.is_draft ? (
-              <button class="btn btn-primary" onClick={handlePublish} disabled={publishing()}>
+              <button class="btn btn-primary" onClick={() => handlePublish()} disabled={publishing()}>
                 {publishing() ? "Publishing..." : "Publish"}
               </button>
             ) : (
@@ -234,7 +292,7 @@ export function AppDetailView(props: Props) {
                   </button>
                 </Show>
                 <Show when={entry.has_changed}>
-                  <button class="btn btn-primary" onClick={handlePublish} disabled={publishing()}>
+                  <button class="btn btn-primary" onClick={() => handlePublish()} disabled={publishing()}>
                     {publishing() ? "Publishing..." : "Publish"}
                   </button>
                 </Show>
@@ -297,11 +355,22 @@ export function AppDetailView(props: Props) {
               title="Unpublish?"
               message={`This will revert "${entry.title}" to draft status.`}
               confirmLabel="Unpublish"
-              onConfirm={handleUnpublish}
+              onConfirm={() => handleUnpublish()}
               onCancel={() => setShowUnpubConfirm(false)}
             />
           )}
 
+          {scanReport() && (
+            <ConfirmDialog
+              title={scanReport()!.action === "publish" ? "Publish anyway?" : "Unpublish anyway?"}
+              message={scanReport()!.message}
+              confirmLabel={scanReport()!.action === "publish" ? "Publish anyway" : "Unpublish anyway"}
+              danger
+              onConfirm={() => (scanReport()!.action === "publish" ? handlePublish(true) : handleUnpublish(true))}
+              onCancel={() => setScanReport(null)}
+            />
+          )}
+
           {showRollbackConfirm() && (
             <ConfirmDialog
               title="Rollback changes?"
@@ -5,8 +5,7 @@
  * "Am I shipping consistently?", and "What should I do next?"
  */
 import { createSignal, createMemo, For, Show, onMount, onCleanup } from "solid-js";
-import { state, openEntry, navigate, devHealth, prodHealth } from "../lib/store";
-import { DEV_SERVER_ORIGIN } from "../lib/commands";
+import { state, openEntry, navigate, devHealth, prodHealth, devServerOrigin, operationQueue, pendingPushes, retryPushes, addToast } from "../lib/store";
 import type { CfDeploymentInfo } from "../lib/commands";
 import { getCachedDeployment, refreshDeployment, getCachedAnalytics } from "../lib/stores/cfcache";
 
@@ -109,6 +108,19 @@ export function ContentListView() {
   });
   onCleanup(() => clearInterval(deployInterval));
 
+  async function pushNow() {
+    const repoPath = state.config.repo_path;
+    if (!repoPath) return;
+    try {
+      await retryPushes(repoPath);
+      const left = pendingPushes().length;
+      if (left === 0) addToast("Pushed");
+      else addToast(`Still unable to push ${left} commit${left === 1 ? "" : "s"}`, "warn");
+    } catch (e) {
+      addToast(`Push failed: ${e}`, "error");
+    }
+  }
+
   // --- Stats ---
   const totalCount = createMemo(() => state.entries.length);
   const publishedCount = createMemo(() => state.entries.filter((e) => !e.is_draft).length);
@@ -191,7 +203,7 @@ export function ContentListView() {
       <div class="mc-status-bar">
         <div class="mc-status-group">
           <span class={`mc-status-dot ${devHealth()?.ok ? "up" : "down"}`} />
-          <a class="mc-status-link" href={DEV_SERVER_ORIGIN} target="_blank" rel="noopener noreferrer">dev server</a>
+          <a class="mc-status-link" href={devServerOrigin()} target="_blank" rel="noopener noreferrer">dev server</a>
           <span class="mc-status-label">{devHealth()?.ok ? "up" : "down"}</span>
         </div>
         <div class="mc-status-group">
@@ -199,6 +211,24 @@ export function ContentListView() {
           <a class="mc-status-link" href="https://fpl0.io" target="_blank" rel="noopener noreferrer">fpl0.io</a>
           <span class="mc-status-label">{prodHealth()?.ok ? "reachable" : "unreachable"}</span>
         </div>
+        <Show when={operationQueue().length > 0}>
+          <div
+            class="mc-status-group"
+            title={operationQueue()
+              .map((m) => `${m.kind.replace("_", " ")} ${m.slug}${m.running ? " (running)" : ""}`)
+              .join("\n")}
+          >
+            <span class="mc-status-dot busy" />
+            <span class="mc-status-label">{operationQueue().length} queued</span>
+          </div>
+        </Show>
+        <Show when={pendingPushes().length > 0}>
+          <div class="mc-status-group" title={pendingPushes()[0].last_error ?? "Waiting for the git remote"}>
+            <span class="mc-status-dot busy" />
+            <span class="mc-status-label">{pendingPushes().length} unpushed</span>
+            <button class="mc-status-action" onClick={pushNow}>push now</button>
+          </div>
+        </Show>
         <Show when={cfConfigured()}>
           <Show when={deployment()}>
             {(dep) => (
@@ -1,18 +1,19 @@
 import { createSignal, For, onMount, onCleanup } from "solid-js";
-import { createPost, createApp } from "../lib/commands";
+import { createPost, createEntry } from "../lib/commands";
 import {
   state,
+  collections,
   refreshEntries,
   openEntry,
   addToast,
-  suppressFsChange,
 } from "../lib/store";
 
 const SUMMARY_MIN = 50;
 const SUMMARY_MAX = 360;
 
 export function CreateView() {
-  const [contentType, setContentType] = createSignal<"post" | "app">("post");
+  /** Target collection id. */
+  const [contentType, setContentType] = createSignal("post");
   const [title, setTitle] = createSignal("");
   const [slug, setSlug] = createSignal("");
   const [summary, setSummary] = createSignal("");
@@ -69,7 +70,6 @@ export function CreateView() {
     }
 
     setCreating(true);
-    suppressFsChange();
     try {
       const args = {
         title: title(),
@@ -81,9 +81,8 @@ export function CreateView() {
       const entry =
         contentType() === "post"
           ? await createPost(repoPath, args)
-          : await createApp(repoPath, args);
+          : await createEntry(repoPath, contentType(), args);
 
-      suppressFsChange();
       await refreshEntries();
       addToast(`Created ${contentType()}: ${entry.title}`);
       openEntry(entry);
@@ -122,23 +121,23 @@ export function CreateView() {
       <div class="metadata-field create-field-lg">
         <span class="label" id="create-type-label">Type</span>
         <div class="filter-group" role="group" aria-labelledby="create-type-label">
-          <button
-            class={`filter-chip ${contentType() === "post" ? "active" : ""}`}
-            onClick={() => setContentType("post")}
-          >
-            Post
-          </button>
-          <button
-            class={`filter-chip ${contentType() === "app" ? "active" : ""}`}
-            onClick={() => setContentType("app")}
-          >
-            App
-          </button>
+          <For each={collections()}>
+            {(c) => (
+              <button
+                class={`filter-chip ${contentType() === c.id ? "active" : ""}`}
+                onClick={() => setContentType(c.id)}
+              >
+                {c.label ?? c.id}
+              </button>
+            )}
+          </For>
         </div>
         <p class="create-type-desc">
           {contentType() === "post"
             ? "A long-form MDX article with full editor support, metadata, and publishing workflow."
-            : "An interactive app entry with live iframe preview and VS Code integration."}
+            : contentType() === "app"
+              ? "An interactive app entry with live iframe preview and VS Code integration."
+              : "A content entry with frontmatter, editor support, and publishing workflow."}
         </p>
       </div>
 
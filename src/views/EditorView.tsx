@@ -1,11 +1,14 @@
 import { createSignal, createEffect, onMount, onCleanup, Show } from "solid-js";
 import type { ContentEntry } from "../lib/commands";
-import { readFile, writeFile, DEV_SERVER_ORIGIN } from "../lib/commands";
+import { readFile, writeFile, restoreSnapshot, isCommandError } from "../lib/commands";
 import { parseMdxFile, serializeMdxFile } from "../lib/mdx";
 import { setYamlField, escapeYamlValue } from "../lib/yaml";
 import {
   state,
+  setState,
   activeEntry,
+  devServerOrigin,
+  previewUrlFor,
   navigate,
   publishEntry,
   unpublishEntry,
@@ -16,9 +19,10 @@ import {
   patchEntry,
   addToast,
   updateToast,
+  trackOperation,
+  toastProgress,
   lastExternalChange,
   clearExternalChange,
-  suppressFsChange,
   setNavigationGuard,
   confirmNavigation,
   cancelNavigation,
@@ -57,6 +61,7 @@ export function EditorView(props: Props) {
   const [showDeleteConfirm, setShowDeleteConfirm] = createSignal(false);
   const [showUnpubConfirm, setShowUnpubConfirm] = createSignal(false);
   const [showRollbackConfirm, setShowRollbackConfirm] = createSignal(false);
+  const [scanReport, setScanReport] = createSignal<{ action: "publish" | "unpublish"; message: string } | null>(null);
   const [showReloadConfirm, setShowReloadConfirm] = createSignal(false);
   const [showPublishConfirm, setShowPublishConfirm] = createSignal(false);
   const [publishing, setPublishing] = createSignal(false);
@@ -70,6 +75,9 @@ export function EditorView(props: Props) {
   let saving = false;
   let pendingResave = false;
   let currentBody: string | null = null;
+  // Version of the file as last read or written; saves are rejected if the disk moved on
+  let fileVersion: string | undefined;
+  let conflictPending = false;
   let editorMethods: EditorMethods | null = null;
   let iframeRef: HTMLIFrameElement | undefined;
 
@@ -82,7 +90,8 @@ export function EditorView(props: Props) {
     filePath = e.file_path;
     setNavigationGuard(true);
     try {
-      const raw = await readFile(filePath);
+      const { content: raw, version } = await readFile(filePath);
+      fileVersion = version;
       const { yaml: y, body, unknownImports: ui } = parseMdxFile(raw);
       setYaml(y);
       setUnknownImports(ui);
@@ -130,7 +139,7 @@ export function EditorView(props: Props) {
   }
 
   async function saveToDisk() {
-    if (currentBody == null || !filePath) return;
+    if (currentBody == null || !filePath || conflictPending) return;
     if (saving) {
       pendingResave = true;
       return;
@@ -141,9 +150,18 @@ export function EditorView(props: Props) {
     while (true) {
       setSaveState("saving");
       try {
-        suppressFsChange();
         const mdx = serializeMdxFile(yaml(), currentBody, unknownImports());
-        await writeFile(filePath, mdx);
+        const result = await writeFile(filePath, mdx, fileVersion);
+        if (result.status === "conflict") {
+          // Changed on disk since we read it — let the user pick via the external-change banner
+          conflictPending = true;
+          pendingResave = false;
+          setSaveState("unsaved");
+          setShowExternalBanner(true);
+          addToast("Not saved: the file changed on disk. Reload it or keep your version.", "warn");
+          break;
+        }
+        fileVersion = result.version;
         // Only report "saved" if no further changes queued
         if (!pendingResave) {
           setSaveState("saved");
@@ -192,40 +210,75 @@ export function EditorView(props: Props) {
   async function syncYamlFromDisk() {
     if (!filePath) return;
     try {
-      const raw = await readFile(filePath);
+      const { content: raw, version } = await readFile(filePath);
+      fileVersion = version;
       const { yaml: y, unknownImports: ui } = parseMdxFile(raw);
       setYaml(y);
       setUnknownImports(ui);
     } catch { /* entry may have been deleted — noop is intentional */ }
   }
 
-  async function handlePublish() {
+  async function handlePublish(allowFindings = false) {
     if (!state.config.repo_path) return;
+    setScanReport(null);
     setPublishing(true);
     const tid = addToast("Publishing...", "warn");
     await saveToDisk();
     try {
-      const updated = await publishEntry(props.slug);
+      const updated = await trackOperation(
+        (id) => publishEntry(props.slug, allowFindings, id),
+        toastProgress(tid, "Publishing"),
+      );
       await syncYamlFromDisk();
-      updateToast(tid, `Published: ${updated.title}`, "success");
+      if (updated.pending_push) {
+        updateToast(tid, `Published locally: ${updated.title}. It will be pushed once the remote is reachable.`, "warn");
+      } else {
+        updateToast(tid, `Published: ${updated.title}`, "success");
+      }
     } catch (e) {
+      if (isCommandError(e, "scan_blocked")) {
+        updateToast(tid, "Publish blocked by the pre-commit scan", "warn");
+        setScanReport({ action: "publish", message: e.message });
+        return;
+      }
+      if (isCommandError(e, "cancelled")) {
+        updateToast(tid, e.message, "warn");
+        return;
+      }
       updateToast(tid, `Publish failed: ${e}`, "error");
     } finally {
       setPublishing(false);
     }
   }
 
-  async function handleUnpublish() {
+  async function handleUnpublish(allowFindings = false) {
     if (!state.config.repo_path) return;
     setShowUnpubConfirm(false);
+    setScanReport(null);
     setPublishing(true);
     const tid = addToast("Unpublishing...", "warn");
     await saveToDisk();
     try {
-      const updated = await unpublishEntry(props.slug);
+      const updated = await trackOperation(
+        (id) => unpublishEntry(props.slug, allowFindings, id),
+        toastProgress(tid, "Unpublishing"),
+      );
       await syncYamlFromDisk();
-      updateToast(tid, `Unpublished: ${updated.title}`, "success");
+      if (updated.pending_push) {
+        updateToast(tid, `Unpublished locally: ${updated.title}. It will be pushed once the remote is reachable.`, "warn");
+      } else {
+        updateToast(tid, `Unpublished: ${updated.title}`, "success");
+      }
     } catch (e) {
+      if (isCommandError(e, "scan_blocked")) {
+        updateToast(tid, "Unpublish blocked by the pre-commit scan", "warn");
+        setScanReport({ action: "unpublish", message: e.message });
+        return;
+      }
+      if (isCommandError(e, "cancelled")) {
+        updateToast(tid, e.message, "warn");
+        return;
+      }
       updateToast(tid, `Unpublish failed: ${e}`, "error");
     } finally {
       setPublishing(false);
@@ -249,7 +302,8 @@ export function EditorView(props: Props) {
       const updated = await rollbackEntry(props.slug);
       await syncYamlFromDisk();
       // Also update editor content
-      const raw = await readFile(updated.file_path);
+      const { content: raw, version } = await readFile(updated.file_path);
+      fileVersion = version;
       const { body, unknownImports: ui } = parseMdxFile(raw);
       setUnknownImports(ui);
       currentBody = body;
@@ -287,9 +341,13 @@ export function EditorView(props: Props) {
     setPublishing(true);
     const tid = addToast("Deleting...", "warn");
     try {
-      await deleteEntry(props.slug);
+      await trackOperation((id) => deleteEntry(props.slug, id), toastProgress(tid, "Deleting"));
       updateToast(tid, `Deleted: ${props.slug}`, "success");
     } catch (e) {
+      if (isCommandError(e, "cancelled")) {
+        updateToast(tid, "Delete cancelled", "warn");
+        return;
+      }
       updateToast(tid, `Delete failed: ${e}`, "error");
     } finally {
       setPublishing(false);
@@ -297,14 +355,16 @@ export function EditorView(props: Props) {
   }
 
   function previewUrl() {
-    return `${DEV_SERVER_ORIGIN}/blog/${props.slug}?theme=${state.theme}`;
+    const type = activeEntry()?.content_type ?? "post";
+    const url = previewUrlFor({ slug: props.slug, content_type: type });
+    return url ? `${url}?theme=${state.theme}` : "about:blank";
   }
 
   function syncIframeTheme() {
     try {
       iframeRef?.contentWindow?.postMessage(
         { type: "setTheme", theme: state.theme },
-        DEV_SERVER_ORIGIN,
+        devServerOrigin(),
       );
     } catch { /* cross-origin — ignored */ }
   }
@@ -387,18 +447,20 @@ export function EditorView(props: Props) {
 
   // External change detection
   createEffect(() => {
-    const paths = lastExternalChange();
-    if (!paths || !filePath) return;
-    if (paths.some((p) => p === filePath || filePath.endsWith("/" + p) || p.endsWith("/" + filePath.split("/").pop()!))) {
+    const changes = lastExternalChange();
+    if (!changes || !filePath) return;
+    if (changes.some((c) => c.path === filePath)) {
       setShowExternalBanner(true);
     }
   });
 
-  async function reloadFromDisk() {
+  async function reloadFromDisk(load = () => readFile(filePath)) {
     if (!filePath) return;
     if (saveTimeout) { clearTimeout(saveTimeout); saveTimeout = null; }
     try {
-      const raw = await readFile(filePath);
+      const { content: raw, version } = await load();
+      fileVersion = version;
+      conflictPending = false;
       const { yaml: y, body, unknownImports: ui } = parseMdxFile(raw);
       setYaml(y);
       setUnknownImports(ui);
@@ -418,6 +480,34 @@ export function EditorView(props: Props) {
     clearExternalChange();
   }
 
+  // Autosave from an interrupted session that is newer than the file on disk
+  const recovery = () => state.recoverable.find((s) => s.path === filePath) ?? null;
+
+  function dismissRecovery() {
+    setState("recoverable", (list) => list.filter((s) => s.path !== filePath));
+  }
+
+  async function restoreRecovery() {
+    const snap = recovery();
+    if (!snap) return;
+    await reloadFromDisk(() => restoreSnapshot(filePath, snap.id));
+    dismissRecovery();
+  }
+
+  /** Keep the editor's version: take the file on disk as the new base and save over it. */
+  async function keepMine() {
+    try {
+      fileVersion = (await readFile(filePath)).version;
+    } catch {
+      fileVersion = undefined; // deleted externally — recreate it
+    }
+    conflictPending = false;
+    setShowExternalBanner(false);
+    clearExternalChange();
+    setSaveState("unsaved");
+    scheduleSave();
+  }
+
   // Reload with conflict guard
   function handleReloadClick() {
     if (saveState() !== "saved") {
@@ -472,11 +562,21 @@ export function EditorView(props: Props) {
             </button>
           </DetailBar>
 
+          <Show when={!loading() && recovery()}>
+            {(snap) => (
+              <div class="external-change-banner">
+                <span>Unsaved changes from {new Date(snap().taken_at).toLocaleString()} were found from a previous session.</span>
+                <button class="btn btn-primary btn-sm" onClick={restoreRecovery}>Restore</button>
+                <button class="btn btn-sm" onClick={dismissRecovery}>Discard</button>
+              </div>
+            )}
+          </Show>
+
           <Show when={showExternalBanner()}>
             <div class="external-change-banner">
               <span>This file was modified externally.</span>
               <button class="btn btn-primary btn-sm" onClick={handleReloadClick}>Reload from disk</button>
-              <button class="btn btn-sm" onClick={() => { setShowExternalBanner(false); clearExternalChange(); setSaveState("unsaved"); scheduleSave(); }} title="Keep your version">Keep mine</button>
+              <button class="btn btn-sm" onClick={keepMine} title="Keep your version">Keep mine</button>
             </div>
           </Show>
 
@@ -546,11 +646,22 @@ export function EditorView(props: Props) {
               title="Unpublish?"
               message={`This will revert "${entry.title}" to draft status.`}
               confirmLabel="Unpublish"
-              onConfirm={handleUnpublish}
+              onConfirm={() => handleUnpublish()}
               onCancel={() => setShowUnpubConfirm(false)}
             />
           )}
 
+          {scanReport() && (
+            <ConfirmDialog
+              title={scanReport()!.action === "publish" ? "Publish anyway?" : "Unpublish anyway?"}
+              message={scanReport()!.message}
+              confirmLabel={scanReport()!.action === "publish" ? "Publish anyway" : "Unpublish anyway"}
+              danger
+              onConfirm={() => (scanReport()!.action === "publish" ? handlePublish(true) : handleUnpublish(true))}
+              onCancel={() => setScanReport(null)}
+            />
+          )}
+
           {showRollbackConfirm() && (
             <ConfirmDialog
               title="Rollback changes?"
@@ -3,9 +3,10 @@
  * Search, type/status/tag filters, sort options, paginated list.
  */
 import { createSignal, createMemo, For, Show, onMount } from "solid-js";
-import { state, openEntry, setState } from "../lib/store";
+import { state, openEntry, setState, collections } from "../lib/store";
 
-type TypeFilter = "all" | "post" | "app";
+/** "all" or a collection id. */
+type TypeFilter = string;
 type StatusFilter = "all" | "draft" | "published" | "changed";
 type SortBy = "created" | "modified" | "published" | "title";
 
@@ -139,17 +140,14 @@ export function LibraryView() {
   const filterCounts = createMemo(() => {
     const sf = statusFilter();
     const tf = typeFilter();
-    let typeAll = 0,
-      typePost = 0,
-      typeApp = 0;
+    let typeAll = 0;
+    const typeCounts: Record<string, number> = {};
     let statusAll = 0,
       statusDraft = 0,
       statusPublished = 0,
       statusChanged = 0;
 
     for (const e of state.entries) {
-      const isPost = e.content_type === "post";
-      const isApp = e.content_type === "app";
       const isDraft = e.is_draft;
       const isChanged = !isDraft && e.has_changed;
       const matchesStatus =
@@ -159,8 +157,7 @@ export function LibraryView() {
 
       if (matchesStatus) {
         typeAll++;
-        if (isPost) typePost++;
-        if (isApp) typeApp++;
+        typeCounts[e.content_type] = (typeCounts[e.content_type] ?? 0) + 1;
       }
       if (matchesType) {
         statusAll++;
@@ -172,8 +169,7 @@ export function LibraryView() {
 
     return {
       typeAll,
-      typePost,
-      typeApp,
+      typeCounts,
       statusAll,
       statusDraft,
       statusPublished,
@@ -279,18 +275,16 @@ export function LibraryView() {
           >
             All {filterCounts().typeAll}
           </button>
-          <button
-            class={`filter-chip ${typeFilter() === "post" ? "active" : ""}`}
-            onClick={() => setTypeAndReset("post")}
-          >
-            Posts {filterCounts().typePost}
-          </button>
-          <button
-            class={`filter-chip ${typeFilter() === "app" ? "active" : ""}`}
-            onClick={() => setTypeAndReset("app")}
-          >
-            Apps {filterCounts().typeApp}
-          </button>
+          <For each={collections()}>
+            {(c) => (
+              <button
+                class={`filter-chip ${typeFilter() === c.id ? "active" : ""}`}
+                onClick={() => setTypeAndReset(c.id)}
+              >
+                {c.label ?? c.id} {filterCounts().typeCounts[c.id] ?? 0}
+              </button>
+            )}
+          </For>
         </div>
 
         <div class="filter-group">
@@ -384,8 +378,8 @@ export function LibraryView() {
               <li class="post-item">
                 <div class="post-date-col">
                   <span
-                    class={`post-status-dot ${entry.is_draft ? "draft" : entry.has_changed ? "changed" : "published"}`}
-                    title={entry.is_draft ? "Draft" : entry.has_changed ? "Modified" : "Published"}
+                    class={`post-status-dot ${entry.pending_push ? "pending" : entry.is_draft ? "draft" : entry.has_changed ? "changed" : "published"}`}
+                    title={entry.pending_push ? "Committed locally, pending push" : entry.is_draft ? "Draft" : entry.has_changed ? "Modified" : "Published"}
                   />
                   <span class="post-date">{displayDate(entry)}</span>
                 </div>
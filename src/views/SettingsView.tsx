@@ -1,14 +1,32 @@
-import { createSignal, Switch, Match, onMount, onCleanup } from "solid-js";
+import { createSignal, For, Switch, Match, onMount, onCleanup } from "solid-js";
 import { open } from "@tauri-apps/plugin-dialog";
-import { validateRepoPath, testCfConnection } from "../lib/commands";
-import { state, updateConfig, addToast } from "../lib/store";
+import {
+  validateRepoPath,
+  testCfConnection,
+  getSecretsStatus,
+  unlockSecrets,
+  rekeySecrets,
+  type SecretsStatus,
+} from "../lib/commands";
+import {
+  state,
+  updateConfig,
+  updateApiToken,
+  addToast,
+  switchProfile,
+  createProfile,
+  deleteProfile,
+} from "../lib/store";
+import { MigrationsPanel } from "../components/MigrationsPanel";
+import { BulkEditPanel } from "../components/BulkEditPanel";
+import { TagsPanel } from "../components/TagsPanel";
 
-type SettingsSection = "project" | "cloudflare" | "shortcuts";
+type SettingsSection = "project" | "cloudflare" | "shortcuts" | "content";
 
 export function SettingsView() {
   const [activeSection, setActiveSection] = createSignal<SettingsSection>("project");
 
-  const sectionMap: Record<string, SettingsSection> = { "1": "project", "2": "cloudflare", "3": "shortcuts" };
+  const sectionMap: Record<string, SettingsSection> = { "1": "project", "2": "cloudflare", "3": "shortcuts", "4": "content" };
 
   function handleKeyDown(e: KeyboardEvent) {
     if ((e.metaKey || e.ctrlKey) && sectionMap[e.key]) {
@@ -40,16 +58,75 @@ export function SettingsView() {
   const [cfProjectName, setCfProjectName] = createSignal(state.config.cf_project_name ?? "");
   const [cfDomain, setCfDomain] = createSignal(state.config.cf_domain ?? "");
   const [cfApiToken, setCfApiToken] = createSignal(state.config.cf_api_token ?? "");
+  const [cfSiteTag, setCfSiteTag] = createSignal(state.config.cf_site_tag ?? "");
   const [cfTesting, setCfTesting] = createSignal(false);
 
+  // --- Credential store ---
+  const [secrets, setSecrets] = createSignal<SecretsStatus | null>(null);
+  const [passphrase, setPassphrase] = createSignal("");
+
+  onMount(() => {
+    getSecretsStatus().then(setSecrets).catch(() => {});
+  });
+
+  async function runSecretsAction(action: () => Promise<SecretsStatus>, message: string) {
+    try {
+      setSecrets(await action());
+      setPassphrase("");
+      addToast(message);
+    } catch (e) {
+      addToast(e instanceof Error ? e.message : String(e), "error");
+    }
+  }
+
+  // --- Profiles ---
+  const [newProfileName, setNewProfileName] = createSignal("");
+
+  /** Reload the Cloudflare form from the (newly) active profile. */
+  function syncCfFields() {
+    setCfAccountId(state.config.cf_account_id ?? "");
+    setCfProjectName(state.config.cf_project_name ?? "");
+    setCfDomain(state.config.cf_domain ?? "");
+    setCfApiToken(state.config.cf_api_token ?? "");
+    setCfSiteTag(state.config.cf_site_tag ?? "");
+  }
+
+  async function runProfileAction(action: () => Promise<void>, message: string) {
+    try {
+      await action();
+      syncCfFields();
+      addToast(message);
+    } catch (e) {
+      addToast(e instanceof Error ? e.message : String(e), "error");
+    }
+  }
+
+  function addProfile() {
+    const name = newProfileName().trim();
+    if (!name) return;
+    runProfileAction(() => createProfile(name), `Profile "${name}" created`).then(() =>
+      setNewProfileName(""),
+    );
+  }
+
   async function saveCfConfig() {
     await updateConfig({
       cf_account_id: cfAccountId() || null,
       cf_project_name: cfProjectName() || null,
       cf_domain: cfDomain() || null,
-      cf_api_token: cfApiToken() || null,
+      cf_site_tag: cfSiteTag() || null,
       cf_zone_id: null, // Reset cached zone_id so it re-discovers
     });
+    // The token field shows a masked placeholder; only send real edits.
+    if (cfApiToken() !== (state.config.cf_api_token ?? "")) {
+      try {
+        await updateApiToken(cfApiToken());
+        setCfApiToken(state.config.cf_api_token ?? "");
+      } catch (e) {
+        addToast(e instanceof Error ? e.message : String(e), "error");
+        return;
+      }
+    }
     addToast("Cloudflare settings saved");
   }
 
@@ -90,6 +167,12 @@ export function SettingsView() {
           >
             Shortcuts
           </button>
+          <button
+            class={`settings-nav-item ${activeSection() === "content" ? "is-active" : ""}`}
+            onClick={() => setActiveSection("content")}
+          >
+            Content
+          </button>
         </nav>
       </aside>
 
@@ -105,6 +188,59 @@ export function SettingsView() {
               </header>
 
               <div class="settings-group">
+                <div class="settings-row">
+                  <div class="settings-info">
+                    <label class="settings-label" for="site-profile">Site Profile</label>
+                    <p class="settings-hint">
+                      Each profile has its own repository and Cloudflare project.
+                    </p>
+                  </div>
+                  <div class="settings-value-with-action">
+                    <select
+                      id="site-profile"
+                      class="settings-input"
+                      value={state.config.active_profile ?? ""}
+                      onChange={(e) => {
+                        const name = e.currentTarget.value;
+                        runProfileAction(() => switchProfile(name), `Switched to "${name}"`);
+                      }}
+                    >
+                      <For each={state.config.profiles}>
+                        {(name) => <option value={name}>{name}</option>}
+                      </For>
+                    </select>
+                    <button
+                      class="btn"
+                      disabled={state.config.profiles.length <= 1}
+                      onClick={() => {
+                        const name = state.config.active_profile;
+                        if (name) runProfileAction(() => deleteProfile(name), `Profile "${name}" deleted`);
+                      }}
+                    >
+                      Delete
+                    </button>
+                  </div>
+                </div>
+
+                <div class="settings-row">
+                  <div class="settings-info">
+                    <label class="settings-label" for="new-profile">New Profile</label>
+                  </div>
+                  <div class="settings-value-with-action">
+                    <input
+                      id="new-profile"
+                      class="settings-input"
+                      type="text"
+                      value={newProfileName()}
+                      onInput={(e) => setNewProfileName(e.currentTarget.value)}
+                      placeholder="e.g. notes-site"
+                    />
+                    <button class="btn" onClick={addProfile}>
+                      Add Profile
+                    </button>
+                  </div>
+                </div>
+
                 <div class="settings-row">
                   <div class="settings-info">
                     <label class="settings-label">Content Directory</label>
@@ -178,8 +314,70 @@ export function SettingsView() {
                       type="password"
                       value={cfApiToken()}
                       onInput={(e) => setCfApiToken(e.currentTarget.value)}
-                      placeholder="Pages:Read & Analytics:Read required"
+                      placeholder="Pages:Read & Analytics:Read required — clear to remove"
+                    />
+                  </div>
+
+                  <div class="settings-field">
+                    <label class="settings-label" for="cf-site-tag">Web Analytics Site Tag</label>
+                    <input
+                      id="cf-site-tag"
+                      class="settings-input"
+                      type="text"
+                      value={cfSiteTag()}
+                      onInput={(e) => setCfSiteTag(e.currentTarget.value)}
+                      placeholder="Optional — enables page views & Web Vitals"
+                    />
+                  </div>
+                </div>
+
+                <div class="settings-row">
+                  <div class="settings-info">
+                    <label class="settings-label" for="secrets-passphrase">Credential Encryption</label>
+                    <p class="settings-hint">
+                      {secrets()?.key_source === "passphrase"
+                        ? secrets()?.unlocked
+                          ? "Tokens are encrypted with your passphrase."
+                          : "Tokens are locked — enter your passphrase to unlock."
+                        : "Tokens are encrypted with a local key file."}
+                    </p>
+                  </div>
+                  <div class="settings-value-with-action">
+                    <input
+                      id="secrets-passphrase"
+                      class="settings-input"
+                      type="password"
+                      value={passphrase()}
+                      onInput={(e) => setPassphrase(e.currentTarget.value)}
+                      placeholder="Passphrase"
                     />
+                    <Switch>
+                      <Match when={secrets()?.key_source === "passphrase" && !secrets()?.unlocked}>
+                        <button
+                          class="btn"
+                          disabled={!passphrase()}
+                          onClick={() => runSecretsAction(() => unlockSecrets(passphrase()), "Credentials unlocked")}
+                        >
+                          Unlock
+                        </button>
+                      </Match>
+                      <Match when={secrets()}>
+                        <button
+                          class="btn"
+                          disabled={!passphrase()}
+                          onClick={() => runSecretsAction(() => rekeySecrets(passphrase()), "Passphrase set")}
+                        >
+                          Set Passphrase
+                        </button>
+                        <button
+                          class="btn"
+                          disabled={secrets()?.key_source !== "passphrase"}
+                          onClick={() => runSecretsAction(() => rekeySecrets(null), "Using key file")}
+                        >
+                          Use Key File
+                        </button>
+                      </Match>
+                    </Switch>
                   </div>
                 </div>
 
@@ -300,11 +498,30 @@ export function SettingsView() {
                       <span class="shortcut-context">Settings</span>
                       <kbd class="settings-kbd">⌘3</kbd>
                     </div>
+                    <div class="settings-shortcut-item">
+                      <span class="shortcut-desc">Content</span>
+                      <span class="shortcut-context">Settings</span>
+                      <kbd class="settings-kbd">⌘4</kbd>
+                    </div>
                   </div>
                 </div>
               </div>
             </section>
           </Match>
+
+          <Match when={activeSection() === "content"}>
+            <section class="settings-section">
+              <header class="settings-section-header">
+                <h2>Content Maintenance</h2>
+                <p class="settings-description">
+                  Changes that span many entries, previewed as diffs before anything is written.
+                </p>
+              </header>
+              <MigrationsPanel />
+              <BulkEditPanel />
+              <TagsPanel />
+            </section>
+          </Match>
         </Switch>
       </main>
     </div>